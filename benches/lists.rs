@@ -0,0 +1,108 @@
+//! Comparative benchmark of the `List` implementations on the same generic workloads.
+//!
+//! Run with `cargo bench --features std-lists [-- <filter>]`.
+
+use std::collections::{LinkedList, VecDeque};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use plain_ds::{List, SinglyLinkedList, SortedList};
+
+const SIZE: u64 = 10_000;
+const RUNS: usize = 5;
+
+type Workload<L> = fn(L) -> L;
+
+fn push_back<L: List<u64>>(mut list: L) -> L {
+    for value in 0..SIZE {
+        list.push(value);
+    }
+    list
+}
+
+fn iterate<L: List<u64>>(list: L) -> L {
+    black_box(list.iter().sum::<u64>());
+    list
+}
+
+fn indexed_access<L: List<u64>>(list: L) -> L {
+    let mut sum = 0;
+    for index in 0..list.len() {
+        sum += list.get(index).copied().unwrap_or_default();
+    }
+    black_box(sum);
+    list
+}
+
+fn find<L: List<u64>>(list: L) -> L {
+    for value in (0..SIZE).step_by(1000) {
+        black_box(list.find(&value));
+    }
+    list
+}
+
+fn remove_middle<L: List<u64>>(mut list: L) -> L {
+    for _ in 0..100 {
+        black_box(list.remove(list.len() / 2).ok());
+    }
+    list
+}
+
+fn pop_front<L: List<u64>>(mut list: L) -> L {
+    while let Some(value) = list.pop_front() {
+        black_box(value);
+    }
+    list
+}
+
+// Returns the best time of `RUNS` runs of `workload` over the list filled by `setup`
+fn measure<L>(setup: impl Fn() -> L, workload: Workload<L>) -> Duration {
+    (0..RUNS)
+        .map(|_| {
+            let list = setup();
+            let start = Instant::now();
+            let list = workload(list);
+            let elapsed = start.elapsed();
+            drop(black_box(list));
+            elapsed
+        })
+        .min()
+        .unwrap_or_default()
+}
+
+fn bench<L: List<u64>>(name: &str, new: fn() -> L, filter: Option<&str>) {
+    let workloads: [(&str, Workload<L>); 6] = [
+        ("push", push_back),
+        ("iterate", iterate),
+        ("get", indexed_access),
+        ("find", find),
+        ("remove", remove_middle),
+        ("pop_front", pop_front),
+    ];
+    for (workload_name, workload) in workloads {
+        let id = format!("{workload_name}/{name}");
+        if filter.is_some_and(|filter| !id.contains(filter)) {
+            continue;
+        }
+        let time = if workload_name == "push" {
+            measure(new, workload)
+        } else {
+            measure(|| push_back(new()), workload)
+        };
+        println!("{id:<32} {time:>12.3?}");
+    }
+}
+
+fn main() {
+    // `cargo bench` passes `--bench` and the optional filter
+    let filter = std::env::args().skip(1).find(|arg| !arg.starts_with("--"));
+    let filter = filter.as_deref();
+
+    println!("{} elements, best of {} runs", SIZE, RUNS);
+    bench("SinglyLinkedList", SinglyLinkedList::new, filter);
+    bench("SortedList", SortedList::new, filter);
+    bench("SortedList (indexed)", || SortedList::new().with_index(), filter);
+    bench("Vec", Vec::new, filter);
+    bench("VecDeque", VecDeque::new, filter);
+    bench("LinkedList", LinkedList::new, filter);
+}
@@ -9,6 +9,33 @@ pub enum DSError {
     NotFile { path: PathBuf },
     NotDirectory { path: PathBuf },
     PathNotFound { path: PathBuf },
+    AlreadyLinked,
+    InvalidHandle,
+    CapacityExceeded { capacity: usize },
 }
 
-pub type Result<T> = std::result::Result<T, DSError>;
\ No newline at end of file
+pub type Result<T> = std::result::Result<T, DSError>;
+
+/// Describes the broken internal invariant of a data structure
+/// (see `check_invariants()` of the lists and `FileTree`).
+#[derive(Debug, PartialEq)]
+pub enum InvariantViolation {
+    /// The stored size differs from the actual number of nodes.
+    SizeMismatch { size: usize, actual: usize },
+    /// The `last` pointer does not point to the tail node (whose `next` is null).
+    WrongLast,
+    /// The node chain contains a cycle.
+    Cycle,
+    /// The cached position does not point to the node with its index.
+    StaleFinger { index: usize },
+    /// A registered handle refers to a node which is not in the list.
+    StaleHandle,
+    /// The element at `index` is less than the previous one in a sorted list.
+    NotSorted { index: usize },
+    /// An express link on `level` of the span index skips a wrong number of nodes.
+    WrongSpan { level: usize },
+    /// The directory has an allocated but empty container of files or subdirectories.
+    EmptyAllocated { path: PathBuf },
+    /// The entry name is not a single normal path component.
+    InvalidName { path: PathBuf },
+}
\ No newline at end of file
@@ -1,4 +1,19 @@
 mod error;
 pub mod utils;
 
-pub use error::{DSError, Result};
+pub use error::{DSError, InvariantViolation, Result};
+
+/// Panics if `check_invariants()` of the value fails.
+/// Does nothing unless it is a debug build with the `check-invariants` feature.
+#[cfg(any(feature = "list", feature = "tree"))]
+macro_rules! debug_check_invariants {
+    ($value:expr) => {
+        #[cfg(all(debug_assertions, feature = "check-invariants"))]
+        if let Err(violation) = $value.check_invariants() {
+            panic!("invariant violated: {violation:?}");
+        }
+    };
+}
+
+#[cfg(any(feature = "list", feature = "tree"))]
+pub(crate) use debug_check_invariants;
@@ -6,10 +6,13 @@ pub mod list;
 #[cfg(feature = "tree")]
 pub mod tree;
 
-pub use core::{DSError, Result};
+pub use core::{DSError, InvariantViolation, Result};
 
 #[cfg(feature = "list")]
-pub use list::{List, SinglyLinkedList, SortedList};
+pub use list::{
+    Deque, IntrusiveList, List, LruCache, PriorityQueue, Queue, RingBuffer, SelfOrganizingList,
+    SinglyLinkedList, SlidingWindow, SortedList, SortedMap, Stack,
+};
 
 #[cfg(feature = "tree")]
 pub use tree::FileTree;
@@ -0,0 +1,222 @@
+//! This module contains double-ended queue adapter implementation.
+
+use super::AdapterIntoIter;
+use crate::list::{List, SinglyLinkedList};
+
+/// A double-ended queue built on [`SinglyLinkedList`].
+///
+/// It exposes only the O(1) operations of the list: items can be pushed to both ends,
+/// but popped only from the front (popping from the end of a singly-linked list is O(n)).
+///
+/// # Type Parameters
+/// * `T`: The type of elements stored in the deque.
+///
+/// # Examples
+/// ```
+/// use plain_ds::list::Deque;
+///
+/// let mut deque = Deque::new();
+/// deque.push_back(2);
+/// deque.push_front(1);
+/// deque.push_back(3);
+///
+/// assert_eq!(deque.front(), Some(&1));
+/// assert_eq!(deque.back(), Some(&3));
+/// assert_eq!(deque.pop_front(), Some(1));
+/// ```
+pub struct Deque<T> {
+    list: SinglyLinkedList<T>,
+}
+
+impl<T> Deque<T> {
+    /// Creates empty deque.
+    pub fn new() -> Self {
+        Self {
+            list: SinglyLinkedList::new(),
+        }
+    }
+
+    /// Returns deque size.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    /// Checks if the deque is empty.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Adds a new item to the front of the deque.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn push_front(&mut self, payload: T) {
+        self.list.push_front(payload);
+    }
+
+    /// Adds a new item to the end of the deque.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn push_back(&mut self, payload: T) {
+        self.list.push(payload);
+    }
+
+    /// Removes the item from the front of the deque and returns it.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+
+    /// Returns the item from the front of the deque.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn front(&self) -> Option<&T> {
+        self.list.head()
+    }
+
+    /// Returns the item from the end of the deque.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn back(&self) -> Option<&T> {
+        self.list.last()
+    }
+
+    /// Returns the item from the front of the deque (the next one to pop).
+    ///
+    /// **Efficiency**: O(1)
+    pub fn peek(&self) -> Option<&T> {
+        self.front()
+    }
+
+    /// Returns the mutable item from the front of the deque (the next one to pop).
+    ///
+    /// **Efficiency**: O(1)
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.list.iter_mut().next()
+    }
+
+    /// Returns an iterator over the items, from the front of the deque to the end.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.list.iter()
+    }
+
+    /// Removes all items from the deque.
+    ///
+    /// **Efficiency**: O(n)
+    pub fn clear(&mut self) {
+        self.list.clear();
+    }
+}
+
+impl<T> Default for Deque<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FromIterator<T> for Deque<T> {
+    /// Pushes the items to the end of the deque in the iteration order.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut deque = Deque::new();
+        deque.extend(iter);
+        deque
+    }
+}
+
+impl<T> Extend<T> for Deque<T> {
+    /// Pushes the items to the end of the deque in the iteration order.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for payload in iter {
+            self.push_back(payload);
+        }
+    }
+}
+
+impl<T> IntoIterator for Deque<T> {
+    type Item = T;
+    type IntoIter = AdapterIntoIter<T>;
+
+    /// Returns an iterator that consumes the deque, from the front to the end.
+    fn into_iter(self) -> Self::IntoIter {
+        AdapterIntoIter::new(self.list)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_creation() {
+        let deque: Deque<u8> = Deque::new();
+        assert_eq!(deque.len(), 0, "not zero length after creation");
+        assert!(deque.is_empty(), "not empty after creation");
+        assert_eq!(deque.front(), None, "not empty front after creation");
+        assert_eq!(deque.back(), None, "not empty back after creation");
+    }
+
+    #[test]
+    fn test_push_both_ends() {
+        let mut deque = Deque::new();
+        deque.push_back(2);
+        deque.push_front(1);
+        deque.push_back(3);
+        deque.push_front(0);
+
+        assert_eq!(deque.len(), 4, "bad length after pushes");
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [0, 1, 2, 3]);
+        assert_eq!(deque.front(), Some(&0));
+        assert_eq!(deque.back(), Some(&3));
+    }
+
+    #[test]
+    fn test_pop_front() {
+        let mut deque: Deque<_> = (1..=3).collect();
+        assert_eq!(deque.pop_front(), Some(1));
+        assert_eq!(deque.pop_front(), Some(2));
+        assert_eq!(deque.pop_front(), Some(3));
+        assert_eq!(deque.pop_front(), None, "pop_front() from empty deque should return None");
+        assert_eq!(deque.back(), None, "back must be reset after the last pop_front()");
+    }
+
+    #[test]
+    fn test_peek_and_peek_mut() {
+        let mut deque = Deque::new();
+        assert_eq!(deque.peek(), None);
+        assert_eq!(deque.peek_mut(), None);
+
+        deque.push_back(5);
+        *deque.peek_mut().unwrap() = 7;
+        assert_eq!(deque.peek(), Some(&7), "peek_mut() must change the front item");
+    }
+
+    #[test]
+    fn test_from_iter_extend_and_into_iter() {
+        let mut deque: Deque<_> = (1..=3).collect();
+        deque.extend([4, 5]);
+        deque.push_front(0);
+
+        let drained: Vec<_> = deque.into_iter().collect();
+        assert_eq!(drained, [0, 1, 2, 3, 4, 5], "into_iter() must yield from the front");
+    }
+
+    #[test]
+    fn test_memory_leaks() {
+        use drop_tracker::DropTracker;
+
+        let mut tracker = DropTracker::new();
+
+        let mut deque: Deque<_> = (0..10).map(|i| tracker.track(i)).collect();
+        deque.push_front(tracker.track(10));
+        let _ = deque.pop_front();
+        assert_eq!(tracker.alive().count(), 10);
+
+        drop(deque);
+        assert_eq!(tracker.alive().count(), 0);
+        assert_eq!(tracker.dropped().count(), 11);
+    }
+}
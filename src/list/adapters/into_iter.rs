@@ -0,0 +1,39 @@
+use crate::list::{List, SinglyLinkedList};
+
+/// An iterator that consumes the [`Stack`](super::Stack), [`Queue`](super::Queue) or
+/// [`Deque`](super::Deque) and yields its items in the removal order.
+///
+/// # Examples
+/// ```
+/// use plain_ds::list::{AdapterIntoIter, Queue};
+///
+/// struct Jobs {
+///     pending: AdapterIntoIter<u32>,
+/// }
+///
+/// let queue: Queue<_> = [1, 2, 3].into_iter().collect();
+/// let mut jobs = Jobs { pending: queue.into_iter() };
+/// assert_eq!(jobs.pending.next(), Some(1));
+/// assert_eq!(jobs.pending.collect::<Vec<_>>(), [2, 3]);
+/// ```
+pub struct AdapterIntoIter<T> {
+    list: SinglyLinkedList<T>,
+}
+
+impl<T> AdapterIntoIter<T> {
+    pub(crate) fn new(list: SinglyLinkedList<T>) -> Self {
+        Self { list }
+    }
+}
+
+impl<T> Iterator for AdapterIntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len(), Some(self.list.len()))
+    }
+}
@@ -0,0 +1,9 @@
+mod deque;
+mod into_iter;
+mod queue;
+mod stack;
+
+pub use deque::Deque;
+pub use into_iter::AdapterIntoIter;
+pub use queue::Queue;
+pub use stack::Stack;
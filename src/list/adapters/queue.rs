@@ -0,0 +1,200 @@
+//! This module contains queue (FIFO) adapter implementation.
+
+use super::AdapterIntoIter;
+use crate::list::{List, SinglyLinkedList};
+
+/// A FIFO queue built on [`SinglyLinkedList`].
+///
+/// It exposes only the O(1) operations of the list: items are pushed to the end
+/// of the list and popped from its front.
+///
+/// # Type Parameters
+/// * `T`: The type of elements stored in the queue.
+///
+/// # Examples
+/// ```
+/// use plain_ds::list::Queue;
+///
+/// let mut queue: Queue<_> = [1, 2].into_iter().collect();
+/// queue.push(3);
+///
+/// assert_eq!(queue.peek(), Some(&1));
+/// assert_eq!(queue.pop(), Some(1));
+/// assert_eq!(queue.peek_back(), Some(&3));
+/// ```
+pub struct Queue<T> {
+    list: SinglyLinkedList<T>,
+}
+
+impl<T> Queue<T> {
+    /// Creates empty queue.
+    pub fn new() -> Self {
+        Self {
+            list: SinglyLinkedList::new(),
+        }
+    }
+
+    /// Returns queue size.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    /// Checks if the queue is empty.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Adds a new item to the end of the queue.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn push(&mut self, payload: T) {
+        self.list.push(payload);
+    }
+
+    /// Removes the item from the front of the queue and returns it.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn pop(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+
+    /// Returns the item from the front of the queue.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn peek(&self) -> Option<&T> {
+        self.list.head()
+    }
+
+    /// Returns the mutable item from the front of the queue.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.list.iter_mut().next()
+    }
+
+    /// Returns the item from the end of the queue (the last pushed one).
+    ///
+    /// **Efficiency**: O(1)
+    pub fn peek_back(&self) -> Option<&T> {
+        self.list.last()
+    }
+
+    /// Returns an iterator over the items, from the front of the queue to the end.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.list.iter()
+    }
+
+    /// Removes all items from the queue.
+    ///
+    /// **Efficiency**: O(n)
+    pub fn clear(&mut self) {
+        self.list.clear();
+    }
+}
+
+impl<T> Default for Queue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FromIterator<T> for Queue<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut queue = Queue::new();
+        queue.extend(iter);
+        queue
+    }
+}
+
+impl<T> Extend<T> for Queue<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for payload in iter {
+            self.push(payload);
+        }
+    }
+}
+
+impl<T> IntoIterator for Queue<T> {
+    type Item = T;
+    type IntoIter = AdapterIntoIter<T>;
+
+    /// Returns an iterator that consumes the queue, from the front to the end.
+    fn into_iter(self) -> Self::IntoIter {
+        AdapterIntoIter::new(self.list)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_creation() {
+        let queue: Queue<u8> = Queue::new();
+        assert_eq!(queue.len(), 0, "not zero length after creation");
+        assert!(queue.is_empty(), "not empty after creation");
+        assert_eq!(queue.peek(), None, "not empty front after creation");
+        assert_eq!(queue.peek_back(), None, "not empty back after creation");
+    }
+
+    #[test]
+    fn test_push_pop_fifo_order() {
+        let mut queue = Queue::new();
+        queue.push(1);
+        queue.push(2);
+        queue.push(3);
+        assert_eq!(queue.len(), 3, "bad length after push()");
+
+        assert_eq!(queue.pop(), Some(1), "pop() must return the first pushed item");
+        assert_eq!(queue.pop(), Some(2));
+        queue.push(4);
+        assert_eq!(queue.pop(), Some(3));
+        assert_eq!(queue.pop(), Some(4));
+        assert_eq!(queue.pop(), None, "pop() from empty queue should return None");
+        assert_eq!(queue.peek_back(), None, "back must be reset after the last pop()");
+    }
+
+    #[test]
+    fn test_peek_and_peek_mut() {
+        let mut queue = Queue::new();
+        assert_eq!(queue.peek_mut(), None);
+
+        queue.push(1);
+        queue.push(2);
+        *queue.peek_mut().unwrap() += 10;
+
+        assert_eq!(queue.peek(), Some(&11), "peek_mut() must change the front item");
+        assert_eq!(queue.peek_back(), Some(&2));
+        assert_eq!(queue.len(), 2, "peek must not change the size");
+    }
+
+    #[test]
+    fn test_from_iter_extend_and_into_iter() {
+        let mut queue: Queue<_> = (1..=3).collect();
+        queue.extend([4, 5]);
+        assert_eq!(queue.iter().copied().collect::<Vec<_>>(), [1, 2, 3, 4, 5]);
+
+        let drained: Vec<_> = queue.into_iter().collect();
+        assert_eq!(drained, [1, 2, 3, 4, 5], "into_iter() must yield in pop order");
+    }
+
+    #[test]
+    fn test_memory_leaks() {
+        use drop_tracker::DropTracker;
+
+        let mut tracker = DropTracker::new();
+
+        let mut queue: Queue<_> = (0..10).map(|i| tracker.track(i)).collect();
+        let _ = queue.pop();
+        queue.clear();
+        queue.push(tracker.track(10));
+        drop(queue);
+
+        assert_eq!(tracker.alive().count(), 0);
+        assert_eq!(tracker.dropped().count(), 11);
+    }
+}
@@ -0,0 +1,201 @@
+//! This module contains stack (LIFO) adapter implementation.
+
+use super::AdapterIntoIter;
+use crate::list::{List, SinglyLinkedList};
+
+/// A LIFO stack built on [`SinglyLinkedList`].
+///
+/// It exposes only the O(1) operations of the list: items are pushed to and popped
+/// from the front of the list.
+///
+/// # Type Parameters
+/// * `T`: The type of elements stored in the stack.
+///
+/// # Examples
+/// ```
+/// use plain_ds::list::Stack;
+///
+/// let mut stack: Stack<_> = [1, 2].into_iter().collect();
+/// stack.push(3);
+///
+/// assert_eq!(stack.peek(), Some(&3));
+/// assert_eq!(stack.pop(), Some(3));
+/// assert_eq!(stack.pop(), Some(2));
+/// ```
+pub struct Stack<T> {
+    list: SinglyLinkedList<T>,
+}
+
+impl<T> Stack<T> {
+    /// Creates empty stack.
+    pub fn new() -> Self {
+        Self {
+            list: SinglyLinkedList::new(),
+        }
+    }
+
+    /// Returns stack size.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    /// Checks if the stack is empty.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Adds a new item to the top of the stack.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn push(&mut self, payload: T) {
+        self.list.push_front(payload);
+    }
+
+    /// Removes the item from the top of the stack and returns it.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn pop(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+
+    /// Returns the item from the top of the stack.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn peek(&self) -> Option<&T> {
+        self.list.head()
+    }
+
+    /// Returns the mutable item from the top of the stack.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.list.iter_mut().next()
+    }
+
+    /// Returns an iterator over the items, from the top of the stack to the bottom.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.list.iter()
+    }
+
+    /// Removes all items from the stack.
+    ///
+    /// **Efficiency**: O(n)
+    pub fn clear(&mut self) {
+        self.list.clear();
+    }
+}
+
+impl<T> Default for Stack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FromIterator<T> for Stack<T> {
+    /// Pushes the items in the iteration order, so the last item is on the top.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut stack = Stack::new();
+        stack.extend(iter);
+        stack
+    }
+}
+
+impl<T> Extend<T> for Stack<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for payload in iter {
+            self.push(payload);
+        }
+    }
+}
+
+impl<T> IntoIterator for Stack<T> {
+    type Item = T;
+    type IntoIter = AdapterIntoIter<T>;
+
+    /// Returns an iterator that consumes the stack, from the top to the bottom.
+    fn into_iter(self) -> Self::IntoIter {
+        AdapterIntoIter::new(self.list)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_creation() {
+        let stack: Stack<u8> = Stack::new();
+        assert_eq!(stack.len(), 0, "not zero length after creation");
+        assert!(stack.is_empty(), "not empty after creation");
+        assert_eq!(stack.peek(), None, "not empty top after creation");
+    }
+
+    #[test]
+    fn test_push_pop_lifo_order() {
+        let mut stack = Stack::new();
+        stack.push(1);
+        stack.push(2);
+        stack.push(3);
+        assert_eq!(stack.len(), 3, "bad length after push()");
+
+        assert_eq!(stack.pop(), Some(3), "pop() must return the last pushed item");
+        assert_eq!(stack.pop(), Some(2));
+        assert_eq!(stack.pop(), Some(1));
+        assert_eq!(stack.pop(), None, "pop() from empty stack should return None");
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn test_peek_and_peek_mut() {
+        let mut stack = Stack::new();
+        assert_eq!(stack.peek_mut(), None);
+
+        stack.push(1);
+        stack.push(2);
+        *stack.peek_mut().unwrap() *= 10;
+
+        assert_eq!(stack.peek(), Some(&20), "peek_mut() must change the top item");
+        assert_eq!(stack.len(), 2, "peek must not change the size");
+    }
+
+    #[test]
+    fn test_from_iter_extend_and_into_iter() {
+        let mut stack: Stack<_> = (1..=3).collect();
+        assert_eq!(stack.iter().copied().collect::<Vec<_>>(), [3, 2, 1]);
+
+        stack.extend([4, 5]);
+        assert_eq!(stack.peek(), Some(&5), "extend() must push items to the top");
+
+        let drained: Vec<_> = stack.into_iter().collect();
+        assert_eq!(drained, [5, 4, 3, 2, 1], "into_iter() must yield in pop order");
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut stack: Stack<_> = (0..10).collect();
+        stack.clear();
+        assert!(stack.is_empty(), "stack should be empty after clear()");
+    }
+
+    #[test]
+    fn test_memory_leaks() {
+        use drop_tracker::DropTracker;
+
+        let mut tracker = DropTracker::new();
+
+        let mut stack: Stack<_> = (0..10).map(|i| tracker.track(i)).collect();
+        let _ = stack.pop();
+        assert_eq!(tracker.alive().count(), 9);
+
+        let mut iter = stack.into_iter();
+        let _ = iter.next();
+        drop(iter);
+
+        assert_eq!(tracker.alive().count(), 0);
+        assert_eq!(tracker.dropped().count(), 10);
+    }
+}
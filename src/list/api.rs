@@ -1,7 +1,18 @@
 use crate::core::{DSError, Result};
 
 /// This trait defines common API for all list implementations.
-pub trait List<'a, T: 'a> {
+///
+/// The items are borrowed from the list, so they cannot outlive it:
+/// ```compile_fail
+/// use plain_ds::{List, SinglyLinkedList};
+///
+/// let mut list = SinglyLinkedList::new();
+/// list.push(1);
+/// let first = List::get(&list, 0).unwrap();
+/// drop(list);
+/// assert_eq!(*first, 1);
+/// ```
+pub trait List<T> {
     /// Returns list size.
     fn len(&self) -> usize;
 
@@ -21,7 +32,7 @@ pub trait List<'a, T: 'a> {
     /// Returns a list item by index, or error if index out of bounds.
     ///
     /// **Efficiency**: O(n)
-    fn get(&self, index: usize) -> Result<&'a T> {
+    fn get(&self, index: usize) -> Result<&T> {
         self.iter().nth(index).ok_or(DSError::IndexOutOfBounds {
             index,
             len: self.len(),
@@ -31,7 +42,7 @@ pub trait List<'a, T: 'a> {
     /// Returns a mutable list item by index, or error if index out of bounds.
     ///
     /// **Efficiency**: O(n)
-    fn get_mut(&mut self, index: usize) -> Result<&'a mut T> {
+    fn get_mut(&mut self, index: usize) -> Result<&mut T> {
         let list_size = self.len();
         self.iter_mut().nth(index).ok_or(DSError::IndexOutOfBounds {
             index,
@@ -40,10 +51,14 @@ pub trait List<'a, T: 'a> {
     }
 
     /// Returns an iterator over the immutable items of the list.
-    fn iter(&self) -> impl Iterator<Item = &'a T>;
+    fn iter<'a>(&'a self) -> impl Iterator<Item = &'a T>
+    where
+        T: 'a;
 
     /// Returns an iterator over the mutable items of the list.
-    fn iter_mut(&mut self) -> impl Iterator<Item = &'a mut T>;
+    fn iter_mut<'a>(&'a mut self) -> impl Iterator<Item = &'a mut T>
+    where
+        T: 'a;
 
     /// Returns an iterator that consumes the list.
     fn into_iter(self) -> impl Iterator<Item = T>;
@@ -0,0 +1,87 @@
+//! This module contains capacity limit support for lists.
+
+use super::common::ListCommon;
+use crate::core::{DSError, Result};
+
+/// Defines what happens when a new element is added to the list which is already full.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Overflow {
+    /// The new element is rejected with [`DSError::CapacityExceeded`].
+    Reject,
+    /// The element is added, then the element at the front position is evicted:
+    /// the smallest one for `SortedList`. For `SinglyLinkedList` it is the oldest one,
+    /// as long as the elements are added to the back. Adding to the front of the full
+    /// `SinglyLinkedList` is rejected with [`DSError::CapacityExceeded`] instead of
+    /// evicting the new element itself.
+    EvictFront,
+    /// The element is added, then the element at the back position is evicted:
+    /// the largest one for `SortedList`. For `SinglyLinkedList` it is the newest one
+    /// added to the front, while the element pushed to the back of the full list
+    /// is returned at once without being linked.
+    ///
+    /// **Efficiency**: O(n) for the front insertions into `SinglyLinkedList`,
+    /// because its back node is removed.
+    EvictBack,
+}
+
+/// Capacity limit of the list.
+#[derive(Clone, Copy, Debug)]
+pub struct Bound {
+    pub capacity: usize,
+    pub policy: Overflow,
+}
+
+impl Bound {
+    /// Checks if one more element can be added to the list of length `len`.
+    /// Error returns, if the list is full and the policy is [`Overflow::Reject`].
+    pub fn check(bound: Option<Bound>, len: usize) -> Result<()> {
+        match bound {
+            Some(Bound {
+                capacity,
+                policy: Overflow::Reject,
+            }) if len >= capacity => Err(DSError::CapacityExceeded { capacity }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks if one more element can be added to the front of the list of length `len`.
+    /// Error returns, if the list is full and the policy is [`Overflow::Reject`],
+    /// or [`Overflow::EvictFront`], which would evict the new element itself.
+    pub fn check_front(bound: Option<Bound>, len: usize) -> Result<()> {
+        match bound {
+            Some(Bound {
+                capacity,
+                policy: Overflow::Reject | Overflow::EvictFront,
+            }) if len >= capacity => Err(DSError::CapacityExceeded { capacity }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks if the element added to the back of the list of length `len` would be evicted itself,
+    /// so it does not need to be linked at all.
+    pub fn evicts_new_back(bound: Option<Bound>, len: usize) -> bool {
+        match bound {
+            Some(Bound {
+                capacity,
+                policy: Overflow::EvictBack,
+            }) => len >= capacity,
+            Some(Bound {
+                capacity,
+                policy: Overflow::EvictFront,
+            }) => capacity == 0,
+            _ => false,
+        }
+    }
+
+    /// Evicts the extra element (if any) after it was added to the list.
+    pub fn trim<T>(bound: Option<Bound>, state: &mut ListCommon<T>) -> Option<T> {
+        match bound {
+            Some(bound) if state.len() > bound.capacity => match bound.policy {
+                Overflow::EvictFront => state.pop_front(),
+                Overflow::EvictBack => state.pop_back(),
+                Overflow::Reject => None,
+            },
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,108 @@
+//! This module contains the linking primitives shared by the singly-linked lists:
+//! the lists of owned nodes ([`ListCommon`](super::common::ListCommon))
+//! and the intrusive list of caller-owned values.
+
+use std::ptr;
+
+/// A link of a singly-linked chain: the node of the list, or the link embedded into a value.
+///
+/// # Safety
+/// The methods are called only for valid links.
+pub trait ChainLink {
+    /// Returns the link which follows `link`.
+    unsafe fn next(link: *mut Self) -> *mut Self;
+
+    /// Sets the link which follows `link`.
+    unsafe fn set_next(link: *mut Self, next: *mut Self);
+}
+
+/// A singly-linked chain with the head, the last link and the size.
+///
+/// The primitives only relink the links: allocation of the nodes, the cached positions,
+/// the handles and the ownership of the embedded links are up to the implementor.
+///
+/// # Safety
+/// The links passed to the primitives must be valid, and the chain must be consistent.
+pub trait Chain {
+    type Link: ChainLink;
+
+    /// Returns the head, the last link and the size of the chain.
+    fn ends_mut(&mut self) -> (&mut *mut Self::Link, &mut *mut Self::Link, &mut usize);
+
+    /// Links the detached `link` to the front of the chain.
+    ///
+    /// **Efficiency**: O(1)
+    unsafe fn link_front(&mut self, link: *mut Self::Link) {
+        let (head, last, size) = self.ends_mut();
+        unsafe { Self::Link::set_next(link, *head) };
+        if *size == 0 {
+            *last = link;
+        }
+        *head = link;
+        *size += 1;
+    }
+
+    /// Links the detached `link` to the end of the chain.
+    ///
+    /// **Efficiency**: O(1)
+    unsafe fn link_back(&mut self, link: *mut Self::Link) {
+        let (head, last, size) = self.ends_mut();
+        unsafe {
+            Self::Link::set_next(link, ptr::null_mut());
+            if *size == 0 {
+                *head = link;
+            } else {
+                Self::Link::set_next(*last, link);
+            }
+        }
+        *last = link;
+        *size += 1;
+    }
+
+    /// Unlinks the link which follows `prev` (null `prev` means the head) and returns it.
+    /// The link must exist.
+    ///
+    /// **Efficiency**: O(1)
+    unsafe fn unlink_after(&mut self, prev: *mut Self::Link) -> *mut Self::Link {
+        let (head, last, size) = self.ends_mut();
+        unsafe {
+            let link = if prev.is_null() { *head } else { Self::Link::next(prev) };
+            let next = Self::Link::next(link);
+            if prev.is_null() {
+                *head = next;
+            } else {
+                Self::Link::set_next(prev, next);
+            }
+            if link == *last {
+                *last = prev;
+            }
+            Self::Link::set_next(link, ptr::null_mut());
+            *size -= 1;
+            link
+        }
+    }
+
+    /// Moves the first `len` links, ending with `front_last`, to the empty chain `front`.
+    ///
+    /// **Efficiency**: O(1)
+    unsafe fn split_front_into(&mut self, front_last: *mut Self::Link, len: usize, front: &mut Self)
+    where
+        Self: Sized,
+    {
+        let (head, last, size) = self.ends_mut();
+        let front_head = *head;
+        unsafe {
+            *head = Self::Link::next(front_last);
+            Self::Link::set_next(front_last, ptr::null_mut());
+        }
+        if head.is_null() {
+            *last = ptr::null_mut();
+        }
+        *size -= len;
+
+        let (head, last, size) = front.ends_mut();
+        *head = front_head;
+        *last = front_last;
+        *size = len;
+    }
+}
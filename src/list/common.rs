@@ -1,22 +1,37 @@
-use crate::core::DSError;
-use super::node_one_link::{Iter, IterMut, Node};
+use crate::core::{DSError, InvariantViolation};
+use super::chain::Chain;
+use super::handle::Handles;
+use super::node_one_link::{Iter, IterMut, Node, merge, merge_sort};
+use std::cell::Cell;
 use std::ptr;
 
 /// `ListCommon` is a core of all lists implementation.
 /// It contains common fields and implement some common methods.
 /// This is not for direct usage.
+///
+/// `finger` caches the last accessed position `(index, node)`, so sequential or nearby
+/// indexed access does not re-walk the list from `head`. `ListCommon` methods keep it
+/// up to date; any other code that relinks nodes must call `reset_finger()`.
+///
+/// `handles` registers the nodes of the elements with stable handles. `ListCommon` methods
+/// release the handles of the freed nodes and move the handles of the nodes moved
+/// to another list; any other code that frees or moves nodes must do the same.
 pub struct ListCommon<T> {
-    pub head: *mut Node<T>, // 8 bytes
-    pub last: *mut Node<T>, // 8 bytes
-    pub size: usize,        // 8 bytes
+    pub head: *mut Node<T>,                     // 8 bytes
+    pub last: *mut Node<T>,                     // 8 bytes
+    pub size: usize,                            // 8 bytes
+    pub finger: Cell<(usize, *mut Node<T>)>,    // 16 bytes
+    pub handles: Handles<T>,                    // 8 bytes
 }
 
-impl<'a, T: 'a> ListCommon<T> {
+impl<T> ListCommon<T> {
     pub fn new() -> Self {
         Self {
             head: ptr::null_mut(),
             last: ptr::null_mut(),
             size: 0,
+            finger: Cell::new((0, ptr::null_mut())),
+            handles: Handles::new(),
         }
     }
 
@@ -65,15 +80,96 @@ impl<'a, T: 'a> ListCommon<T> {
         }
     }
 
+    /// Returns a list item by index, or error if index out of bounds.
+    ///
+    /// **Efficiency**: O(n), O(1) amortized for sequential access
+    #[inline]
+    pub fn get(&self, index: usize) -> crate::Result<&T> {
+        if index >= self.size {
+            return Err(DSError::IndexOutOfBounds {
+                index,
+                len: self.size,
+            });
+        }
+        Ok(unsafe { &(*self.node_at(index)).payload })
+    }
+
+    /// Returns a mutable list item by index, or error if index out of bounds.
+    ///
+    /// **Efficiency**: O(n), O(1) amortized for sequential access
+    #[inline]
+    pub fn get_mut(&mut self, index: usize) -> crate::Result<&mut T> {
+        if index >= self.size {
+            return Err(DSError::IndexOutOfBounds {
+                index,
+                len: self.size,
+            });
+        }
+        Ok(unsafe { &mut (*self.node_at(index)).payload })
+    }
+
+    /// Returns the node which follows `prev` (null `prev` means the head).
+    ///
+    /// **Efficiency**: O(1)
+    pub fn node_after(&self, prev: *mut Node<T>) -> *mut Node<T> {
+        if prev.is_null() {
+            self.head
+        } else {
+            unsafe { (*prev).next }
+        }
+    }
+
+    /// Returns the node by valid index and remembers its position.
+    /// The search starts from the cached position, if it is not after `index`.
+    ///
+    /// **Efficiency**: O(index - cached index)
+    pub fn node_at(&self, index: usize) -> *mut Node<T> {
+        if index + 1 == self.size {
+            self.finger.set((index, self.last));
+            return self.last;
+        }
+
+        let (mut current_index, mut current) = self.finger.get();
+        if current.is_null() || current_index > index {
+            current_index = 0;
+            current = self.head;
+        }
+        unsafe {
+            while current_index < index {
+                current = (*current).next;
+                current_index += 1;
+            }
+        }
+
+        self.finger.set((index, current));
+        current
+    }
+
+    /// Forgets the cached position.
+    /// Must be called after any relinking of nodes outside of `ListCommon` methods.
+    #[inline]
+    pub fn reset_finger(&self) {
+        self.finger.set((0, ptr::null_mut()));
+    }
+
+    /// Adjusts the cached position after a node was linked to the front of the list.
+    #[inline]
+    pub fn shift_finger(&self) {
+        let (index, node) = self.finger.get();
+        if !node.is_null() {
+            self.finger.set((index + 1, node));
+        }
+    }
+
     /// Returns an iterator over the immutable items of the list.
     #[inline]
-    pub fn iter(&self) -> impl Iterator<Item = &'a T> {
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
         Iter::new(self.head)
     }
 
     /// Returns an iterator over the mutable items of the list.
     #[inline]
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = &'a mut T> {
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
         IterMut::new(self.head)
     }
 
@@ -83,19 +179,23 @@ impl<'a, T: 'a> ListCommon<T> {
         IntoIter::new(self)
     }
 
+    /// Adds a new node to the front of the list.
+    ///
+    /// **Efficiency**: O(1)
+    #[inline]
+    pub fn push_front(&mut self, payload: T) {
+        let ptr = Box::into_raw(Box::new(Node::new(payload)));
+        unsafe { self.link_front(ptr) };
+        self.shift_finger();
+    }
+
     /// Adds a new node to the end of the list.
     ///
     /// **Efficiency**: O(1)
     #[inline]
     pub fn push_back(&mut self, payload: T) {
         let ptr = Box::into_raw(Box::new(Node::new(payload)));
-        if self.len() == 0 {
-            self.head = ptr;
-        } else {
-            unsafe { (*self.last).next = ptr };
-        }
-        self.last = ptr;
-        self.size += 1;
+        unsafe { self.link_back(ptr) };
     }
 
     /// Removes a node from the end of the list and returns its payload value.
@@ -107,36 +207,26 @@ impl<'a, T: 'a> ListCommon<T> {
             return None;
         }
 
-        // Case: only one node in list
-        if self.head == self.last {
-            let payload = unsafe { Box::from_raw(self.head).payload };
-            self.head = ptr::null_mut();
-            self.last = ptr::null_mut();
-            self.size -= 1;
-            return Some(payload);
-        }
-
-        // Finding the penultimate node
-        let mut current = self.head;
-        unsafe {
-            while (*current).next != self.last {
-                current = (*current).next;
+        // Finding the penultimate node (null, if there is only one node in list)
+        let mut prev = ptr::null_mut();
+        if self.head != self.last {
+            prev = self.head;
+            unsafe {
+                while (*prev).next != self.last {
+                    prev = (*prev).next;
+                }
             }
         }
 
-        // current now points to the penultimate node
         let old_last = self.last;
-        self.last = current;
-        unsafe { (*self.last).next = ptr::null_mut() };
+        if self.finger.get().1 == old_last {
+            self.reset_finger();
+        }
+        unsafe { self.unlink_after(prev) };
 
         // Release the last node and extract the payload
-        let payload = unsafe {
-            let boxed = Box::from_raw(old_last);
-            boxed.payload
-        };
-
-        self.size -= 1;
-        Some(payload)
+        self.handles.release(old_last);
+        Some(unsafe { Box::from_raw(old_last).payload })
     }
 
     /// Removes a node from the front of the list and returns its payload value.
@@ -148,14 +238,16 @@ impl<'a, T: 'a> ListCommon<T> {
             return None;
         }
 
-        let old_head = unsafe { Box::from_raw(self.head) };
-        self.head = old_head.next;
-        if self.len() == 1 {
-            self.last = ptr::null_mut();
+        let (index, node) = self.finger.get();
+        if index == 0 {
+            self.reset_finger();
+        } else {
+            self.finger.set((index - 1, node));
         }
 
-        self.size -= 1;
-        Some(old_head.payload)
+        let old_head = unsafe { self.unlink_after(ptr::null_mut()) };
+        self.handles.release(old_head);
+        Some(unsafe { Box::from_raw(old_head).payload })
     }
 
     /// Removes a node from the specified location in the list.
@@ -179,21 +271,55 @@ impl<'a, T: 'a> ListCommon<T> {
             return Ok(self.pop_back().unwrap());
         }
 
-        // Finding the removing item
-        let mut before = self.head;
-        let mut index = index;
+        // Finding the node before the removing item (the cached position stays valid)
+        let before = self.node_at(index - 1);
+
+        let removed = unsafe { self.unlink_after(before) };
+        self.handles.release(removed);
+        Ok(unsafe { Box::from_raw(removed).payload })
+    }
+
+    /// Removes the node of the list and returns its payload. The payload of the next node
+    /// is moved into the removed one together with its handle, so only the last node
+    /// needs the search of the predecessor.
+    ///
+    /// **Efficiency**: O(1), or O(n) for the last node
+    pub fn remove_node(&mut self, node: *mut Node<T>) -> T {
+        if node == self.head {
+            return self.pop_front().unwrap();
+        }
+        if node == self.last {
+            return self.pop_back().unwrap();
+        }
+
         unsafe {
-            while index > 1 {
-                before = (*before).next;
-                index -= 1;
+            let next = (*node).next;
+            std::mem::swap(&mut (*node).payload, &mut (*next).payload);
+            (*node).next = (*next).next;
+            if next == self.last {
+                self.last = node;
             }
-        }
+            self.handles.release(node);
+            let id = self.handles.release(next);
+            self.handles.restore(node, id);
 
-        let removed = unsafe { Box::from_raw((*before).next) };
-        unsafe { (*before).next = removed.next };
+            self.size -= 1;
+            self.reset_finger();
+            Box::from_raw(next).payload
+        }
+    }
 
-        self.size -= 1;
-        Ok(removed.payload)
+    /// Returns the index of the node of the list.
+    ///
+    /// **Efficiency**: O(n)
+    pub fn index_of(&self, node: *mut Node<T>) -> usize {
+        let mut index = 0;
+        let mut current = self.head;
+        while current != node {
+            current = unsafe { (*current).next };
+            index += 1;
+        }
+        index
     }
 
     /// Finds the first node whose payload satisfies the predicate and returns its index.
@@ -205,21 +331,242 @@ impl<'a, T: 'a> ListCommon<T> {
     where
         T: PartialEq,
     {
-        self.iter().position(|item| predicate(item))
+        self.iter().position(predicate)
+    }
+
+    /// Sorts the list in ascending order using merge sort algorithm.
+    ///
+    /// **Efficiency**: O(n log n)
+    ///
+    /// Space complexity: O(1), the sort is bottom-up
+    ///
+    /// If a comparison panics, the list keeps all its elements (in unspecified order).
+    pub fn sort(&mut self)
+    where
+        T: PartialOrd,
+    {
+        if self.len() <= 1 {
+            return; // Already sorted
+        }
+
+        self.reset_finger();
+        // If a comparison panics, the list keeps all nodes in some order
+        let list = Relinked(self);
+        merge_sort(&mut list.0.head);
+    }
+
+    /// Checks the structure of the list: the node chain has no cycles, `size` is equal
+    /// to the number of nodes, `last` is the tail node, the cached position is valid
+    /// and every registered handle refers to a node of the list.
+    ///
+    /// **Efficiency**: O(n)
+    pub fn check_invariants(&self) -> Result<(), InvariantViolation> {
+        // Floyd's cycle detection: the fast pointer meets the slow one inside a cycle
+        let mut slow = self.head;
+        let mut fast = self.head;
+        unsafe {
+            while !fast.is_null() && !(*fast).next.is_null() {
+                slow = (*slow).next;
+                fast = (*(*fast).next).next;
+                if slow == fast {
+                    return Err(InvariantViolation::Cycle);
+                }
+            }
+        }
+
+        let (finger_index, finger) = self.finger.get();
+        let mut finger_found = finger.is_null();
+        let mut actual = 0;
+        let mut tail = ptr::null_mut();
+        let mut registered = 0;
+        let mut current = self.head;
+        while !current.is_null() {
+            if self.handles.contains(current) {
+                registered += 1;
+            }
+            if current == finger {
+                if actual != finger_index {
+                    return Err(InvariantViolation::StaleFinger { index: finger_index });
+                }
+                finger_found = true;
+            }
+            tail = current;
+            current = unsafe { (*current).next };
+            actual += 1;
+        }
+
+        if actual != self.size {
+            return Err(InvariantViolation::SizeMismatch {
+                size: self.size,
+                actual,
+            });
+        }
+        if tail != self.last {
+            return Err(InvariantViolation::WrongLast);
+        }
+        if !finger_found {
+            return Err(InvariantViolation::StaleFinger { index: finger_index });
+        }
+        if registered != self.handles.len() {
+            return Err(InvariantViolation::StaleHandle);
+        }
+        Ok(())
+    }
+
+    /// Checks that every element is not less than the previous one.
+    /// Returns the index of the first element which breaks the order.
+    ///
+    /// **Efficiency**: O(n)
+    pub fn check_sorted(&self) -> Result<(), InvariantViolation>
+    where
+        T: PartialOrd,
+    {
+        let mut iter = self.iter();
+        let Some(mut prev) = iter.next() else {
+            return Ok(());
+        };
+        for (index, current) in iter.enumerate() {
+            if current < prev {
+                return Err(InvariantViolation::NotSorted { index: index + 1 });
+            }
+            prev = current;
+        }
+        Ok(())
+    }
+
+    /// Merges the nodes of sorted `other` list into this sorted list in one pass.
+    /// Equal elements of this list are placed before the elements of `other`.
+    ///
+    /// **Efficiency**: O(n + m)
+    pub fn merge_sorted(&mut self, mut other: ListCommon<T>)
+    where
+        T: PartialOrd,
+    {
+        if other.len() == 0 {
+            return;
+        }
+
+        // Quick Case: the other list goes after all elements
+        if self.len() == 0 || unsafe { (*self.last).payload <= (*other.head).payload } {
+            self.append(other);
+        } else {
+            self.reset_finger();
+            // If a comparison panics, every node stays either in `self` or in `other`
+            let (list, other) = (Relinked(self), Relinked(&mut other));
+            merge(&mut list.0.head, &mut other.0.head);
+            // After a panic the handles of the merged nodes are lost, but none of them is stale
+            other.0.handles.append(&mut list.0.handles);
+        }
+    }
+
+    /// Moves all nodes of `other` to the end of the list.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn append(&mut self, mut other: ListCommon<T>) {
+        if other.len() == 0 {
+            return;
+        }
+        if self.len() == 0 {
+            self.head = other.head;
+        } else {
+            unsafe { (*self.last).next = other.head };
+        }
+        self.last = other.last;
+        self.size += other.size;
+        other.handles.append(&mut self.handles);
+        other.head = ptr::null_mut();
+        other.last = ptr::null_mut();
+        other.size = 0;
+    }
+
+    /// Detaches the first `len` nodes into a new list (all nodes, if `len` is greater than the size).
+    ///
+    /// **Efficiency**: O(len)
+    pub fn split_front(&mut self, len: usize) -> ListCommon<T> {
+        if len >= self.len() {
+            return std::mem::replace(self, ListCommon::new());
+        }
+        let mut front = ListCommon::new();
+        if len == 0 {
+            return front;
+        }
+
+        let last = self.node_at(len - 1);
+        unsafe { self.split_front_into(last, len, &mut front) };
+        self.handles.transfer_chain(front.head, &mut front.handles);
+        self.reset_finger();
+        front
+    }
+
+    /// Rebuilds the list from a chain of nodes
+    fn rebuild_from_sorted_list(&mut self, head: *mut Node<T>) {
+        self.head = head;
+        self.size = 0;
+
+        if head.is_null() {
+            self.last = ptr::null_mut();
+            return;
+        }
+
+        // Traverse to find the last node and count size
+        let mut current = head;
+        self.size = 1;
+
+        unsafe {
+            while !(*current).next.is_null() {
+                current = (*current).next;
+                self.size += 1;
+            }
+            self.last = current;
+        }
+    }
+
+    // Unlinks the head node and returns it (only for dropping: the handles are not released).
+    fn unlink_head(&mut self) -> Option<Box<Node<T>>> {
+        if self.head.is_null() {
+            return None;
+        }
+        Some(unsafe { Box::from_raw(self.unlink_after(ptr::null_mut())) })
+    }
+}
+
+impl<T> Chain for ListCommon<T> {
+    type Link = Node<T>;
+
+    fn ends_mut(&mut self) -> (&mut *mut Node<T>, &mut *mut Node<T>, &mut usize) {
+        (&mut self.head, &mut self.last, &mut self.size)
     }
 }
 
 impl<T> Drop for ListCommon<T> {
     fn drop(&mut self) {
-        use std::mem::ManuallyDrop;
-
-        let mut current = ManuallyDrop::new(self.head);
-        while !current.is_null() {
-            unsafe {
-                let node = Box::from_raw(ManuallyDrop::take(&mut current));
-                current = ManuallyDrop::new(node.next);
+        // Drops the rest of the nodes, if a payload destructor panics
+        struct DropGuard<'a, T>(&'a mut ListCommon<T>);
+
+        impl<T> Drop for DropGuard<'_, T> {
+            fn drop(&mut self) {
+                while let Some(node) = self.0.unlink_head() {
+                    drop(node);
+                }
             }
         }
+
+        while let Some(node) = self.unlink_head() {
+            let guard = DropGuard(self);
+            drop(node);
+            std::mem::forget(guard);
+        }
+    }
+}
+
+// Recomputes `last` and `size` of the list from the node chain on drop,
+// so the list is valid after relinking of its nodes, even if it is interrupted by a panic.
+struct Relinked<'a, T>(&'a mut ListCommon<T>);
+
+impl<T> Drop for Relinked<'_, T> {
+    fn drop(&mut self) {
+        let head = self.0.head;
+        self.0.rebuild_from_sorted_list(head);
     }
 }
 
@@ -553,6 +900,7 @@ mod tests {
         }
 
         #[test]
+        #[allow(clippy::map_clone)]
         fn test_remove_with_complex_types_string() {
             let mut list = ListCommon::new();
             list.push_back("first".to_string());
@@ -592,6 +940,111 @@ mod tests {
         }
     }
 
+    mod finger {
+        use super::*;
+
+        fn finger_index<T>(list: &ListCommon<T>) -> Option<usize> {
+            let (index, node) = list.finger.get();
+            if node.is_null() { None } else { Some(index) }
+        }
+
+        #[test]
+        fn test_get_out_of_bounds() {
+            let list = setup_list(3);
+            assert_eq!(
+                list.get(3),
+                Err(DSError::IndexOutOfBounds { index: 3, len: 3 }),
+                "get() with index == size should return error"
+            );
+            assert_eq!(finger_index(&list), None, "failed get() must not move the finger");
+        }
+
+        #[test]
+        fn test_sequential_get() {
+            let list = setup_list(100);
+            for i in 0..100 {
+                assert_eq!(list.get(i), Ok(&i), "get() returns wrong item at index {}", i);
+                assert_eq!(finger_index(&list), Some(i), "finger must follow the accessed index");
+            }
+        }
+
+        #[test]
+        fn test_backward_and_random_get() {
+            let list = setup_list(50);
+            for i in (0..50).rev() {
+                assert_eq!(list.get(i), Ok(&i), "get() returns wrong item at index {}", i);
+            }
+            for i in [7, 3, 40, 41, 0, 49, 25] {
+                assert_eq!(list.get(i), Ok(&i), "get() returns wrong item at index {}", i);
+            }
+        }
+
+        #[test]
+        fn test_get_mut_moves_finger() {
+            let mut list = setup_list(10);
+            *list.get_mut(4).unwrap() = 40;
+            assert_eq!(finger_index(&list), Some(4));
+            *list.get_mut(5).unwrap() = 50;
+            assert_eq!(list.to_vec(), [0, 1, 2, 3, 40, 50, 6, 7, 8, 9]);
+        }
+
+        #[test]
+        fn test_finger_after_pop_front() {
+            let mut list = setup_list(10);
+            let _ = list.get(5);
+            let _ = list.pop_front();
+            assert_eq!(finger_index(&list), Some(4), "finger index must be shifted");
+            assert_eq!(list.get(4), Ok(&5));
+
+            let _ = list.get(0);
+            let _ = list.pop_front();
+            assert_eq!(finger_index(&list), None, "finger to removed head must be reset");
+            assert_eq!(list.get(0), Ok(&2));
+        }
+
+        #[test]
+        fn test_finger_after_pop_back() {
+            let mut list = setup_list(5);
+            let _ = list.get(4);
+            let _ = list.pop_back();
+            assert_eq!(finger_index(&list), None, "finger to removed last must be reset");
+            assert_eq!(list.get(3), Ok(&3));
+
+            let _ = list.get(1);
+            let _ = list.pop_back();
+            assert_eq!(finger_index(&list), Some(1), "finger before last must stay");
+            assert_eq!(list.get(2), Ok(&2));
+        }
+
+        #[test]
+        fn test_finger_after_remove() {
+            let mut list = setup_list(10);
+            let _ = list.get(6);
+            assert_eq!(list.remove(6), Ok(6));
+            assert_eq!(list.get(6), Ok(&7), "get() after remove() returns wrong item");
+
+            // Sequential removal uses the cached position
+            for expected in [1, 2, 3] {
+                assert_eq!(list.remove(1), Ok(expected));
+            }
+            assert_eq!(list.to_vec(), [0, 4, 5, 7, 8, 9]);
+            for (i, value) in [0, 4, 5, 7, 8, 9].iter().enumerate() {
+                assert_eq!(list.get(i), Ok(value));
+            }
+        }
+
+        #[test]
+        fn test_finger_reset_when_emptied() {
+            let mut list = setup_list(1);
+            let _ = list.get(0);
+            let _ = list.pop_back();
+            assert_eq!(finger_index(&list), None);
+
+            list.push_back(7);
+            assert_eq!(list.get(0), Ok(&7));
+        }
+    }
+
     mod iterators {
         use super::*;
 
@@ -683,6 +1136,7 @@ mod tests {
         }
 
         #[test]
+        #[allow(clippy::assign_op_pattern)]
         fn test_mutable_iteration_modification() {
             let mut list = ListCommon::new();
             for i in 1..=3 {
@@ -811,6 +1265,7 @@ mod tests {
         }
 
         #[test]
+        #[allow(clippy::needless_range_loop)]
         fn test_to_vec_large_list() {
             const SIZE: usize = 1000;
             let values: Vec<usize> = (0..SIZE).collect();
@@ -1030,4 +1485,241 @@ mod tests {
             assert_eq!(result, Some(2), "should work with closures that capture variables from environment");
         }
     }
+
+    mod merge_split {
+        use super::*;
+
+        fn create_list_from_slice<T: Clone>(values: &[T]) -> ListCommon<T> {
+            let mut list = ListCommon::new();
+            for value in values {
+                list.push_back(value.clone());
+            }
+            list
+        }
+
+        #[test]
+        fn test_merge_sorted() {
+            let mut list = create_list_from_slice(&[1, 4, 6]);
+            list.merge_sorted(create_list_from_slice(&[2, 3, 7]));
+            assert_eq!(list.to_vec(), [1, 2, 3, 4, 6, 7]);
+            assert_eq!(list.len(), 6, "bad length after merge_sorted()");
+            assert_eq!(list.last(), Some(&7), "last must be updated after merge_sorted()");
+
+            list.merge_sorted(create_list_from_slice(&[8, 9]));
+            assert_eq!(list.to_vec(), [1, 2, 3, 4, 6, 7, 8, 9], "merge after all elements");
+            assert_eq!(list.last(), Some(&9));
+
+            let mut empty = ListCommon::new();
+            empty.merge_sorted(create_list_from_slice(&[5]));
+            assert_eq!(empty.to_vec(), [5], "merge into empty list");
+        }
+
+        #[test]
+        fn test_split_front() {
+            let mut list = create_list_from_slice(&[1, 2, 3, 4, 5]);
+            let front = list.split_front(0);
+            assert_eq!(front.len(), 0, "split_front(0) must detach nothing");
+            assert_eq!(list.len(), 5);
+
+            let front = list.split_front(2);
+            assert_eq!(front.to_vec(), [1, 2]);
+            assert_eq!(front.last(), Some(&2), "last of the detached list must be set");
+            assert_eq!(list.to_vec(), [3, 4, 5]);
+            assert_eq!(list.get(0), Ok(&3), "finger must be valid after split_front()");
+            assert_eq!(front.check_invariants(), Ok(()));
+            assert_eq!(list.check_invariants(), Ok(()));
+
+            let front = list.split_front(7);
+            assert_eq!(front.to_vec(), [3, 4, 5], "all nodes must be detached");
+            assert_eq!(list.len(), 0);
+            assert_eq!(list.last(), None);
+        }
+    }
+
+    mod panic_safety {
+        use super::*;
+        use drop_tracker::{DropItem, DropTracker};
+        use std::cell::Cell;
+        use std::cmp::Ordering;
+        use std::panic::{self, AssertUnwindSafe};
+
+        thread_local! {
+            // The number of comparisons of `Fragile` elements allowed before panic
+            static COMPARISONS_LEFT: Cell<usize> = const { Cell::new(usize::MAX) };
+        }
+
+        #[derive(Debug, PartialEq)]
+        struct Fragile(DropItem<u32>);
+
+        impl PartialOrd for Fragile {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                COMPARISONS_LEFT.with(|left| {
+                    assert!(left.get() > 0, "comparison budget is over");
+                    left.set(left.get() - 1);
+                });
+                self.0.partial_cmp(&other.0)
+            }
+        }
+
+        fn set_comparisons(limit: usize) {
+            COMPARISONS_LEFT.with(|left| left.set(limit));
+        }
+
+        fn values(list: &ListCommon<Fragile>) -> Vec<u32> {
+            let mut values: Vec<_> = list.iter().map(|item| *item.0).collect();
+            values.sort_unstable();
+            values
+        }
+
+        #[test]
+        fn test_sort_with_panicking_comparison() {
+            for limit in [0, 1, 7, 40, 100] {
+                let mut tracker = DropTracker::new();
+                let mut list = ListCommon::new();
+                for i in 0..32 {
+                    list.push_back(Fragile(tracker.track((i * 7) % 32)));
+                }
+
+                set_comparisons(limit);
+                let result = panic::catch_unwind(AssertUnwindSafe(|| list.sort()));
+                set_comparisons(usize::MAX);
+                assert!(result.is_err(), "sort must panic with the limit {limit}");
+
+                assert_eq!(list.check_invariants(), Ok(()), "list must be valid after panic in sort()");
+                assert_eq!(list.len(), 32, "sort() must keep all elements on panic");
+                assert_eq!(values(&list), (0..32).collect::<Vec<_>>());
+                let last = list.iter().last().map(|item| *item.0);
+                assert_eq!(list.get(31).ok().map(|item| *item.0), last, "finger must be reset");
+
+                list.sort();
+                assert_eq!(list.check_sorted(), Ok(()), "list must be usable after panic in sort()");
+                drop(list);
+                assert_eq!(tracker.alive().count(), 0);
+                assert_eq!(tracker.dropped().count(), 32);
+            }
+        }
+
+        #[test]
+        fn test_merge_with_panicking_comparison() {
+            let mut tracker = DropTracker::new();
+            let mut list = ListCommon::new();
+            let mut other = ListCommon::new();
+            for i in 0..10 {
+                list.push_back(Fragile(tracker.track(2 * i + 1)));
+                other.push_back(Fragile(tracker.track(2 * i)));
+            }
+
+            set_comparisons(5);
+            let result = panic::catch_unwind(AssertUnwindSafe(|| list.merge_sorted(other)));
+            set_comparisons(usize::MAX);
+            assert!(result.is_err(), "merge_sorted() must panic");
+
+            assert_eq!(list.check_invariants(), Ok(()), "list must be valid after panic in merge");
+            assert_eq!(list.check_sorted(), Ok(()), "merged part must stay sorted");
+            assert!(list.len() >= 10, "elements of the list must be kept");
+            assert_eq!(
+                tracker.alive().count(),
+                list.len(),
+                "elements of `other` which are not merged must be dropped"
+            );
+            drop(list);
+            assert_eq!(tracker.alive().count(), 0);
+            assert_eq!(tracker.dropped().count(), 20);
+        }
+
+        #[test]
+        fn test_drop_with_panicking_destructor() {
+            struct Bomb {
+                _item: DropItem<u32>,
+                explode: bool,
+            }
+
+            impl Drop for Bomb {
+                fn drop(&mut self) {
+                    if self.explode {
+                        panic!("drop failed");
+                    }
+                }
+            }
+
+            let mut tracker = DropTracker::new();
+            let mut list = ListCommon::new();
+            for i in 0..10 {
+                list.push_back(Bomb {
+                    _item: tracker.track(i),
+                    explode: i == 3,
+                });
+            }
+
+            let result = panic::catch_unwind(AssertUnwindSafe(|| drop(list)));
+            assert!(result.is_err(), "panic of the destructor must be propagated");
+            assert_eq!(tracker.alive().count(), 0, "the rest of the nodes must be dropped");
+            assert_eq!(tracker.dropped().count(), 10);
+        }
+    }
+
+    mod invariants {
+        use super::*;
+
+        #[test]
+        fn test_valid_list() {
+            assert_eq!(ListCommon::<u8>::new().check_invariants(), Ok(()));
+            let list = setup_list(10);
+            let _ = list.get(4);
+            assert_eq!(list.check_invariants(), Ok(()));
+        }
+
+        #[test]
+        fn test_size_mismatch() {
+            let mut list = setup_list(3);
+            list.size = 4;
+            assert_eq!(
+                list.check_invariants(),
+                Err(InvariantViolation::SizeMismatch { size: 4, actual: 3 })
+            );
+            list.size = 3;
+        }
+
+        #[test]
+        fn test_wrong_last() {
+            let mut list = setup_list(3);
+            let last = list.last;
+            list.last = list.head;
+            assert_eq!(list.check_invariants(), Err(InvariantViolation::WrongLast));
+            list.last = last;
+        }
+
+        #[test]
+        fn test_cycle() {
+            let list = setup_list(4);
+            unsafe { (*list.last).next = (*list.head).next };
+            assert_eq!(list.check_invariants(), Err(InvariantViolation::Cycle));
+            unsafe { (*list.last).next = ptr::null_mut() };
+
+            let single = setup_list(1);
+            unsafe { (*single.head).next = single.head };
+            assert_eq!(single.check_invariants(), Err(InvariantViolation::Cycle));
+            unsafe { (*single.head).next = ptr::null_mut() };
+        }
+
+        #[test]
+        fn test_stale_finger() {
+            let list = setup_list(5);
+            let _ = list.get(2);
+            let (_, node) = list.finger.get();
+            list.finger.set((3, node));
+            assert_eq!(
+                list.check_invariants(),
+                Err(InvariantViolation::StaleFinger { index: 3 })
+            );
+        }
+
+        #[test]
+        fn test_check_sorted() {
+            let mut list = setup_list(5);
+            assert_eq!(list.check_sorted(), Ok(()));
+            *list.get_mut(3).unwrap() = 0;
+            assert_eq!(list.check_sorted(), Err(InvariantViolation::NotSorted { index: 3 }));
+        }
+    }
 }
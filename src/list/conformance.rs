@@ -0,0 +1,209 @@
+//! This module contains reusable conformance tests for `List` implementations.
+
+/// Generates the module `$name` with the tests which check that the list created by `$new`
+/// behaves as the [`List`](crate::List) trait requires: push, pop, remove, get, find, clear,
+/// iteration and dropping of all elements.
+///
+/// The tests push the elements in ascending order and expect them to be iterated
+/// in the same order, so the macro is suitable both for sequential and for sorted lists.
+/// Elements are `i32` values and drop-counting values, which implement `PartialOrd`.
+///
+/// The generated module imports everything from the parent module, so `$new` may refer
+/// to any item visible at the macro invocation.
+///
+/// The macro is exported with the `testing` feature.
+///
+/// # Examples
+/// ```
+/// use plain_ds::SinglyLinkedList;
+///
+/// plain_ds::list_conformance_tests!(singly_linked_conformance, SinglyLinkedList::new);
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! list_conformance_tests {
+    ($name:ident, $new:expr) => {
+        #[cfg(test)]
+        mod $name {
+            #[allow(unused_imports)]
+            use super::*;
+            use $crate::{DSError, List};
+            use ::std::cell::Cell;
+            use ::std::rc::Rc;
+
+            // Element which counts its drops
+            #[derive(Debug)]
+            struct Tracked {
+                value: i32,
+                drops: Rc<Cell<usize>>,
+            }
+
+            impl PartialEq for Tracked {
+                fn eq(&self, other: &Self) -> bool {
+                    self.value == other.value
+                }
+            }
+
+            impl PartialOrd for Tracked {
+                fn partial_cmp(&self, other: &Self) -> Option<::std::cmp::Ordering> {
+                    self.value.partial_cmp(&other.value)
+                }
+            }
+
+            impl Drop for Tracked {
+                fn drop(&mut self) {
+                    self.drops.set(self.drops.get() + 1);
+                }
+            }
+
+            fn filled(n: i32) -> impl List<i32> {
+                let mut list = $new();
+                for value in 0..n {
+                    list.push(value);
+                }
+                list
+            }
+
+            fn values(list: &impl List<i32>) -> Vec<i32> {
+                list.iter().copied().collect()
+            }
+
+            #[test]
+            fn test_empty() {
+                let list = filled(0);
+                assert_eq!(list.len(), 0, "new list must be empty");
+                assert!(list.is_empty(), "new list must be empty");
+                assert_eq!(list.head(), None);
+                assert_eq!(list.last(), None);
+                assert_eq!(list.iter().next(), None);
+            }
+
+            #[test]
+            fn test_push() {
+                let mut list = filled(0);
+                for value in 0..5 {
+                    list.push(value);
+                    assert_eq!(list.len(), value as usize + 1, "push() must increase the size");
+                    assert_eq!(list.last(), Some(&value), "push() must add the greatest value to the end");
+                }
+                assert_eq!(list.head(), Some(&0));
+                assert!(!list.is_empty());
+            }
+
+            #[test]
+            fn test_pop_front() {
+                let mut list = filled(3);
+                assert_eq!(list.pop_front(), Some(0));
+                assert_eq!(list.pop_front(), Some(1));
+                assert_eq!(list.head(), Some(&2));
+                assert_eq!(list.pop_front(), Some(2));
+                assert_eq!(list.pop_front(), None, "pop_front() from empty list must return None");
+                assert!(list.is_empty());
+                assert_eq!(list.last(), None, "last must be reset after the last pop_front()");
+            }
+
+            #[test]
+            fn test_pop_back() {
+                let mut list = filled(3);
+                assert_eq!(list.pop_back(), Some(2));
+                assert_eq!(list.last(), Some(&1), "last must be updated after pop_back()");
+                assert_eq!(list.pop_back(), Some(1));
+                assert_eq!(list.pop_back(), Some(0));
+                assert_eq!(list.pop_back(), None, "pop_back() from empty list must return None");
+                assert_eq!(list.head(), None, "head must be reset after the last pop_back()");
+            }
+
+            #[test]
+            fn test_remove() {
+                let mut list = filled(5);
+                assert_eq!(list.remove(0), Ok(0), "remove() of the head");
+                assert_eq!(list.remove(1), Ok(2), "remove() from the middle");
+                assert_eq!(list.remove(2), Ok(4), "remove() of the last");
+                assert_eq!(values(&list), [1, 3]);
+                assert_eq!(list.last(), Some(&3), "last must be updated after remove()");
+                assert_eq!(list.remove(2), Err(DSError::IndexOutOfBounds { index: 2, len: 2 }));
+                assert_eq!(list.len(), 2, "failed remove() must not change the size");
+            }
+
+            #[test]
+            fn test_get() {
+                let mut list = filled(5);
+                for index in 0..5 {
+                    assert_eq!(list.get(index), Ok(&(index as i32)));
+                }
+                assert_eq!(list.get(5), Err(DSError::IndexOutOfBounds { index: 5, len: 5 }));
+
+                *list.get_mut(4).unwrap() = 10;
+                assert_eq!(list.get(4), Ok(&10), "get_mut() must change the item");
+                assert_eq!(list.get_mut(5), Err(DSError::IndexOutOfBounds { index: 5, len: 5 }));
+            }
+
+            #[test]
+            fn test_find() {
+                let list = filled(5);
+                assert_eq!(list.find(&0), Some(0));
+                assert_eq!(list.find(&3), Some(3));
+                assert_eq!(list.find(&4), Some(4));
+                assert_eq!(list.find(&7), None);
+                assert_eq!(filled(0).find(&0), None);
+            }
+
+            #[test]
+            fn test_clear() {
+                let mut list = filled(5);
+                list.clear();
+                assert!(list.is_empty(), "list must be empty after clear()");
+                assert_eq!(list.head(), None);
+                assert_eq!(list.last(), None);
+
+                list.push(1);
+                assert_eq!(values(&list), [1], "list must be usable after clear()");
+            }
+
+            #[test]
+            fn test_iteration() {
+                let mut list = filled(5);
+                assert_eq!(values(&list), [0, 1, 2, 3, 4]);
+                assert_eq!(list.iter().count(), list.len());
+
+                // Keeps the order, so it is safe for sorted lists
+                for value in list.iter_mut() {
+                    *value *= 2;
+                }
+                assert_eq!(values(&list), [0, 2, 4, 6, 8], "iter_mut() must change the items");
+                assert_eq!(List::into_iter(list).collect::<Vec<_>>(), [0, 2, 4, 6, 8]);
+            }
+
+            #[test]
+            fn test_drop_count() {
+                let drops = Rc::new(Cell::new(0));
+                let tracked = |value| Tracked {
+                    value,
+                    drops: Rc::clone(&drops),
+                };
+
+                let mut list = $new();
+                for value in 0..10 {
+                    list.push(tracked(value));
+                }
+                drop(list.pop_front());
+                drop(list.pop_back());
+                drop(List::remove(&mut list, 3));
+                assert_eq!(drops.get(), 3, "removed elements must be dropped once");
+
+                let mut iter = List::into_iter(list);
+                drop(iter.next());
+                drop(iter);
+                assert_eq!(drops.get(), 10, "all elements must be dropped exactly once");
+
+                let mut list = $new();
+                for value in 0..5 {
+                    list.push(tracked(value));
+                }
+                list.clear();
+                drop(list);
+                assert_eq!(drops.get(), 15, "clear() must drop all elements");
+            }
+        }
+    };
+}
@@ -0,0 +1,306 @@
+//! This module contains conversions between the lists and std collections.
+//!
+//! All conversions move the payloads, nothing is cloned.
+
+use std::collections::{BTreeSet, LinkedList, VecDeque};
+
+use super::api::List;
+use super::singly_linked::SinglyLinkedList;
+use super::sorted::SortedList;
+
+impl<T> From<Vec<T>> for SinglyLinkedList<T> {
+    /// Keeps the order of the vector.
+    ///
+    /// **Efficiency**: O(n)
+    fn from(vec: Vec<T>) -> Self {
+        IntoIterator::into_iter(vec).collect()
+    }
+}
+
+impl<T> From<VecDeque<T>> for SinglyLinkedList<T> {
+    /// Keeps the order of the deque.
+    ///
+    /// **Efficiency**: O(n)
+    fn from(deque: VecDeque<T>) -> Self {
+        IntoIterator::into_iter(deque).collect()
+    }
+}
+
+impl<T> From<LinkedList<T>> for SinglyLinkedList<T> {
+    /// Keeps the order of the list.
+    ///
+    /// **Efficiency**: O(n)
+    fn from(list: LinkedList<T>) -> Self {
+        IntoIterator::into_iter(list).collect()
+    }
+}
+
+impl<T> From<BTreeSet<T>> for SinglyLinkedList<T> {
+    /// The elements go in ascending order.
+    ///
+    /// **Efficiency**: O(n)
+    fn from(set: BTreeSet<T>) -> Self {
+        set.into_iter().collect()
+    }
+}
+
+impl<T> From<SinglyLinkedList<T>> for Vec<T> {
+    /// **Efficiency**: O(n)
+    fn from(list: SinglyLinkedList<T>) -> Self {
+        let mut vec = Vec::with_capacity(list.len());
+        vec.extend(List::into_iter(list));
+        vec
+    }
+}
+
+impl<T> From<SinglyLinkedList<T>> for VecDeque<T> {
+    /// **Efficiency**: O(n)
+    fn from(list: SinglyLinkedList<T>) -> Self {
+        let mut deque = VecDeque::with_capacity(list.len());
+        deque.extend(List::into_iter(list));
+        deque
+    }
+}
+
+impl<T> From<SinglyLinkedList<T>> for LinkedList<T> {
+    /// **Efficiency**: O(n)
+    fn from(list: SinglyLinkedList<T>) -> Self {
+        List::into_iter(list).collect()
+    }
+}
+
+impl<T: Ord> From<SinglyLinkedList<T>> for BTreeSet<T> {
+    /// Duplicate elements are dropped.
+    ///
+    /// **Efficiency**: O(n log n)
+    fn from(list: SinglyLinkedList<T>) -> Self {
+        List::into_iter(list).collect()
+    }
+}
+
+impl<T: PartialOrd> From<Vec<T>> for SortedList<T> {
+    /// **Efficiency**: O(n log n)
+    fn from(vec: Vec<T>) -> Self {
+        SortedList::from_vec(vec)
+    }
+}
+
+impl<T: PartialOrd> From<VecDeque<T>> for SortedList<T> {
+    /// **Efficiency**: O(n log n)
+    fn from(deque: VecDeque<T>) -> Self {
+        IntoIterator::into_iter(deque).collect()
+    }
+}
+
+impl<T: PartialOrd> From<LinkedList<T>> for SortedList<T> {
+    /// **Efficiency**: O(n log n)
+    fn from(list: LinkedList<T>) -> Self {
+        IntoIterator::into_iter(list).collect()
+    }
+}
+
+impl<T: Ord> From<BTreeSet<T>> for SortedList<T> {
+    /// The set is already sorted, so no sorting is performed.
+    ///
+    /// **Efficiency**: O(n)
+    fn from(set: BTreeSet<T>) -> Self {
+        SortedList::from_sorted_iter_unchecked(set)
+    }
+}
+
+impl<T: PartialOrd> From<SortedList<T>> for Vec<T> {
+    /// The elements go in ascending order.
+    ///
+    /// **Efficiency**: O(n)
+    fn from(list: SortedList<T>) -> Self {
+        let mut vec = Vec::with_capacity(list.len());
+        vec.extend(List::into_iter(list));
+        vec
+    }
+}
+
+impl<T: PartialOrd> From<SortedList<T>> for VecDeque<T> {
+    /// The elements go in ascending order.
+    ///
+    /// **Efficiency**: O(n)
+    fn from(list: SortedList<T>) -> Self {
+        let mut deque = VecDeque::with_capacity(list.len());
+        deque.extend(List::into_iter(list));
+        deque
+    }
+}
+
+impl<T: PartialOrd> From<SortedList<T>> for LinkedList<T> {
+    /// The elements go in ascending order.
+    ///
+    /// **Efficiency**: O(n)
+    fn from(list: SortedList<T>) -> Self {
+        List::into_iter(list).collect()
+    }
+}
+
+impl<T: Ord> From<SortedList<T>> for BTreeSet<T> {
+    /// Duplicate elements are dropped. The elements are already sorted,
+    /// so the set is built in bulk.
+    ///
+    /// **Efficiency**: O(n)
+    fn from(list: SortedList<T>) -> Self {
+        List::into_iter(list).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use drop_tracker::{DropItem, DropTracker};
+
+    // Tracked item which can be stored in `BTreeSet`
+    #[derive(Debug, PartialEq, Eq)]
+    struct Item(DropItem<i32>);
+
+    impl PartialOrd for Item {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Item {
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            (*self.0).cmp(&*other.0)
+        }
+    }
+
+    mod singly_linked {
+        use super::*;
+
+        #[test]
+        fn test_from_std_collections() {
+            let list = SinglyLinkedList::from(vec![3, 1, 2]);
+            assert_eq!(list.to_vec(), [3, 1, 2], "order of the vector must be kept");
+            assert_eq!(list.last(), Some(&2));
+
+            let list = SinglyLinkedList::from(VecDeque::from([3, 1, 2]));
+            assert_eq!(list.to_vec(), [3, 1, 2]);
+
+            let list = SinglyLinkedList::from(LinkedList::from([3, 1, 2]));
+            assert_eq!(list.to_vec(), [3, 1, 2]);
+
+            let list = SinglyLinkedList::from(BTreeSet::from([3, 1, 2]));
+            assert_eq!(list.to_vec(), [1, 2, 3]);
+        }
+
+        #[test]
+        fn test_into_std_collections() {
+            let vec: Vec<_> = SinglyLinkedList::from(vec![3, 1, 2]).into();
+            assert_eq!(vec, [3, 1, 2]);
+
+            let deque: VecDeque<_> = SinglyLinkedList::from(vec![3, 1, 2]).into();
+            assert_eq!(deque, [3, 1, 2]);
+
+            let list: LinkedList<_> = SinglyLinkedList::from(vec![3, 1, 2]).into();
+            assert_eq!(list, LinkedList::from([3, 1, 2]));
+
+            let set: BTreeSet<_> = SinglyLinkedList::from(vec![3, 1, 3, 2]).into();
+            assert_eq!(set, BTreeSet::from([1, 2, 3]));
+
+            let empty: Vec<u8> = SinglyLinkedList::new().into();
+            assert!(empty.is_empty());
+        }
+
+        #[test]
+        fn test_conversions_move_payloads() {
+            let mut tracker = DropTracker::new();
+
+            let vec: Vec<_> = (0..5).map(|i| tracker.track(i)).collect();
+            let list = SinglyLinkedList::from(vec);
+            let deque = VecDeque::from(list);
+            let list = SinglyLinkedList::from(deque);
+            let std_list = LinkedList::from(list);
+            let list = SinglyLinkedList::from(std_list);
+            let vec = Vec::from(list);
+            assert_eq!(tracker.alive().count(), 5, "conversions must not drop payloads");
+            assert_eq!(tracker.dropped().count(), 0);
+            assert_eq!(vec.iter().map(|item| **item).collect::<Vec<_>>(), [0, 1, 2, 3, 4]);
+
+            drop(vec);
+            assert_eq!(tracker.alive().count(), 0);
+            assert_eq!(tracker.dropped().count(), 5);
+        }
+    }
+
+    mod sorted {
+        use super::*;
+
+        #[test]
+        fn test_from_std_collections() {
+            let list = SortedList::from(vec![3, 1, 2]);
+            assert_eq!(list.to_vec(), [1, 2, 3]);
+            assert_eq!(list.last(), Some(&3));
+
+            let list = SortedList::from(VecDeque::from([3, 1, 2]));
+            assert_eq!(list.to_vec(), [1, 2, 3]);
+
+            let list = SortedList::from(LinkedList::from([3, 1, 2]));
+            assert_eq!(list.to_vec(), [1, 2, 3]);
+
+            let list = SortedList::from(BTreeSet::from([3, 1, 2]));
+            assert_eq!(list.to_vec(), [1, 2, 3]);
+            assert_eq!(list.last(), Some(&3), "last must be set by the sorted fast path");
+            assert_eq!(list.find(&2), Some(1));
+        }
+
+        #[test]
+        fn test_into_std_collections() {
+            let vec: Vec<_> = SortedList::from(vec![3, 1, 2]).into();
+            assert_eq!(vec, [1, 2, 3]);
+
+            let deque: VecDeque<_> = SortedList::from(vec![3, 1, 2]).into();
+            assert_eq!(deque, [1, 2, 3]);
+
+            let list: LinkedList<_> = SortedList::from(vec![3, 1, 2]).into();
+            assert_eq!(list, LinkedList::from([1, 2, 3]));
+
+            let set: BTreeSet<_> = SortedList::from(vec![3, 1, 3, 2]).into();
+            assert_eq!(set, BTreeSet::from([1, 2, 3]));
+        }
+
+        #[test]
+        fn test_conversions_move_payloads() {
+            let mut tracker = DropTracker::new();
+
+            let vec: Vec<_> = [4, 2, 0, 3, 1].into_iter().map(|i| tracker.track(i)).collect();
+            let list = SortedList::from(vec);
+            let deque = VecDeque::from(list);
+            let list = SortedList::from(deque);
+            let std_list = LinkedList::from(list);
+            let list = SortedList::from(std_list);
+            let vec = Vec::from(list);
+            assert_eq!(tracker.alive().count(), 5, "conversions must not drop payloads");
+            assert_eq!(tracker.dropped().count(), 0);
+            assert_eq!(vec.iter().map(|item| **item).collect::<Vec<_>>(), [0, 1, 2, 3, 4]);
+
+            drop(vec);
+            assert_eq!(tracker.alive().count(), 0);
+            assert_eq!(tracker.dropped().count(), 5);
+        }
+
+        #[test]
+        #[allow(clippy::mutable_key_type)] // the ordering of tracked items never changes
+        fn test_set_conversions_move_payloads() {
+            let mut tracker = DropTracker::new();
+
+            let set: BTreeSet<_> = [4, 2, 0, 3, 1].into_iter().map(|i| Item(tracker.track(i))).collect();
+            let list = SortedList::from(set);
+            let set = BTreeSet::from(list);
+            let list = SinglyLinkedList::from(set);
+            let set = BTreeSet::from(list);
+            assert_eq!(tracker.alive().count(), 5, "conversions must not drop payloads");
+            assert_eq!(tracker.dropped().count(), 0);
+            assert_eq!(set.iter().map(|item| *item.0).collect::<Vec<_>>(), [0, 1, 2, 3, 4]);
+
+            drop(set);
+            assert_eq!(tracker.alive().count(), 0);
+            assert_eq!(tracker.dropped().count(), 5);
+        }
+    }
+}
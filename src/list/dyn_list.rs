@@ -0,0 +1,262 @@
+//! This module contains object-safe list API.
+
+use super::api::List;
+use crate::core::Result;
+
+/// Object-safe companion of the [`List`] trait: it allows to choose a list implementation
+/// at runtime and use it as `Box<dyn DynList<T>>`.
+///
+/// Iteration is provided by boxed iterators and by visitor-style [`for_each`](DynList::for_each).
+/// The trait is implemented for every [`List`] implementor, including the lists
+/// of borrowed items (`T` does not have to be `'static`).
+///
+/// Methods have the same names as in [`List`], so for concrete list types
+/// import only one of these traits in a scope (or use fully qualified syntax).
+/// That is why the trait is not re-exported from the crate root: glob import of the root
+/// brings only [`List`], import `DynList` from [`plain_ds::list`](crate::list).
+///
+/// # Type Parameters
+/// * `T`: The type of elements stored in the list.
+///
+/// # Examples
+/// ```
+/// use plain_ds::list::DynList;
+/// use plain_ds::{SinglyLinkedList, SortedList};
+///
+/// fn create(sorted: bool) -> Box<dyn DynList<i32>> {
+///     if sorted {
+///         Box::new(SortedList::new())
+///     } else {
+///         Box::new(SinglyLinkedList::new())
+///     }
+/// }
+///
+/// let mut list = create(true);
+/// list.push(2);
+/// list.push(1);
+/// assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2]);
+/// ```
+pub trait DynList<T> {
+    /// Returns list size.
+    fn len(&self) -> usize;
+
+    /// Checks if the list is empty.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the payload value of the first node in the list.
+    fn head(&self) -> Option<&T>;
+
+    /// Returns the payload value of the last node in the list.
+    fn last(&self) -> Option<&T>;
+
+    /// Returns a list item by index, or error if index out of bounds.
+    fn get(&self, index: usize) -> Result<&T>;
+
+    /// Returns a mutable list item by index, or error if index out of bounds.
+    fn get_mut(&mut self, index: usize) -> Result<&mut T>;
+
+    /// Returns a boxed iterator over the immutable items of the list.
+    fn iter(&self) -> Box<dyn Iterator<Item = &T> + '_>;
+
+    /// Returns a boxed iterator over the mutable items of the list.
+    fn iter_mut(&mut self) -> Box<dyn Iterator<Item = &mut T> + '_>;
+
+    /// Calls `visitor` for every item of the list (without allocation of an iterator).
+    fn for_each(&self, visitor: &mut dyn FnMut(&T));
+
+    /// Adds a new node to the list.
+    fn push(&mut self, payload: T);
+
+    /// Removes a node from the end of the list and returns its payload value.
+    fn pop_back(&mut self) -> Option<T>;
+
+    /// Removes a node from the front of the list and returns its payload value.
+    fn pop_front(&mut self) -> Option<T>;
+
+    /// Removes a node from the specified location in the list.
+    /// Error returns, if the index out of bounds.
+    fn remove(&mut self, index: usize) -> Result<T>;
+
+    /// Removes all items from the list.
+    fn clear(&mut self);
+}
+
+impl<T, L> DynList<T> for L
+where
+    L: List<T>,
+{
+    fn len(&self) -> usize {
+        List::len(self)
+    }
+
+    fn head(&self) -> Option<&T> {
+        List::head(self)
+    }
+
+    fn last(&self) -> Option<&T> {
+        List::last(self)
+    }
+
+    fn get(&self, index: usize) -> Result<&T> {
+        List::get(self, index)
+    }
+
+    fn get_mut(&mut self, index: usize) -> Result<&mut T> {
+        List::get_mut(self, index)
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &T> + '_> {
+        Box::new(List::iter(self))
+    }
+
+    fn iter_mut(&mut self) -> Box<dyn Iterator<Item = &mut T> + '_> {
+        Box::new(List::iter_mut(self))
+    }
+
+    fn for_each(&self, visitor: &mut dyn FnMut(&T)) {
+        List::iter(self).for_each(visitor);
+    }
+
+    fn push(&mut self, payload: T) {
+        List::push(self, payload);
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        List::pop_back(self)
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        List::pop_front(self)
+    }
+
+    fn remove(&mut self, index: usize) -> Result<T> {
+        List::remove(self, index)
+    }
+
+    fn clear(&mut self) {
+        List::clear(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::list::{EqualOrder, Overflow};
+    use crate::{SinglyLinkedList, SortedList};
+
+    fn create(kind: &str) -> Box<dyn DynList<i32>> {
+        match kind {
+            "singly" => Box::new(SinglyLinkedList::new()),
+            "bounded" => Box::new(SinglyLinkedList::bounded(3, Overflow::EvictFront)),
+            "sorted" => Box::new(SortedList::new()),
+            "sorted-lifo" => Box::new(SortedList::new().with_equal_order(EqualOrder::Lifo)),
+            _ => unreachable!(),
+        }
+    }
+
+    fn fill(list: &mut dyn DynList<i32>) {
+        for value in [3, 1, 2, 4] {
+            list.push(value);
+        }
+    }
+
+    fn values(list: &dyn DynList<i32>) -> Vec<i32> {
+        list.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_lists_behind_one_dyn_type() {
+        let mut lists: Vec<Box<dyn DynList<i32>>> = vec![create("singly"), create("sorted")];
+        for list in &mut lists {
+            fill(list.as_mut());
+        }
+
+        assert_eq!(values(lists[0].as_ref()), [3, 1, 2, 4], "singly-linked list keeps push order");
+        assert_eq!(values(lists[1].as_ref()), [1, 2, 3, 4], "sorted list sorts the items");
+        assert_eq!(lists[1].get(2), Ok(&3));
+        assert_eq!(lists[1].last(), Some(&4));
+    }
+
+    #[test]
+    fn test_runtime_configuration() {
+        let mut list = create("bounded");
+        fill(list.as_mut());
+        assert_eq!(values(list.as_ref()), [1, 2, 4], "overflow policy must be applied");
+
+        let mut list = create("sorted-lifo");
+        fill(list.as_mut());
+        assert_eq!(list.head(), Some(&1));
+    }
+
+    #[test]
+    fn test_mutation_through_dyn() {
+        for kind in ["singly", "sorted"] {
+            let mut list = create(kind);
+            assert!(list.is_empty());
+            fill(list.as_mut());
+            assert_eq!(list.len(), 4);
+
+            for value in list.iter_mut() {
+                *value *= 10;
+            }
+            *list.get_mut(0).unwrap() += 1;
+            assert_eq!(list.head().map(|value| value % 10), Some(1), "get_mut() must change the item");
+
+            let front = list.pop_front().unwrap();
+            let back = list.pop_back().unwrap();
+            assert_eq!(list.remove(5), Err(crate::DSError::IndexOutOfBounds { index: 5, len: 2 }));
+            let removed = list.remove(0).unwrap();
+            assert_eq!(list.len(), 1, "{kind}: bad length after removals");
+            assert_ne!(front, back);
+            assert_ne!(removed, back);
+
+            list.clear();
+            assert!(list.is_empty(), "{kind}: list must be empty after clear()");
+            assert_eq!(list.last(), None);
+        }
+    }
+
+    #[test]
+    fn test_borrowed_items() {
+        let words = [String::from("beta"), String::from("alpha")];
+        let mut list: Box<dyn DynList<&str> + '_> = Box::new(SortedList::new());
+        for word in &words {
+            list.push(word.as_str());
+        }
+        assert_eq!(list.head(), Some(&"alpha"));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), ["alpha", "beta"]);
+    }
+
+    #[test]
+    fn test_visitor() {
+        let mut list = create("sorted");
+        fill(list.as_mut());
+
+        let mut visited = Vec::new();
+        list.for_each(&mut |value| visited.push(*value));
+        assert_eq!(visited, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_drop_through_dyn() {
+        use drop_tracker::DropTracker;
+
+        let mut tracker = DropTracker::new();
+
+        let mut lists: Vec<Box<dyn DynList<_>>> =
+            vec![Box::new(SinglyLinkedList::new()), Box::new(SortedList::new())];
+        for (index, list) in lists.as_mut_slice().iter_mut().enumerate() {
+            for value in 0..5 {
+                list.push(tracker.track(index * 10 + value));
+            }
+        }
+        let _ = lists[0].pop_front();
+        assert_eq!(tracker.alive().count(), 9);
+
+        drop(lists);
+        assert_eq!(tracker.alive().count(), 0);
+        assert_eq!(tracker.dropped().count(), 10);
+    }
+}
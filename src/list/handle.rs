@@ -0,0 +1,157 @@
+//! This module contains stable handles of list elements.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::node_one_link::Node;
+use crate::core::{DSError, Result};
+
+// Source of the handle identifiers, they are unique among all lists
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// A handle of the element added with `push_with_handle` to
+/// [`SinglyLinkedList`](super::SinglyLinkedList) or [`SortedList`](super::SortedList).
+///
+/// The handle stays valid while the element is in the list, whatever happens to its index.
+/// It follows the element, when the element is moved to another list by splitting or merging.
+/// The handle of the removed (or evicted) element is rejected with [`DSError::InvalidHandle`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ListHandle(u64);
+
+impl ListHandle {
+    // Creates the handle which is not registered in any list.
+    pub(crate) fn detached() -> Self {
+        Self(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// Registry of the nodes with handles: maps every handle identifier to its node and back.
+/// A node is never dereferenced by a handle before the handle is found in the registry,
+/// so the handles of the removed elements are rejected safely.
+///
+/// The maps are allocated with the first handle, so the lists without handles pay
+/// only for one null pointer. The code which frees a node, or moves it to another list,
+/// must release its handle (`check_invariants()` of the lists detects the stale ones).
+pub struct Handles<T>(Option<Box<Registry<T>>>);
+
+struct Registry<T> {
+    nodes: HashMap<u64, *mut Node<T>>,
+    ids: HashMap<*mut Node<T>, u64>,
+}
+
+impl<T> Handles<T> {
+    pub fn new() -> Self {
+        Self(None)
+    }
+
+    /// Returns the number of the nodes with handles.
+    pub fn len(&self) -> usize {
+        self.0.as_ref().map_or(0, |registry| registry.ids.len())
+    }
+
+    /// Checks if the node has a handle.
+    pub fn contains(&self, node: *mut Node<T>) -> bool {
+        self.0.as_ref().is_some_and(|registry| registry.ids.contains_key(&node))
+    }
+
+    /// Creates a new handle of the node.
+    pub fn register(&mut self, node: *mut Node<T>) -> ListHandle {
+        let handle = ListHandle::detached();
+        self.restore(node, Some(handle.0));
+        handle
+    }
+
+    /// Returns the node of the valid handle.
+    /// Error returns, if the element of the handle is not in the list.
+    pub fn node(&self, handle: ListHandle) -> Result<*mut Node<T>> {
+        self.0
+            .as_ref()
+            .and_then(|registry| registry.nodes.get(&handle.0).copied())
+            .ok_or(DSError::InvalidHandle)
+    }
+
+    /// Invalidates the handle of the node (if any) and returns its identifier.
+    pub fn release(&mut self, node: *mut Node<T>) -> Option<u64> {
+        let registry = self.0.as_mut()?;
+        let id = registry.ids.remove(&node)?;
+        registry.nodes.remove(&id);
+        Some(id)
+    }
+
+    /// Restores the handle released with [`release`](Handles::release),
+    /// possibly for another node.
+    pub fn restore(&mut self, node: *mut Node<T>, id: Option<u64>) {
+        if let Some(id) = id {
+            let registry = self.0.get_or_insert_with(|| {
+                Box::new(Registry {
+                    nodes: HashMap::new(),
+                    ids: HashMap::new(),
+                })
+            });
+            registry.ids.insert(node, id);
+            registry.nodes.insert(id, node);
+        }
+    }
+
+    /// Exchanges the handles of two nodes, when their payloads are exchanged.
+    pub fn swap(&mut self, first: *mut Node<T>, second: *mut Node<T>) {
+        let first_id = self.release(first);
+        let second_id = self.release(second);
+        self.restore(first, second_id);
+        self.restore(second, first_id);
+    }
+
+    /// Moves the handle of the node (if any) to the registry of another list.
+    pub fn transfer(&mut self, node: *mut Node<T>, to: &mut Handles<T>) {
+        let id = self.release(node);
+        to.restore(node, id);
+    }
+
+    /// Moves the handles of the chain starting at `head` to the registry of another list.
+    pub fn transfer_chain(&mut self, mut head: *mut Node<T>, to: &mut Handles<T>) {
+        while self.len() > 0 && !head.is_null() {
+            self.transfer(head, to);
+            head = unsafe { (*head).next };
+        }
+    }
+
+    /// Moves all handles to the registry of another list.
+    pub fn append(&mut self, to: &mut Handles<T>) {
+        if self.len() == 0 {
+            return;
+        }
+        match &mut to.0 {
+            None => to.0 = self.0.take(),
+            Some(registry) => {
+                let from = self.0.as_mut().unwrap();
+                registry.nodes.extend(from.nodes.drain());
+                registry.ids.extend(from.ids.drain());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem::size_of;
+
+    #[test]
+    fn test_registry_is_lazy() {
+        assert_eq!(size_of::<Handles<u8>>(), size_of::<usize>());
+
+        let mut handles = Handles::new();
+        assert!(handles.0.is_none(), "new registry must not allocate");
+        let mut node = Node::new(1u8);
+        let node = &mut node as *mut Node<u8>;
+        assert_eq!(handles.release(node), None);
+        assert!(handles.0.is_none(), "release() must not allocate");
+
+        let handle = handles.register(node);
+        assert_eq!(handles.node(handle), Ok(node));
+        let mut other = Handles::new();
+        handles.append(&mut other);
+        assert_eq!(other.node(handle), Ok(node));
+        assert_eq!(handles.node(handle), Err(DSError::InvalidHandle));
+    }
+}
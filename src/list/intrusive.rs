@@ -0,0 +1,644 @@
+//! This module contains intrusive singly-linked list implementation.
+
+use std::marker::{PhantomData, PhantomPinned};
+use std::pin::Pin;
+use std::ptr;
+use std::sync::atomic::{AtomicBool, AtomicPtr, Ordering};
+
+use super::chain::{Chain, ChainLink};
+use crate::core::{DSError, Result};
+
+/// `Link` is a field that must be embedded into the value type to allow it to be
+/// chained into an [`IntrusiveList`].
+///
+/// Embedding a `Link` makes the value type `!Unpin`: once a value has been linked,
+/// it cannot be moved, so the list may safely hold pointers to it.
+/// `Link` is `Sync`, so the values can be placed into `static` storage.
+pub struct Link {
+    next: AtomicPtr<Link>,
+    linked: AtomicBool,
+    _pin: PhantomPinned,
+}
+
+impl Link {
+    /// Creates new unlinked `Link`.
+    pub const fn new() -> Self {
+        Self {
+            next: AtomicPtr::new(ptr::null_mut()),
+            linked: AtomicBool::new(false),
+            _pin: PhantomPinned,
+        }
+    }
+
+    /// Checks if the owner of this link is contained in some list.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn is_linked(&self) -> bool {
+        self.linked.load(Ordering::Acquire)
+    }
+
+    // Takes the ownership of the link, returns `false` if it is already linked.
+    fn try_link(&self) -> bool {
+        self.linked
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_ok()
+    }
+
+    fn unlink(&self) {
+        self.next.store(ptr::null_mut(), Ordering::Relaxed);
+        self.linked.store(false, Ordering::Release);
+    }
+}
+
+impl ChainLink for Link {
+    unsafe fn next(link: *mut Self) -> *mut Self {
+        unsafe { (*link).next.load(Ordering::Relaxed) }
+    }
+
+    unsafe fn set_next(link: *mut Self, next: *mut Self) {
+        unsafe { (*link).next.store(next, Ordering::Relaxed) };
+    }
+}
+
+impl Default for Link {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `Adapter` describes where the [`Link`] is located inside the value type.
+///
+/// Usually there is no need to implement it manually: use the
+/// [`intrusive_adapter!`](crate::intrusive_adapter) macro instead.
+///
+/// # Safety
+/// `value` must be the exact inverse of `link`: for every value `v`
+/// `value(link(v))` must return a pointer to `v`.
+pub unsafe trait Adapter {
+    /// The type of the values stored in the list.
+    type Value;
+
+    /// Returns the link embedded in `value`.
+    fn link(value: &Self::Value) -> &Link;
+
+    /// Returns the value that contains `link`.
+    ///
+    /// # Safety
+    /// `link` must point to a link obtained from [`Adapter::link`].
+    unsafe fn value(link: *const Link) -> *const Self::Value;
+}
+
+/// Declares an [`Adapter`] for the value type with an embedded [`Link`] field.
+///
+/// # Examples
+/// ```
+/// use plain_ds::intrusive_adapter;
+/// use plain_ds::list::Link;
+///
+/// struct Task {
+///     id: u32,
+///     link: Link,
+/// }
+///
+/// intrusive_adapter!(TaskAdapter = Task { link });
+/// ```
+#[macro_export]
+macro_rules! intrusive_adapter {
+    ($vis:vis $name:ident = $value:ty { $field:ident }) => {
+        $vis struct $name;
+
+        unsafe impl $crate::list::Adapter for $name {
+            type Value = $value;
+
+            fn link(value: &Self::Value) -> &$crate::list::Link {
+                &value.$field
+            }
+
+            unsafe fn value(link: *const $crate::list::Link) -> *const Self::Value {
+                let offset = ::core::mem::offset_of!($value, $field);
+                unsafe { link.byte_sub(offset) as *const Self::Value }
+            }
+        }
+    };
+}
+
+/// An intrusive singly-linked list: it chains values owned by the caller
+/// (living in arenas, static storage or on the stack) without any allocation.
+///
+/// Every value must embed a [`Link`] field, described by an [`Adapter`].
+/// The list only borrows the values, so they cannot be moved or dropped while they are linked.
+/// The links are chained by the same primitives as the nodes of the other lists.
+///
+/// **Pinning rules**:
+/// - values are added as `Pin<&'a A::Value>`, so they stay at the same address
+///   for the whole time they are in the list;
+/// - a value can be contained only in one list at a time;
+/// - a value becomes unlinked after it is popped or removed, or when the list is dropped,
+///   and can then be added to a list again.
+///
+/// # Type Parameters
+/// * `A`: The adapter which describes the value type and the location of its link.
+///
+/// # Examples
+/// ```
+/// use std::pin::Pin;
+/// use plain_ds::intrusive_adapter;
+/// use plain_ds::list::{IntrusiveList, Link};
+///
+/// struct Task {
+///     id: u32,
+///     link: Link,
+/// }
+///
+/// intrusive_adapter!(TaskAdapter = Task { link });
+///
+/// static FIRST: Task = Task { id: 1, link: Link::new() };
+/// static SECOND: Task = Task { id: 2, link: Link::new() };
+///
+/// let mut list = IntrusiveList::<TaskAdapter>::new();
+/// list.push_back(Pin::static_ref(&FIRST)).unwrap();
+/// list.push_back(Pin::static_ref(&SECOND)).unwrap();
+///
+/// assert_eq!(list.pop_front().map(|task| task.id), Some(1));
+/// assert_eq!(list.len(), 1);
+/// ```
+pub struct IntrusiveList<'a, A: Adapter> {
+    head: *mut Link, // 8 bytes
+    last: *mut Link, // 8 bytes
+    size: usize,     // 8 bytes
+    _marker: PhantomData<(A, Pin<&'a A::Value>)>,
+}
+
+impl<'a, A: Adapter> IntrusiveList<'a, A> {
+    /// Creates empty intrusive list.
+    pub fn new() -> Self {
+        Self {
+            head: ptr::null_mut(),
+            last: ptr::null_mut(),
+            size: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns list size.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    /// Checks if the list is empty.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Returns the first value in the list.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn head(&self) -> Option<&'a A::Value> {
+        Self::value_of(self.head)
+    }
+
+    /// Returns the last value in the list.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn last(&self) -> Option<&'a A::Value> {
+        Self::value_of(self.last)
+    }
+
+    /// Returns an iterator over the values of the list.
+    pub fn iter(&self) -> IntrusiveIter<'a, A> {
+        IntrusiveIter {
+            current: self.head,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns a cursor pointing to the first value of the list.
+    pub fn cursor_mut(&mut self) -> CursorMut<'_, 'a, A> {
+        CursorMut {
+            current: self.head,
+            prev: ptr::null_mut(),
+            list: self,
+        }
+    }
+
+    /// Links a value to the front of the list.
+    /// Error returns, if the value is already contained in some list.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn push_front(&mut self, value: Pin<&'a A::Value>) -> Result<()> {
+        let link = Self::acquire(value)?;
+        unsafe { self.link_front(link) };
+        Ok(())
+    }
+
+    /// Links a value to the end of the list.
+    /// Error returns, if the value is already contained in some list.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn push_back(&mut self, value: Pin<&'a A::Value>) -> Result<()> {
+        let link = Self::acquire(value)?;
+        unsafe { self.link_back(link) };
+        Ok(())
+    }
+
+    /// Unlinks the value from the front of the list and returns it.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn pop_front(&mut self) -> Option<Pin<&'a A::Value>> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let old_head = unsafe { self.unlink_after(ptr::null_mut()) };
+        Some(Self::release(old_head))
+    }
+
+    /// Unlinks all values from the list.
+    ///
+    /// **Efficiency**: O(n)
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    // Marks the value link as linked, or returns error if it is already linked.
+    fn acquire(value: Pin<&'a A::Value>) -> Result<*mut Link> {
+        let link = A::link(value.get_ref());
+        if !link.try_link() {
+            return Err(DSError::AlreadyLinked);
+        }
+        // The link is changed only through its atomic fields
+        Ok(ptr::from_ref(link).cast_mut())
+    }
+
+    // Resets the detached link and returns its owner.
+    fn release(link: *mut Link) -> Pin<&'a A::Value> {
+        unsafe {
+            (*link).unlink();
+            // Safety: the value was pinned when it was linked
+            Pin::new_unchecked(&*A::value(link))
+        }
+    }
+
+    fn value_of(link: *mut Link) -> Option<&'a A::Value> {
+        if link.is_null() {
+            None
+        } else {
+            Some(unsafe { &*A::value(link) })
+        }
+    }
+}
+
+impl<A: Adapter> Default for IntrusiveList<'_, A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: Adapter> Chain for IntrusiveList<'_, A> {
+    type Link = Link;
+
+    fn ends_mut(&mut self) -> (&mut *mut Link, &mut *mut Link, &mut usize) {
+        (&mut self.head, &mut self.last, &mut self.size)
+    }
+}
+
+impl<A: Adapter> Drop for IntrusiveList<'_, A> {
+    fn drop(&mut self) {
+        // The values are not owned by the list: just unlink them to make reusable
+        self.clear();
+    }
+}
+
+/// An iterator over the values of the [`IntrusiveList`].
+pub struct IntrusiveIter<'a, A: Adapter> {
+    current: *mut Link,
+    _marker: PhantomData<&'a A::Value>,
+}
+
+impl<'a, A: Adapter> Iterator for IntrusiveIter<'a, A> {
+    type Item = &'a A::Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.current.is_null() {
+            None
+        } else {
+            unsafe {
+                let value = &*A::value(self.current);
+                self.current = Link::next(self.current);
+                Some(value)
+            }
+        }
+    }
+}
+
+/// A cursor over the [`IntrusiveList`] which allows to remove values during traversal.
+///
+/// The cursor points either to a value of the list, or past the end of the list.
+pub struct CursorMut<'l, 'a, A: Adapter> {
+    list: &'l mut IntrusiveList<'a, A>,
+    prev: *mut Link,
+    current: *mut Link,
+}
+
+impl<'a, A: Adapter> CursorMut<'_, 'a, A> {
+    /// Returns the value the cursor points to, or `None` if the cursor is past the end.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn current(&self) -> Option<&'a A::Value> {
+        IntrusiveList::<A>::value_of(self.current)
+    }
+
+    /// Moves the cursor to the next value.
+    /// Does nothing, if the cursor is past the end.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn move_next(&mut self) {
+        if !self.current.is_null() {
+            self.prev = self.current;
+            self.current = unsafe { Link::next(self.current) };
+        }
+    }
+
+    /// Unlinks the current value from the list and returns it.
+    /// The cursor moves to the next value.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn remove_current(&mut self) -> Option<Pin<&'a A::Value>> {
+        if self.current.is_null() {
+            return None;
+        }
+
+        let removed = self.current;
+        unsafe {
+            self.current = Link::next(removed);
+            self.list.unlink_after(self.prev);
+        }
+        Some(IntrusiveList::<A>::release(removed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::pin::pin;
+
+    struct Item {
+        value: u32,
+        link: Link,
+    }
+
+    impl Item {
+        fn new(value: u32) -> Self {
+            Self {
+                value,
+                link: Link::new(),
+            }
+        }
+    }
+
+    crate::intrusive_adapter!(ItemAdapter = Item { link });
+
+    fn values(list: &IntrusiveList<ItemAdapter>) -> Vec<u32> {
+        list.iter().map(|item| item.value).collect()
+    }
+
+    #[test]
+    fn test_creation() {
+        let list = IntrusiveList::<ItemAdapter>::new();
+        assert_eq!(list.len(), 0, "not zero length after creation");
+        assert!(list.is_empty(), "not empty after creation");
+        assert!(list.head().is_none(), "not empty head after creation");
+        assert!(list.last().is_none(), "not empty last after creation");
+    }
+
+    mod push {
+        use super::*;
+
+        #[test]
+        fn test_push_back() {
+            let a = pin!(Item::new(1));
+            let b = pin!(Item::new(2));
+
+            let mut list = IntrusiveList::<ItemAdapter>::new();
+            list.push_back(a.as_ref()).unwrap();
+            assert_eq!(list.len(), 1, "bad length after push_back()");
+            assert_eq!(list.head().unwrap().value, 1, "incorrect head after push_back()");
+            assert_eq!(list.last().unwrap().value, 1, "incorrect last after push_back()");
+
+            list.push_back(b.as_ref()).unwrap();
+            assert_eq!(list.len(), 2, "bad length after push_back()");
+            assert_eq!(list.head().unwrap().value, 1, "incorrect head after push_back()");
+            assert_eq!(list.last().unwrap().value, 2, "incorrect last after push_back()");
+        }
+
+        #[test]
+        fn test_push_front() {
+            let a = pin!(Item::new(1));
+            let b = pin!(Item::new(2));
+
+            let mut list = IntrusiveList::<ItemAdapter>::new();
+            list.push_front(a.as_ref()).unwrap();
+            list.push_front(b.as_ref()).unwrap();
+            assert_eq!(values(&list), [2, 1], "push_front() must add to the front");
+            assert_eq!(list.last().unwrap().value, 1, "incorrect last after push_front()");
+        }
+
+        #[test]
+        fn test_push_already_linked() {
+            let a = pin!(Item::new(1));
+
+            let mut list = IntrusiveList::<ItemAdapter>::new();
+            list.push_back(a.as_ref()).unwrap();
+            assert_eq!(
+                list.push_back(a.as_ref()),
+                Err(DSError::AlreadyLinked),
+                "the same value must not be linked twice"
+            );
+
+            let mut other = IntrusiveList::<ItemAdapter>::new();
+            assert_eq!(
+                other.push_front(a.as_ref()),
+                Err(DSError::AlreadyLinked),
+                "the value must not be linked into two lists"
+            );
+            assert_eq!(list.len(), 1, "failed push must not change the list");
+            assert_eq!(other.len(), 0, "failed push must not change the list");
+        }
+
+        #[test]
+        fn test_push_static_values() {
+            static FIRST: Item = Item {
+                value: 1,
+                link: Link::new(),
+            };
+            static SECOND: Item = Item {
+                value: 2,
+                link: Link::new(),
+            };
+
+            let mut list = IntrusiveList::<ItemAdapter>::new();
+            list.push_back(Pin::static_ref(&FIRST)).unwrap();
+            list.push_back(Pin::static_ref(&SECOND)).unwrap();
+            assert_eq!(values(&list), [1, 2]);
+
+            drop(list);
+            assert!(!FIRST.link.is_linked(), "values must be unlinked after drop");
+            assert!(!SECOND.link.is_linked(), "values must be unlinked after drop");
+        }
+
+        #[test]
+        fn test_push_arena_values() {
+            let arena: Vec<Pin<Box<Item>>> = (0..10).map(|i| Box::pin(Item::new(i))).collect();
+
+            let mut list = IntrusiveList::<ItemAdapter>::new();
+            for item in arena.iter() {
+                list.push_front(item.as_ref()).unwrap();
+            }
+            assert_eq!(values(&list), [9, 8, 7, 6, 5, 4, 3, 2, 1, 0]);
+        }
+    }
+
+    mod pop {
+        use super::*;
+
+        #[test]
+        fn test_pop_front_empty_list() {
+            let mut list = IntrusiveList::<ItemAdapter>::new();
+            assert!(list.pop_front().is_none(), "pop_front() from empty list should return None");
+        }
+
+        #[test]
+        fn test_pop_front() {
+            let a = pin!(Item::new(1));
+            let b = pin!(Item::new(2));
+
+            let mut list = IntrusiveList::<ItemAdapter>::new();
+            list.push_back(a.as_ref()).unwrap();
+            list.push_back(b.as_ref()).unwrap();
+
+            let popped = list.pop_front().unwrap();
+            assert_eq!(popped.value, 1, "pop_front() should return first value");
+            assert!(!popped.link.is_linked(), "popped value must be unlinked");
+            assert_eq!(list.len(), 1);
+            assert_eq!(list.head().unwrap().value, 2, "head should be 2");
+            assert_eq!(list.last().unwrap().value, 2, "last should be 2");
+
+            assert_eq!(list.pop_front().unwrap().value, 2);
+            assert!(list.is_empty(), "list should be empty after all pops");
+            assert!(list.head().is_none(), "head should be None after pop");
+            assert!(list.last().is_none(), "last should be None after pop");
+        }
+
+        #[test]
+        fn test_relink_after_pop() {
+            let a = pin!(Item::new(1));
+
+            let mut first = IntrusiveList::<ItemAdapter>::new();
+            let mut second = IntrusiveList::<ItemAdapter>::new();
+            first.push_back(a.as_ref()).unwrap();
+
+            let popped = first.pop_front().unwrap();
+            assert!(second.push_back(popped).is_ok(), "unlinked value must be reusable");
+            assert_eq!(values(&second), [1]);
+        }
+
+        #[test]
+        fn test_clear() {
+            let a = pin!(Item::new(1));
+            let b = pin!(Item::new(2));
+
+            let mut list = IntrusiveList::<ItemAdapter>::new();
+            list.push_back(a.as_ref()).unwrap();
+            list.push_back(b.as_ref()).unwrap();
+            list.clear();
+
+            assert!(list.is_empty(), "list should be empty after clear()");
+            assert!(!a.link.is_linked(), "values must be unlinked after clear()");
+            assert!(!b.link.is_linked(), "values must be unlinked after clear()");
+        }
+    }
+
+    mod cursor {
+        use super::*;
+
+        fn remove_where(list: &mut IntrusiveList<ItemAdapter>, predicate: impl Fn(u32) -> bool) -> Vec<u32> {
+            let mut removed = vec![];
+            let mut cursor = list.cursor_mut();
+            while let Some(item) = cursor.current() {
+                if predicate(item.value) {
+                    removed.push(cursor.remove_current().unwrap().value);
+                } else {
+                    cursor.move_next();
+                }
+            }
+            removed
+        }
+
+        #[test]
+        fn test_cursor_empty_list() {
+            let mut list = IntrusiveList::<ItemAdapter>::new();
+            let mut cursor = list.cursor_mut();
+            assert!(cursor.current().is_none(), "cursor over empty list must be past the end");
+            assert!(cursor.remove_current().is_none(), "nothing to remove in empty list");
+        }
+
+        #[test]
+        fn test_cursor_remove_positions() {
+            let arena: Vec<Pin<Box<Item>>> = (0..6).map(|i| Box::pin(Item::new(i))).collect();
+            let extra = pin!(Item::new(7));
+
+            let mut list = IntrusiveList::<ItemAdapter>::new();
+            for item in arena.iter() {
+                list.push_back(item.as_ref()).unwrap();
+            }
+
+            // head, middle and tail values
+            let removed = remove_where(&mut list, |v| v == 0 || v == 3 || v == 5);
+            assert_eq!(removed, [0, 3, 5]);
+            assert_eq!(values(&list), [1, 2, 4]);
+            assert_eq!(list.len(), 3, "size must decrease after removal");
+            assert_eq!(list.head().unwrap().value, 1, "head must be updated");
+            assert_eq!(list.last().unwrap().value, 4, "last must be updated");
+            assert!(!arena[5].link.is_linked(), "removed value must be unlinked");
+
+            // The list must remain consistent for pushes after removal
+            list.push_back(extra.as_ref()).unwrap();
+            assert_eq!(values(&list), [1, 2, 4, 7]);
+        }
+
+        #[test]
+        fn test_cursor_remove_all() {
+            let arena: Vec<Pin<Box<Item>>> = (0..4).map(|i| Box::pin(Item::new(i))).collect();
+
+            let mut list = IntrusiveList::<ItemAdapter>::new();
+            for item in arena.iter() {
+                list.push_back(item.as_ref()).unwrap();
+            }
+
+            assert_eq!(remove_where(&mut list, |_| true), [0, 1, 2, 3]);
+            assert!(list.is_empty(), "list should be empty");
+            assert!(list.head().is_none(), "head should be None");
+            assert!(list.last().is_none(), "last should be None");
+        }
+
+        #[test]
+        fn test_cursor_move_past_end() {
+            let a = pin!(Item::new(1));
+
+            let mut list = IntrusiveList::<ItemAdapter>::new();
+            list.push_back(a.as_ref()).unwrap();
+
+            let mut cursor = list.cursor_mut();
+            assert_eq!(cursor.current().unwrap().value, 1);
+            cursor.move_next();
+            assert!(cursor.current().is_none(), "cursor must be past the end");
+            cursor.move_next();
+            assert!(cursor.remove_current().is_none(), "nothing to remove past the end");
+            assert_eq!(list.len(), 1);
+        }
+    }
+}
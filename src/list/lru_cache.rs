@@ -0,0 +1,584 @@
+//! This module contains LRU cache implementation.
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::ptr;
+
+use super::common::ListCommon;
+use super::node_one_link::{Iter, Node};
+
+// Cache entry stored in the recency list.
+struct Entry<K, V> {
+    key: K,
+    value: V,
+}
+
+// A reference to the key stored in the node of the recency list.
+struct KeyRef<K>(*const K);
+
+impl<K: Hash> Hash for KeyRef<K> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        unsafe { (*self.0).hash(state) }
+    }
+}
+
+impl<K: PartialEq> PartialEq for KeyRef<K> {
+    fn eq(&self, other: &Self) -> bool {
+        unsafe { *self.0 == *other.0 }
+    }
+}
+
+impl<K: Eq> Eq for KeyRef<K> {}
+
+// Allows to search the index by any borrowed form of the key.
+#[repr(transparent)]
+struct KeyWrapper<Q: ?Sized>(Q);
+
+impl<Q: ?Sized> KeyWrapper<Q> {
+    fn from_ref(key: &Q) -> &Self {
+        // Safety: `KeyWrapper` is transparent
+        unsafe { &*(key as *const Q as *const KeyWrapper<Q>) }
+    }
+}
+
+impl<Q: ?Sized + Hash> Hash for KeyWrapper<Q> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl<Q: ?Sized + PartialEq> PartialEq for KeyWrapper<Q> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<Q: ?Sized + Eq> Eq for KeyWrapper<Q> {}
+
+impl<K, Q: ?Sized> Borrow<KeyWrapper<Q>> for KeyRef<K>
+where
+    K: Borrow<Q>,
+{
+    fn borrow(&self) -> &KeyWrapper<Q> {
+        KeyWrapper::from_ref(unsafe { (*self.0).borrow() })
+    }
+}
+
+type EntryNode<K, V> = Node<Entry<K, V>>;
+
+/// A bounded map which evicts the least recently used entry when it is full.
+///
+/// The entries are kept in a singly-linked recency list, from the most recently used
+/// to the least recently used one. The hash index maps every key to the *predecessor*
+/// of its node, so any node can be unlinked or moved to the front of the list in O(1).
+///
+/// **Efficiency**: all operations, except `iter`, `clear` and `resize`, are O(1)
+///
+/// # Type Parameters
+/// * `K`: The type of keys. Must implement `Hash` and `Eq`.
+/// * `V`: The type of values.
+///
+/// # Examples
+/// ```
+/// use plain_ds::list::LruCache;
+///
+/// let mut cache = LruCache::new(2);
+/// cache.put("a", 1);
+/// cache.put("b", 2);
+/// assert_eq!(cache.get(&"a"), Some(&1)); // "a" becomes the most recently used
+///
+/// cache.put("c", 3); // "b" is evicted
+/// assert_eq!(cache.peek(&"b"), None);
+/// assert_eq!(cache.iter().collect::<Vec<_>>(), vec![(&"c", &3), (&"a", &1)]);
+/// ```
+pub struct LruCache<K, V> {
+    list: ListCommon<Entry<K, V>>,
+    index: HashMap<KeyRef<K>, *mut EntryNode<K, V>>,
+    capacity: usize,
+    on_evict: Option<Box<dyn FnMut(K, V)>>,
+}
+
+impl<K: Hash + Eq, V> LruCache<K, V> {
+    /// Creates empty cache which can hold at most `capacity` entries.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            list: ListCommon::new(),
+            index: HashMap::with_capacity(capacity),
+            capacity,
+            on_evict: None,
+        }
+    }
+
+    /// Creates empty cache which can hold at most `capacity` entries.
+    /// The `callback` is called for every entry evicted by `put` or `resize`.
+    pub fn with_eviction_callback(capacity: usize, callback: impl FnMut(K, V) + 'static) -> Self {
+        let mut cache = Self::new(capacity);
+        cache.on_evict = Some(Box::new(callback));
+        cache
+    }
+
+    /// Returns the number of entries in the cache.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    /// Checks if the cache is empty.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the maximum number of entries in the cache.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Checks if the cache contains `key`. Does not change the recency of the entry.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.index.contains_key(KeyWrapper::from_ref(key))
+    }
+
+    /// Returns the value by `key` and marks the entry as the most recently used.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let node = self.touch(key)?;
+        Some(unsafe { &(*node).payload.value })
+    }
+
+    /// Returns the mutable value by `key` and marks the entry as the most recently used.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let node = self.touch(key)?;
+        Some(unsafe { &mut (*node).payload.value })
+    }
+
+    /// Returns the value by `key`. Does not change the recency of the entry.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let prev = *self.index.get(KeyWrapper::from_ref(key))?;
+        Some(unsafe { &(*self.list.node_after(prev)).payload.value })
+    }
+
+    /// Returns the least recently used entry. Does not change the recency of the entry.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn peek_lru(&self) -> Option<(&K, &V)> {
+        if self.list.last.is_null() {
+            None
+        } else {
+            let entry = unsafe { &(*self.list.last).payload };
+            Some((&entry.key, &entry.value))
+        }
+    }
+
+    /// Inserts the entry into the cache and marks it as the most recently used.
+    /// If the cache already contains `key`, replaces its value and returns the old one.
+    /// If the cache is full, the least recently used entry is evicted.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn put(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(node) = self.touch(&key) {
+            return Some(std::mem::replace(unsafe { &mut (*node).payload.value }, value));
+        }
+
+        if self.capacity == 0 {
+            self.evict(key, value);
+            return None;
+        }
+        if self.len() == self.capacity
+            && let Some((key, value)) = self.pop_lru()
+        {
+            self.evict(key, value);
+        }
+
+        let node = Box::into_raw(Box::new(Node::new(Entry { key, value })));
+        self.link_front(node);
+        self.list.size += 1;
+        None
+    }
+
+    /// Removes the entry by `key` and returns its value.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let prev = self.index.remove(KeyWrapper::from_ref(key))?;
+        let node = self.unlink(prev);
+        self.list.size -= 1;
+        Some(unsafe { Box::from_raw(node) }.payload.value)
+    }
+
+    /// Removes the least recently used entry and returns it.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn pop_lru(&mut self) -> Option<(K, V)> {
+        if self.list.last.is_null() {
+            return None;
+        }
+
+        let key = KeyRef(unsafe { &(*self.list.last).payload.key } as *const K);
+        let prev = self.index.remove(&key).expect("LRU entry must be indexed");
+        let node = self.unlink(prev);
+        self.list.size -= 1;
+
+        let entry = unsafe { Box::from_raw(node) }.payload;
+        Some((entry.key, entry.value))
+    }
+
+    /// Changes the capacity of the cache.
+    /// If the cache contains more entries, the least recently used ones are evicted.
+    ///
+    /// **Efficiency**: O(m), where `m` is a number of evicted entries
+    pub fn resize(&mut self, capacity: usize) {
+        while self.len() > capacity {
+            if let Some((key, value)) = self.pop_lru() {
+                self.evict(key, value);
+            }
+        }
+        self.capacity = capacity;
+    }
+
+    /// Removes all entries from the cache. The eviction callback is not called.
+    ///
+    /// **Efficiency**: O(n)
+    pub fn clear(&mut self) {
+        self.index.clear();
+        self.list = ListCommon::new();
+    }
+
+    /// Returns an iterator over the entries, from the most recently used to the least recently used.
+    /// Does not change the recency of the entries.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        Iter::new(self.list.head).map(|entry| (&entry.key, &entry.value))
+    }
+
+    // Moves the entry by `key` to the front of the list.
+    fn touch<Q>(&mut self, key: &Q) -> Option<*mut EntryNode<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let prev = *self.index.get(KeyWrapper::from_ref(key))?;
+        if prev.is_null() {
+            // Already the most recently used
+            return Some(self.list.head);
+        }
+        let node = self.unlink(prev);
+        self.link_front(node);
+        Some(node)
+    }
+
+    // Detaches the node which follows `prev` and fixes the index of its successor.
+    // The index entry of the detached node is not changed.
+    fn unlink(&mut self, prev: *mut EntryNode<K, V>) -> *mut EntryNode<K, V> {
+        let node = self.list.node_after(prev);
+        let next = unsafe { (*node).next };
+
+        if prev.is_null() {
+            self.list.head = next;
+        } else {
+            unsafe { (*prev).next = next };
+        }
+
+        if next.is_null() {
+            self.list.last = prev;
+        } else {
+            self.set_prev(next, prev);
+        }
+
+        unsafe { (*node).next = ptr::null_mut() };
+        node
+    }
+
+    // Links the detached node to the front of the list and indexes it.
+    fn link_front(&mut self, node: *mut EntryNode<K, V>) {
+        let head = self.list.head;
+        if head.is_null() {
+            self.list.last = node;
+        } else {
+            self.set_prev(head, node);
+        }
+        unsafe { (*node).next = head };
+        self.list.head = node;
+        self.set_prev(node, ptr::null_mut());
+    }
+
+    fn set_prev(&mut self, node: *mut EntryNode<K, V>, prev: *mut EntryNode<K, V>) {
+        let key = KeyRef(unsafe { &(*node).payload.key } as *const K);
+        self.index.insert(key, prev);
+    }
+
+    fn evict(&mut self, key: K, value: V) {
+        if let Some(callback) = self.on_evict.as_mut() {
+            callback(key, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    fn keys<V>(cache: &LruCache<i32, V>) -> Vec<i32> {
+        cache.iter().map(|(key, _)| *key).collect()
+    }
+
+    fn setup_cache(capacity: usize, keys: &[i32]) -> LruCache<i32, i32> {
+        let mut cache = LruCache::new(capacity);
+        for key in keys {
+            cache.put(*key, key * 10);
+        }
+        cache
+    }
+
+    #[test]
+    fn test_creation() {
+        let cache: LruCache<i32, i32> = LruCache::new(3);
+        assert_eq!(cache.len(), 0, "not zero length after creation");
+        assert!(cache.is_empty(), "not empty after creation");
+        assert_eq!(cache.capacity(), 3, "bad capacity after creation");
+        assert_eq!(cache.peek_lru(), None, "not empty LRU entry after creation");
+    }
+
+    mod put {
+        use super::*;
+
+        #[test]
+        fn test_put_orders_by_recency() {
+            let cache = setup_cache(5, &[1, 2, 3]);
+            assert_eq!(cache.len(), 3, "bad length after put()");
+            assert_eq!(keys(&cache), [3, 2, 1], "iteration must go from MRU to LRU");
+            assert_eq!(cache.peek_lru(), Some((&1, &10)));
+        }
+
+        #[test]
+        fn test_put_existing_key_replaces_value() {
+            let mut cache = setup_cache(3, &[1, 2, 3]);
+            assert_eq!(cache.put(1, 100), Some(10), "put() must return the old value");
+            assert_eq!(cache.len(), 3, "replacing must not change the size");
+            assert_eq!(keys(&cache), [1, 3, 2], "replaced entry must become MRU");
+            assert_eq!(cache.peek(&1), Some(&100));
+        }
+
+        #[test]
+        fn test_put_evicts_lru() {
+            let mut cache = setup_cache(3, &[1, 2, 3]);
+            assert_eq!(cache.put(4, 40), None);
+            assert_eq!(cache.len(), 3, "size must not exceed the capacity");
+            assert!(!cache.contains(&1), "LRU entry must be evicted");
+            assert_eq!(keys(&cache), [4, 3, 2]);
+        }
+
+        #[test]
+        fn test_put_calls_eviction_callback() {
+            let evicted = Rc::new(RefCell::new(vec![]));
+            let sink = evicted.clone();
+            let mut cache = LruCache::with_eviction_callback(2, move |key, value| {
+                sink.borrow_mut().push((key, value));
+            });
+
+            cache.put(1, 10);
+            cache.put(2, 20);
+            cache.get(&1);
+            cache.put(3, 30);
+            cache.put(4, 40);
+
+            assert_eq!(*RefCell::borrow(&evicted), [(2, 20), (1, 10)]);
+            assert_eq!(keys(&cache), [4, 3]);
+        }
+
+        #[test]
+        fn test_put_zero_capacity() {
+            let evicted = Rc::new(RefCell::new(vec![]));
+            let sink = evicted.clone();
+            let mut cache = LruCache::with_eviction_callback(0, move |key, _| {
+                sink.borrow_mut().push(key);
+            });
+
+            cache.put(1, 10);
+            assert!(cache.is_empty(), "zero capacity cache must stay empty");
+            assert_eq!(*RefCell::borrow(&evicted), [1], "new entry must be evicted at once");
+        }
+    }
+
+    mod get {
+        use super::*;
+
+        #[test]
+        fn test_get_touches_entry() {
+            let mut cache = setup_cache(3, &[1, 2, 3]);
+
+            assert_eq!(cache.get(&2), Some(&20)); // middle
+            assert_eq!(keys(&cache), [2, 3, 1]);
+            assert_eq!(cache.get(&1), Some(&10)); // last
+            assert_eq!(keys(&cache), [1, 2, 3]);
+            assert_eq!(cache.get(&1), Some(&10)); // head
+            assert_eq!(keys(&cache), [1, 2, 3]);
+            assert_eq!(cache.peek_lru(), Some((&3, &30)), "LRU entry must be updated");
+        }
+
+        #[test]
+        fn test_get_missing_key() {
+            let mut cache = setup_cache(3, &[1, 2]);
+            assert_eq!(cache.get(&5), None);
+            assert_eq!(keys(&cache), [2, 1], "missing key must not change the order");
+        }
+
+        #[test]
+        fn test_get_mut() {
+            let mut cache = setup_cache(3, &[1, 2, 3]);
+            *cache.get_mut(&1).unwrap() += 1;
+            assert_eq!(cache.peek(&1), Some(&11));
+            assert_eq!(keys(&cache), [1, 3, 2]);
+        }
+
+        #[test]
+        fn test_peek_does_not_touch() {
+            let cache = setup_cache(3, &[1, 2, 3]);
+            assert_eq!(cache.peek(&1), Some(&10));
+            assert_eq!(cache.peek(&4), None);
+            assert_eq!(keys(&cache), [3, 2, 1], "peek() must not change the order");
+        }
+
+        #[test]
+        fn test_get_by_borrowed_key() {
+            let mut cache = LruCache::new(2);
+            cache.put("one".to_string(), 1);
+            cache.put("two".to_string(), 2);
+
+            assert_eq!(cache.get("one"), Some(&1));
+            assert!(cache.contains("two"));
+            assert_eq!(cache.remove("two"), Some(2));
+        }
+    }
+
+    mod remove {
+        use super::*;
+
+        #[test]
+        fn test_remove_positions() {
+            let mut cache = setup_cache(5, &[1, 2, 3, 4, 5]);
+
+            assert_eq!(cache.remove(&5), Some(50)); // head
+            assert_eq!(cache.remove(&3), Some(30)); // middle
+            assert_eq!(cache.remove(&1), Some(10)); // last
+            assert_eq!(cache.remove(&1), None);
+            assert_eq!(keys(&cache), [4, 2]);
+            assert_eq!(cache.len(), 2);
+            assert_eq!(cache.peek_lru(), Some((&2, &20)));
+
+            // Index must remain consistent after removals
+            assert_eq!(cache.get(&2), Some(&20));
+            assert_eq!(keys(&cache), [2, 4]);
+        }
+
+        #[test]
+        fn test_pop_lru() {
+            let mut cache = setup_cache(3, &[1, 2, 3]);
+            cache.get(&1);
+
+            assert_eq!(cache.pop_lru(), Some((2, 20)));
+            assert_eq!(cache.pop_lru(), Some((3, 30)));
+            assert_eq!(cache.pop_lru(), Some((1, 10)));
+            assert_eq!(cache.pop_lru(), None);
+            assert!(cache.is_empty());
+        }
+
+        #[test]
+        fn test_clear() {
+            let mut cache = setup_cache(3, &[1, 2, 3]);
+            cache.clear();
+            assert!(cache.is_empty(), "cache must be empty after clear()");
+            assert!(!cache.contains(&1));
+
+            cache.put(4, 40);
+            assert_eq!(keys(&cache), [4], "cache must be usable after clear()");
+        }
+    }
+
+    mod resize {
+        use super::*;
+
+        #[test]
+        fn test_resize_shrink_evicts_lru() {
+            let evicted = Rc::new(RefCell::new(vec![]));
+            let sink = evicted.clone();
+            let mut cache = LruCache::with_eviction_callback(4, move |key, _| {
+                sink.borrow_mut().push(key);
+            });
+            for key in 1..=4 {
+                cache.put(key, key);
+            }
+
+            cache.resize(2);
+            assert_eq!(cache.capacity(), 2);
+            assert_eq!(keys(&cache), [4, 3]);
+            assert_eq!(*RefCell::borrow(&evicted), [1, 2]);
+        }
+
+        #[test]
+        fn test_resize_grow() {
+            let mut cache = setup_cache(2, &[1, 2]);
+            cache.resize(3);
+            cache.put(3, 30);
+            assert_eq!(keys(&cache), [3, 2, 1], "grown cache must not evict");
+        }
+    }
+
+    mod memory_leaks {
+        use super::*;
+        use drop_tracker::DropTracker;
+
+        #[test]
+        fn test_memory_leaks() {
+            let mut tracker = DropTracker::new();
+
+            let mut cache = LruCache::new(10);
+            for i in 0..20 {
+                cache.put(i, tracker.track(i));
+            }
+            assert_eq!(tracker.alive().count(), 10, "evicted values must be dropped");
+
+            let _ = cache.remove(&15);
+            let _ = cache.pop_lru();
+            cache.put(19, tracker.track(100));
+            assert_eq!(tracker.alive().count(), 8);
+
+            drop(cache);
+            assert_eq!(tracker.alive().count(), 0);
+            assert_eq!(tracker.dropped().count(), 21);
+        }
+    }
+}
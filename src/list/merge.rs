@@ -0,0 +1,231 @@
+//! This module contains lazy k-way merge of sorted iterators.
+
+use std::cmp::Ordering;
+use std::iter::FusedIterator;
+
+/// Creates a lazy iterator which merges the sorted `iters` into one sorted sequence.
+/// Every iterator must be sorted according to `compare`.
+///
+/// The merge is stable: equal elements go in the order of the iterators, and keep
+/// their order within every iterator.
+///
+/// **Efficiency**: O(log k) per element, where k is the number of iterators
+///
+/// # Examples
+/// ```
+/// use plain_ds::list::merge_iters;
+///
+/// let shards = vec![vec![1, 4, 7], vec![2, 5], vec![3, 6]];
+/// let merged: Vec<_> = merge_iters(shards, |a, b| a.cmp(b)).collect();
+/// assert_eq!(merged, vec![1, 2, 3, 4, 5, 6, 7]);
+///
+/// let descending: Vec<_> = merge_iters([vec![9, 1], vec![5]], |a, b| b.cmp(a)).collect();
+/// assert_eq!(descending, vec![9, 5, 1]);
+/// ```
+pub fn merge_iters<I, F>(iters: impl IntoIterator<Item = I>, compare: F) -> MergeIters<I::IntoIter, F>
+where
+    I: IntoIterator,
+    F: FnMut(&I::Item, &I::Item) -> Ordering,
+{
+    let mut merge = MergeIters {
+        heap: Vec::new(),
+        compare,
+    };
+    for (source, iter) in iters.into_iter().enumerate() {
+        let mut iter = iter.into_iter();
+        if let Some(item) = iter.next() {
+            merge.heap.push(Head { item, source, iter });
+            merge.sift_up(merge.heap.len() - 1);
+        }
+    }
+    merge
+}
+
+/// Iterator which merges sorted iterators, created by [`merge_iters`].
+pub struct MergeIters<I: Iterator, F> {
+    // Binary min-heap of the current items of the iterators
+    heap: Vec<Head<I>>,
+    compare: F,
+}
+
+// The current item of the iterator with the index of the iterator among the merged ones.
+struct Head<I: Iterator> {
+    item: I::Item,
+    source: usize,
+    iter: I,
+}
+
+impl<I, F> MergeIters<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item, &I::Item) -> Ordering,
+{
+    // Checks if the head `a` goes before the head `b` (equal items go by the iterator index)
+    fn goes_before(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (&self.heap[a], &self.heap[b]);
+        match (self.compare)(&a.item, &b.item) {
+            Ordering::Less => true,
+            Ordering::Greater => false,
+            Ordering::Equal => a.source < b.source,
+        }
+    }
+
+    fn sift_up(&mut self, mut index: usize) {
+        while index > 0 {
+            let parent = (index - 1) / 2;
+            if !self.goes_before(index, parent) {
+                break;
+            }
+            self.heap.swap(index, parent);
+            index = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut index: usize) {
+        loop {
+            let mut first = index;
+            for child in [2 * index + 1, 2 * index + 2] {
+                if child < self.heap.len() && self.goes_before(child, first) {
+                    first = child;
+                }
+            }
+            if first == index {
+                break;
+            }
+            self.heap.swap(index, first);
+            index = first;
+        }
+    }
+}
+
+impl<I, F> Iterator for MergeIters<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item, &I::Item) -> Ordering,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let top = self.heap.first_mut()?;
+        let item = match top.iter.next() {
+            Some(next) => std::mem::replace(&mut top.item, next),
+            None => self.heap.swap_remove(0).item,
+        };
+        if !self.heap.is_empty() {
+            self.sift_down(0);
+        }
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.heap.iter().fold((0, Some(0)), |(lower, upper), head| {
+            let (head_lower, head_upper) = head.iter.size_hint();
+            (
+                lower.saturating_add(head_lower).saturating_add(1),
+                upper
+                    .zip(head_upper)
+                    .and_then(|(upper, head_upper)| upper.checked_add(head_upper)?.checked_add(1)),
+            )
+        })
+    }
+}
+
+// The exhausted iterators are removed from the heap, so they are never polled again
+impl<I, F> FusedIterator for MergeIters<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item, &I::Item) -> Ordering,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy)]
+    struct Record {
+        key: u8,
+        source: u8,
+        seq: u8,
+    }
+
+    fn records(source: u8, keys: &[u8]) -> Vec<Record> {
+        keys.iter()
+            .enumerate()
+            .map(|(seq, key)| Record {
+                key: *key,
+                source,
+                seq: seq as u8,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_merge() {
+        let merged: Vec<_> = merge_iters([vec![1, 3, 5], vec![2, 4, 6], vec![0, 7]], u8::cmp).collect();
+        assert_eq!(merged, [0, 1, 2, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn test_empty_inputs() {
+        let merged: Vec<u8> = merge_iters(Vec::<Vec<u8>>::new(), u8::cmp).collect();
+        assert!(merged.is_empty(), "no iterators");
+
+        let merged: Vec<_> = merge_iters([vec![], vec![1, 2], vec![]], u8::cmp).collect();
+        assert_eq!(merged, [1, 2], "empty iterators must be skipped");
+    }
+
+    #[test]
+    fn test_stability() {
+        let inputs = [
+            records(0, &[1, 2, 2, 5]),
+            records(1, &[2, 2, 3]),
+            records(2, &[1, 2, 5, 5]),
+        ];
+        let merged: Vec<_> = merge_iters(inputs, |a, b| a.key.cmp(&b.key))
+            .map(|record| (record.key, record.source, record.seq))
+            .collect();
+        assert_eq!(
+            merged,
+            [
+                (1, 0, 0),
+                (1, 2, 0),
+                (2, 0, 1),
+                (2, 0, 2),
+                (2, 1, 0),
+                (2, 1, 1),
+                (2, 2, 1),
+                (3, 1, 2),
+                (5, 0, 3),
+                (5, 2, 2),
+                (5, 2, 3),
+            ],
+            "equal elements must go in the order of the iterators"
+        );
+    }
+
+    #[test]
+    fn test_many_iterators() {
+        let inputs: Vec<Vec<u32>> = (0..50).map(|shard| (0..20).map(|i| i * 50 + shard).collect()).collect();
+        let merged: Vec<_> = merge_iters(inputs, u32::cmp).collect();
+        assert_eq!(merged, (0..1000).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_lazy() {
+        let pulled = std::cell::Cell::new(0);
+        let counted = |range: std::ops::RangeFrom<u32>| range.inspect(|_| pulled.set(pulled.get() + 1));
+        let first: Vec<_> = merge_iters([counted(0..), counted(1..)], u32::cmp).take(4).collect();
+        assert_eq!(first, [0, 1, 1, 2]);
+        assert!(pulled.get() <= 6, "merge must not pull more items than needed");
+    }
+
+    #[test]
+    fn test_size_hint() {
+        let mut merge = merge_iters([vec![1, 2, 3], vec![4]], u8::cmp);
+        assert_eq!(merge.size_hint(), (4, Some(4)));
+        merge.next();
+        assert_eq!(merge.size_hint(), (3, Some(3)));
+        assert_eq!(merge.count(), 3);
+    }
+}
@@ -1,9 +1,42 @@
+mod adapters;
 mod api;
+mod bounded;
+mod chain;
 mod common;
+#[cfg(any(test, feature = "testing"))]
+mod conformance;
+mod convert;
+mod dyn_list;
+mod handle;
+mod intrusive;
+mod lru_cache;
+mod merge;
 mod node_one_link;
+mod priority_queue;
+mod ring_buffer;
+mod self_organizing;
 mod singly_linked;
+mod sliding_window;
 mod sorted;
+mod sorted_map;
+mod span_index;
+#[cfg(feature = "std-lists")]
+mod std_impls;
 
+pub use adapters::{AdapterIntoIter, Deque, Queue, Stack};
 pub use api::List;
+pub use bounded::Overflow;
+pub use dyn_list::DynList;
+pub use handle::ListHandle;
+pub use intrusive::{Adapter, CursorMut, IntrusiveIter, IntrusiveList, Link};
+pub use lru_cache::LruCache;
+pub use merge::{MergeIters, merge_iters};
+pub use priority_queue::{
+    Max, MaxPriorityQueue, Min, MinPriorityQueue, Order, PriorityQueue, QueueHandle,
+};
+pub use ring_buffer::RingBuffer;
+pub use self_organizing::{AccessStats, Heuristic, SelfOrganizingList};
 pub use singly_linked::SinglyLinkedList;
-pub use sorted::SortedList;
+pub use sliding_window::{CheckedSub, SlidingWindow};
+pub use sorted::{EqualOrder, Keep, SortedList};
+pub use sorted_map::{MapEntry, OccupiedEntry, SortedMap, VacantEntry};
@@ -1,90 +1,145 @@
 use super::Node;
+use std::mem;
+use std::ptr;
 
-/// Merge sort implementation for single linked nodes
-pub fn merge_sort<T>(head: *mut Node<T>) -> *mut Node<T>
+// The maximum number of sorted runs: the run on level `i` has 2^i nodes
+const MAX_RUNS: usize = usize::BITS as usize;
+
+/// Stable merge sort implementation for single linked nodes.
+/// `head` is the head of the null-terminated chain, it is replaced with the head of the sorted one.
+///
+/// Panic safety: if a comparison panics, all nodes are linked back into one (not sorted)
+/// chain starting at `head`, so they are still owned by the caller.
+pub fn merge_sort<T>(head: &mut *mut Node<T>)
 where
-    T: PartialOrd + Default,
+    T: PartialOrd,
 {
-    // Base case: empty or single node
-    if head.is_null() || unsafe { (*head).next.is_null() } {
-        return head;
-    }
-
-    // Split the list into two halves
-    let (left, right) = split_list(head);
-
-    // Recursively sort both halves
-    let left_sorted = merge_sort(left);
-    let right_sorted = merge_sort(right);
-
-    // Merge the sorted halves
-    merge(left_sorted, right_sorted)
+    let mut sorter = Sorter {
+        input: mem::replace(head, ptr::null_mut()),
+        output: head,
+        runs: [ptr::null_mut(); MAX_RUNS],
+        older: ptr::null_mut(),
+        newer: ptr::null_mut(),
+    };
+    sorter.sort();
 }
 
-/// Splits the list into two approximately equal halves
-fn split_list<T>(head: *mut Node<T>) -> (*mut Node<T>, *mut Node<T>) {
-    let mut slow = head;
-    let mut fast = unsafe { (*head).next };
+/// Merges the sorted chain `right` into the sorted chain `left`.
+/// Equal nodes from `left` are placed before the nodes from `right`.
+///
+/// Panic safety: every node is always reachable either from `left` or from `right`,
+/// so if a comparison panics, both chains are valid (but not merged).
+pub fn merge<T>(left: &mut *mut Node<T>, right: &mut *mut Node<T>)
+where
+    T: PartialOrd,
+{
+    // `left` is the result followed by the rest of the left nodes: `tail` is the last
+    // node of the result, `rest` is the first of the left nodes which are not merged yet
+    let mut tail: *mut Node<T> = ptr::null_mut();
+    let mut rest = *left;
+
+    unsafe {
+        while !rest.is_null() && !right.is_null() {
+            let node = *right;
+            if (*node).payload < (*rest).payload {
+                // Move the node from right before the rest of left
+                *right = (*node).next;
+                (*node).next = rest;
+                if tail.is_null() {
+                    *left = node;
+                } else {
+                    (*tail).next = node;
+                }
+                tail = node;
+            } else {
+                // The left node is already in place
+                tail = rest;
+                rest = (*rest).next;
+            }
+        }
 
-    // Use fast and slow pointers to find the middle
-    while !fast.is_null() {
-        fast = unsafe { (*fast).next };
-        if !fast.is_null() {
-            slow = unsafe { (*slow).next };
-            fast = unsafe { (*fast).next };
+        // Attach remaining right nodes
+        if rest.is_null() && !right.is_null() {
+            if tail.is_null() {
+                *left = *right;
+            } else {
+                (*tail).next = *right;
+            }
+            *right = ptr::null_mut();
         }
     }
+}
 
-    // Split at the slow pointer
-    let right_head = unsafe { (*slow).next };
-    unsafe { (*slow).next = std::ptr::null_mut() };
-
-    (head, right_head)
+// Bottom-up merge sort which keeps every node reachable from its fields,
+// so the nodes are linked back into one chain on drop (also on unwinding).
+struct Sorter<'a, T> {
+    output: &'a mut *mut Node<T>,
+    // The rest of the input chain
+    input: *mut Node<T>,
+    // Sorted runs: `runs[i]` is null or has 2^i nodes, the higher runs are older
+    runs: [*mut Node<T>; MAX_RUNS],
+    // Operands of the current merge
+    older: *mut Node<T>,
+    newer: *mut Node<T>,
 }
 
-/// Merges two sorted linked lists into one sorted list
-fn merge<T>(mut left: *mut Node<T>, mut right: *mut Node<T>) -> *mut Node<T>
-where
-    T: PartialOrd + Default,
-{
-    // Dummy node to simplify merging logic
-    let dummy = Box::new(Node {
-        payload: T::default(), // Placeholder, will be ignored
-        next: std::ptr::null_mut(),
-    });
-    let tail = Box::into_raw(dummy);
+impl<T: PartialOrd> Sorter<'_, T> {
+    fn sort(&mut self) {
+        while !self.input.is_null() {
+            // Detach the next node as a run of one node
+            let node = self.input;
+            unsafe {
+                self.input = (*node).next;
+                (*node).next = ptr::null_mut();
+            }
+            self.newer = node;
 
-    // Keep track of the actual head (skip dummy)
-    let mut actual_tail = tail;
+            // Merge the runs of equal length like in binary addition
+            let mut level = 0;
+            while !self.runs[level].is_null() {
+                self.older = mem::replace(&mut self.runs[level], ptr::null_mut());
+                self.merge();
+                level += 1;
+            }
+            self.runs[level] = mem::replace(&mut self.newer, ptr::null_mut());
+        }
 
-    while !left.is_null() && !right.is_null() {
-        unsafe {
-            if (*left).payload <= (*right).payload {
-                // Take from left single_linked
-                (*actual_tail).next = left;
-                actual_tail = left;
-                left = (*left).next;
-            } else {
-                // Take from right single_linked
-                (*actual_tail).next = right;
-                actual_tail = right;
-                right = (*right).next;
+        // Merge all runs from the newest to the oldest
+        for level in 0..MAX_RUNS {
+            if !self.runs[level].is_null() {
+                self.older = mem::replace(&mut self.runs[level], ptr::null_mut());
+                self.merge();
             }
         }
     }
 
-    // Attach remaining nodes
-    if !left.is_null() {
-        unsafe { (*actual_tail).next = left };
-    } else if !right.is_null() {
-        unsafe { (*actual_tail).next = right };
+    // Merges `newer` into `older` and moves the result into `newer`.
+    fn merge(&mut self) {
+        merge(&mut self.older, &mut self.newer);
+        self.newer = mem::replace(&mut self.older, ptr::null_mut());
     }
+}
 
-    // The real head is the next of dummy node
-    let result_head = unsafe { (*tail).next };
-
-    // Free the dummy node
-    let _ = unsafe { Box::from_raw(tail) };
-
-    result_head
+impl<T> Drop for Sorter<'_, T> {
+    fn drop(&mut self) {
+        // After the successful sort, all nodes are in `newer`
+        let chains = [self.newer, self.older, self.input].into_iter().chain(self.runs);
+        let mut head: *mut Node<T> = ptr::null_mut();
+        // The first node of the last appended chain
+        let mut last_chain: *mut Node<T> = ptr::null_mut();
+        for chain in chains.filter(|chain| !chain.is_null()) {
+            if head.is_null() {
+                head = chain;
+            } else {
+                unsafe {
+                    while !(*last_chain).next.is_null() {
+                        last_chain = (*last_chain).next;
+                    }
+                    (*last_chain).next = chain;
+                }
+            }
+            last_chain = chain;
+        }
+        *self.output = head;
+    }
 }
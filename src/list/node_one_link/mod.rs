@@ -6,4 +6,4 @@ mod iter_mut;
 pub use node::Node;
 pub use iter::Iter;
 pub use iter_mut::IterMut;
-pub use merge_sort::merge_sort;
\ No newline at end of file
+pub use merge_sort::{merge, merge_sort};
\ No newline at end of file
@@ -1,5 +1,7 @@
 use std::ptr;
 
+use crate::list::chain::ChainLink;
+
 #[derive(PartialEq, Debug)]
 pub struct Node<T> {
     pub next: *mut Node<T>, // 8 bytes
@@ -14,3 +16,13 @@ impl<T> Node<T> {
         }
     }
 }
+
+impl<T> ChainLink for Node<T> {
+    unsafe fn next(link: *mut Self) -> *mut Self {
+        unsafe { (*link).next }
+    }
+
+    unsafe fn set_next(link: *mut Self, next: *mut Self) {
+        unsafe { (*link).next = next };
+    }
+}
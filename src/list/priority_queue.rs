@@ -0,0 +1,530 @@
+//! This module contains priority queue implementation.
+
+use std::cmp::Ordering;
+use std::marker::PhantomData;
+
+use super::api::List;
+use super::handle::ListHandle;
+use super::sorted::SortedList;
+use crate::core::Result;
+
+/// Defines which end of the [`PriorityQueue`] has the top priority.
+pub trait Order {
+    /// `true` if the greatest element has the top priority.
+    const DESCENDING: bool;
+}
+
+/// The smallest element has the top priority.
+pub struct Min;
+
+/// The greatest element has the top priority.
+pub struct Max;
+
+impl Order for Min {
+    const DESCENDING: bool = false;
+}
+
+impl Order for Max {
+    const DESCENDING: bool = true;
+}
+
+/// A handle of the element pushed into the [`PriorityQueue`].
+///
+/// It stays valid until the element is popped or removed from the queue.
+/// The handles are unique among all queues, so the handle of another queue is rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct QueueHandle(ListHandle);
+
+// Queue element: the payload ordered by `O`.
+struct Entry<T, O> {
+    payload: T,
+    _order: PhantomData<O>,
+}
+
+impl<T, O> Entry<T, O> {
+    fn new(payload: T) -> Self {
+        Self {
+            payload,
+            _order: PhantomData,
+        }
+    }
+}
+
+impl<T: PartialEq, O> PartialEq for Entry<T, O> {
+    fn eq(&self, other: &Self) -> bool {
+        self.payload == other.payload
+    }
+}
+
+impl<T: PartialOrd, O: Order> PartialOrd for Entry<T, O> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if O::DESCENDING {
+            other.payload.partial_cmp(&self.payload)
+        } else {
+            self.payload.partial_cmp(&other.payload)
+        }
+    }
+}
+
+/// A priority queue built on [`SortedList`].
+///
+/// Elements are kept in priority order: the top priority element is at the front of the list,
+/// the element with the lowest priority is at the end of it. The list is in the indexed mode
+/// (see [`SortedList::with_index`]), so both ends are popped in O(log n) expected time
+/// without the search of the predecessor from the head.
+/// Elements with equal priority are popped in the insertion order.
+///
+/// Every `push_with_handle` returns a [`QueueHandle`] that allows to `update` or `remove`
+/// the element later.
+///
+/// **Efficiency** (k is the number of elements with the same priority):
+/// - `push`: O(log n) expected
+/// - `peek`, `peek_min`, `peek_max`: O(1)
+/// - `pop`, `pop_min`, `pop_max`: O(log n) expected
+/// - `get`: O(1)
+/// - `update`, `remove`: O(log n + k) expected
+///
+/// **Memory**: the span index costs about 13 bytes and half a heap allocation per element
+/// on average (see [`SortedList::with_index`]).
+///
+/// # Type Parameters
+/// * `T`: The type of elements stored in the queue. Must implement `PartialOrd`.
+/// * `O`: The order of the queue: [`Min`] (by default) or [`Max`].
+///
+/// # Examples
+/// ```
+/// use plain_ds::list::{MaxPriorityQueue, PriorityQueue};
+///
+/// let mut queue: PriorityQueue<_> = PriorityQueue::new();
+/// queue.push(3);
+/// let handle = queue.push_with_handle(5);
+/// queue.push(1);
+///
+/// queue.update(handle, 0).unwrap();
+/// assert_eq!(queue.pop(), Some(0));
+/// assert_eq!(queue.pop_max(), Some(3));
+///
+/// let mut queue = MaxPriorityQueue::new();
+/// queue.push(3);
+/// queue.push(5);
+/// assert_eq!(queue.pop(), Some(5));
+/// ```
+pub struct PriorityQueue<T, O: Order = Min> {
+    list: SortedList<Entry<T, O>>,
+}
+
+/// A priority queue where the smallest element has the top priority.
+pub type MinPriorityQueue<T> = PriorityQueue<T, Min>;
+
+/// A priority queue where the greatest element has the top priority.
+pub type MaxPriorityQueue<T> = PriorityQueue<T, Max>;
+
+impl<T: PartialOrd, O: Order> PriorityQueue<T, O> {
+    /// Creates empty priority queue.
+    pub fn new() -> Self {
+        Self {
+            list: SortedList::new().with_index(),
+        }
+    }
+
+    /// Returns queue size.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    /// Checks if the queue is empty.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Returns an iterator over the elements of the queue, from the top priority to the lowest.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.list.iter().map(|entry| &entry.payload)
+    }
+
+    /// Adds a new element to the queue.
+    ///
+    /// **Efficiency**: O(log n) expected
+    pub fn push(&mut self, payload: T) {
+        let _ = self.push_with_handle(payload);
+    }
+
+    /// Adds a new element to the queue and returns its handle.
+    ///
+    /// **Efficiency**: O(log n) expected
+    pub fn push_with_handle(&mut self, payload: T) -> QueueHandle {
+        match self.list.push_with_handle(Entry::new(payload)) {
+            Ok(handle) => QueueHandle(handle),
+            Err(_) => unreachable!("the list is unbounded"),
+        }
+    }
+
+    /// Returns the element with the top priority.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn peek(&self) -> Option<&T> {
+        self.list.head().map(|entry| &entry.payload)
+    }
+
+    /// Removes the element with the top priority from the queue and returns it.
+    ///
+    /// **Efficiency**: O(log n) expected
+    pub fn pop(&mut self) -> Option<T> {
+        self.list.pop_front().map(|entry| entry.payload)
+    }
+
+    /// Returns the smallest element of the queue.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn peek_min(&self) -> Option<&T> {
+        if O::DESCENDING {
+            self.list.last().map(|entry| &entry.payload)
+        } else {
+            self.peek()
+        }
+    }
+
+    /// Returns the greatest element of the queue.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn peek_max(&self) -> Option<&T> {
+        if O::DESCENDING {
+            self.peek()
+        } else {
+            self.list.last().map(|entry| &entry.payload)
+        }
+    }
+
+    /// Removes the smallest element from the queue and returns it.
+    ///
+    /// **Efficiency**: O(log n) expected
+    pub fn pop_min(&mut self) -> Option<T> {
+        if O::DESCENDING {
+            self.list.pop_back().map(|entry| entry.payload)
+        } else {
+            self.pop()
+        }
+    }
+
+    /// Removes the greatest element from the queue and returns it.
+    ///
+    /// **Efficiency**: O(log n) expected
+    pub fn pop_max(&mut self) -> Option<T> {
+        if O::DESCENDING {
+            self.pop()
+        } else {
+            self.list.pop_back().map(|entry| entry.payload)
+        }
+    }
+
+    /// Returns the element by its handle.
+    /// Error returns, if the element was already popped or removed.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn get(&self, handle: QueueHandle) -> Result<&T> {
+        self.list.get_by_handle(handle.0).map(|entry| &entry.payload)
+    }
+
+    /// Replaces the element by its handle with `payload`, and moves it according
+    /// to its new priority. The handle remains valid.
+    /// Error returns, if the element was already popped or removed.
+    ///
+    /// **Efficiency**: O(log n + k) expected, where k is the number of elements
+    /// with the same priority as the replaced one
+    pub fn update(&mut self, handle: QueueHandle, payload: T) -> Result<()> {
+        self.list.replace_by_handle(handle.0, Entry::new(payload))?;
+        Ok(())
+    }
+
+    /// Removes the element by its handle from the queue and returns it.
+    /// Error returns, if the element was already popped or removed.
+    ///
+    /// **Efficiency**: O(log n + k) expected, where k is the number of elements
+    /// with the same priority
+    pub fn remove(&mut self, handle: QueueHandle) -> Result<T> {
+        self.list.remove_by_handle(handle.0).map(|entry| entry.payload)
+    }
+
+    /// Removes all elements from the queue.
+    ///
+    /// **Efficiency**: O(n)
+    pub fn clear(&mut self) {
+        self.list.clear();
+    }
+}
+
+impl<T: PartialOrd, O: Order> Default for PriorityQueue<T, O> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup_queue<O: Order>(values: &[i32]) -> PriorityQueue<i32, O> {
+        let mut queue = PriorityQueue::new();
+        for value in values {
+            queue.push(*value);
+        }
+        queue
+    }
+
+    #[test]
+    fn test_creation() {
+        let queue: PriorityQueue<i32> = PriorityQueue::new();
+        assert_eq!(queue.len(), 0, "not zero length after creation");
+        assert!(queue.is_empty(), "not empty after creation");
+        assert_eq!(queue.peek(), None, "not empty peek after creation");
+    }
+
+    mod push {
+        use super::*;
+
+        #[test]
+        fn test_push_min_order() {
+            let queue = setup_queue::<Min>(&[5, 1, 4, 2, 3]);
+            assert_eq!(queue.len(), 5, "bad length after push()");
+            assert_eq!(
+                queue.iter().copied().collect::<Vec<_>>(),
+                [1, 2, 3, 4, 5],
+                "min queue must iterate from the smallest element"
+            );
+        }
+
+        #[test]
+        fn test_push_max_order() {
+            let queue = setup_queue::<Max>(&[5, 1, 4, 2, 3]);
+            assert_eq!(
+                queue.iter().copied().collect::<Vec<_>>(),
+                [5, 4, 3, 2, 1],
+                "max queue must iterate from the greatest element"
+            );
+        }
+
+        #[test]
+        fn test_push_duplicate_values() {
+            let queue = setup_queue::<Min>(&[2, 1, 2, 1]);
+            assert_eq!(queue.iter().copied().collect::<Vec<_>>(), [1, 1, 2, 2]);
+        }
+
+        #[test]
+        fn test_push_with_handle_returns_distinct_handles() {
+            let mut queue = MinPriorityQueue::new();
+            let first = queue.push_with_handle(1);
+            let second = queue.push_with_handle(1);
+            assert_ne!(first, second, "handles of equal elements must differ");
+        }
+    }
+
+    mod peek_pop {
+        use super::*;
+
+        #[test]
+        fn test_equal_priority_pops_in_insertion_order() {
+            #[derive(Debug)]
+            struct Job(u8, &'static str);
+
+            impl PartialEq for Job {
+                fn eq(&self, other: &Self) -> bool {
+                    self.0 == other.0
+                }
+            }
+
+            impl PartialOrd for Job {
+                fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                    self.0.partial_cmp(&other.0)
+                }
+            }
+
+            let mut queue = MaxPriorityQueue::new();
+            for job in [Job(1, "a"), Job(2, "b"), Job(1, "c"), Job(2, "d"), Job(2, "e")] {
+                queue.push(job);
+            }
+            let names: Vec<_> = std::iter::from_fn(|| queue.pop()).map(|job| job.1).collect();
+            assert_eq!(names, ["b", "d", "e", "a", "c"]);
+        }
+
+        #[test]
+        fn test_peek_pop_empty_queue() {
+            let mut queue: MinPriorityQueue<i32> = PriorityQueue::new();
+            assert_eq!(queue.peek(), None);
+            assert_eq!(queue.peek_min(), None);
+            assert_eq!(queue.peek_max(), None);
+            assert_eq!(queue.pop(), None);
+            assert_eq!(queue.pop_min(), None);
+            assert_eq!(queue.pop_max(), None);
+        }
+
+        #[test]
+        fn test_min_queue_ends() {
+            let mut queue = setup_queue::<Min>(&[3, 1, 5, 2, 4]);
+            assert_eq!(queue.peek(), Some(&1), "peek() must return the smallest element");
+            assert_eq!(queue.peek_min(), Some(&1));
+            assert_eq!(queue.peek_max(), Some(&5));
+
+            assert_eq!(queue.pop(), Some(1));
+            assert_eq!(queue.pop_max(), Some(5));
+            assert_eq!(queue.pop_min(), Some(2));
+            assert_eq!(queue.len(), 2, "size must decrease after pops");
+            assert_eq!(queue.peek_max(), Some(&4), "last must be updated after pop_max()");
+        }
+
+        #[test]
+        fn test_max_queue_ends() {
+            let mut queue = setup_queue::<Max>(&[3, 1, 5, 2, 4]);
+            assert_eq!(queue.peek(), Some(&5), "peek() must return the greatest element");
+            assert_eq!(queue.peek_min(), Some(&1));
+            assert_eq!(queue.peek_max(), Some(&5));
+
+            assert_eq!(queue.pop(), Some(5));
+            assert_eq!(queue.pop_min(), Some(1));
+            assert_eq!(queue.pop_max(), Some(4));
+            assert_eq!(queue.len(), 2, "size must decrease after pops");
+            assert_eq!(queue.peek_min(), Some(&2), "last must be updated after pop_min()");
+        }
+
+        #[test]
+        fn test_pop_both_ends() {
+            let values: Vec<i32> = (0..500).map(|i| (i * 37) % 500).collect();
+            let mut min_queue = setup_queue::<Min>(&values);
+            let mut max_queue = setup_queue::<Max>(&values);
+            assert!(min_queue.list.is_indexed(), "the other end must be popped without the search from the head");
+
+            for i in 0..250 {
+                assert_eq!(min_queue.pop_max(), Some(499 - i));
+                assert_eq!(min_queue.pop(), Some(i));
+                assert_eq!(max_queue.pop_min(), Some(i));
+                assert_eq!(max_queue.pop(), Some(499 - i));
+            }
+            assert!(min_queue.is_empty());
+            assert!(max_queue.is_empty());
+            assert_eq!(min_queue.list.check_invariants(), Ok(()));
+        }
+
+        #[test]
+        fn test_pop_drains_in_priority_order() {
+            let mut queue = setup_queue::<Min>(&[9, 7, 8, 1, 3]);
+            let mut drained = vec![];
+            while let Some(value) = queue.pop() {
+                drained.push(value);
+            }
+            assert_eq!(drained, [1, 3, 7, 8, 9]);
+            assert!(queue.is_empty(), "queue must be empty after draining");
+        }
+    }
+
+    mod handles {
+        use super::*;
+        use crate::core::DSError;
+
+        #[test]
+        fn test_get_by_handle() {
+            let mut queue = MinPriorityQueue::new();
+            queue.push(1);
+            let handle = queue.push_with_handle(2);
+            assert_eq!(queue.get(handle), Ok(&2));
+        }
+
+        #[test]
+        fn test_update_moves_element() {
+            let mut queue = MinPriorityQueue::new();
+            queue.push(10);
+            let handle = queue.push_with_handle(20);
+            queue.push(30);
+
+            queue.update(handle, 5).unwrap();
+            assert_eq!(queue.iter().copied().collect::<Vec<_>>(), [5, 10, 30]);
+
+            queue.update(handle, 40).unwrap();
+            assert_eq!(queue.iter().copied().collect::<Vec<_>>(), [10, 30, 40]);
+            assert_eq!(queue.get(handle), Ok(&40), "handle must remain valid after update()");
+            assert_eq!(queue.len(), 3, "update() must not change the size");
+        }
+
+        #[test]
+        fn test_update_max_queue() {
+            let mut queue = MaxPriorityQueue::new();
+            let handle = queue.push_with_handle(1);
+            queue.push(2);
+            queue.push(3);
+
+            queue.update(handle, 4).unwrap();
+            assert_eq!(queue.peek(), Some(&4), "updated element must become the top priority");
+        }
+
+        #[test]
+        fn test_remove_by_handle() {
+            let mut queue = MinPriorityQueue::new();
+            queue.push(1);
+            let handle = queue.push_with_handle(2);
+            queue.push(3);
+
+            assert_eq!(queue.remove(handle), Ok(2));
+            assert_eq!(queue.iter().copied().collect::<Vec<_>>(), [1, 3]);
+            assert_eq!(
+                queue.remove(handle),
+                Err(DSError::InvalidHandle),
+                "handle must be invalid after remove()"
+            );
+        }
+
+        #[test]
+        fn test_handle_of_another_queue() {
+            let mut first = MinPriorityQueue::new();
+            let mut second = MinPriorityQueue::new();
+            let handle = first.push_with_handle(1);
+            let other = second.push_with_handle(99);
+
+            assert_eq!(second.get(handle), Err(DSError::InvalidHandle));
+            assert_eq!(second.remove(handle), Err(DSError::InvalidHandle));
+            assert_eq!(second.update(handle, 0), Err(DSError::InvalidHandle));
+            assert_eq!(second.get(other), Ok(&99), "queue must not change on a foreign handle");
+            assert_eq!(first.remove(handle), Ok(1));
+        }
+
+        #[test]
+        fn test_handle_invalid_after_pop() {
+            let mut queue = MinPriorityQueue::new();
+            let handle = queue.push_with_handle(1);
+            queue.push(2);
+
+            assert_eq!(queue.pop(), Some(1));
+            assert_eq!(queue.get(handle), Err(DSError::InvalidHandle));
+            assert_eq!(queue.update(handle, 0), Err(DSError::InvalidHandle));
+            assert_eq!(queue.len(), 1, "failed update() must not change the queue");
+        }
+    }
+
+    mod memory_leaks {
+        use super::*;
+        use drop_tracker::DropTracker;
+
+        #[test]
+        fn test_memory_leaks() {
+            let mut tracker = DropTracker::new();
+
+            let mut queue = MinPriorityQueue::new();
+            let mut handles = vec![];
+            for i in 0..50 {
+                handles.push(queue.push_with_handle(tracker.track(i)));
+            }
+            assert_eq!(tracker.alive().count(), 50);
+
+            let _ = queue.pop();
+            let _ = queue.pop_max();
+            let _ = queue.remove(handles[10]);
+            queue.update(handles[20], tracker.track(100)).unwrap();
+            assert_eq!(tracker.alive().count(), 47, "removed and replaced elements must be dropped");
+
+            drop(queue);
+            assert_eq!(tracker.alive().count(), 0);
+            assert_eq!(tracker.dropped().count(), 51);
+        }
+    }
+}
@@ -0,0 +1,650 @@
+//! This module contains ring buffer implementation.
+
+use std::mem::MaybeUninit;
+use std::ops::Range;
+use std::{ptr, slice};
+
+use super::api::List;
+use super::bounded::Overflow;
+use crate::core::{DSError, Result};
+
+/// A fixed-capacity double-ended queue over one preallocated buffer.
+///
+/// The `RingBuffer` allocates its storage once on creation, so push and pop at both ends
+/// are O(1) without per-element allocation. When the buffer is full, the overflow policy
+/// is applied: [`Overflow::EvictFront`] overwrites the oldest element on `push`,
+/// [`Overflow::Reject`] rejects the new element with [`DSError::CapacityExceeded`].
+///
+/// # Type Parameters
+/// * `T`: The type of elements stored in the buffer.
+///
+/// # Examples
+/// ```
+/// use plain_ds::List;
+/// use plain_ds::list::{Overflow, RingBuffer};
+///
+/// // The last three log records
+/// let mut log = RingBuffer::new(3, Overflow::EvictFront);
+/// for record in ["start", "connect", "send", "close"] {
+///     log.push(record);
+/// }
+/// assert_eq!(log.iter().copied().collect::<Vec<_>>(), ["connect", "send", "close"]);
+///
+/// let mut strict = RingBuffer::new(1, Overflow::Reject);
+/// assert_eq!(strict.try_push(1), Ok(None));
+/// assert!(strict.try_push(2).is_err());
+/// ```
+pub struct RingBuffer<T> {
+    buffer: Box<[MaybeUninit<T>]>,
+    // Index of the slot with the first element
+    head: usize,
+    len: usize,
+    policy: Overflow,
+}
+
+impl<T> RingBuffer<T> {
+    /// Creates empty buffer for `capacity` elements.
+    /// The `policy` defines what happens when a new element is added to the full buffer.
+    ///
+    /// **Efficiency**: O(1), the storage is allocated once
+    pub fn new(capacity: usize, policy: Overflow) -> Self {
+        Self {
+            buffer: (0..capacity).map(|_| MaybeUninit::uninit()).collect(),
+            head: 0,
+            len: 0,
+            policy,
+        }
+    }
+
+    /// Returns the maximum number of elements.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Returns the overflow policy.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn policy(&self) -> Overflow {
+        self.policy
+    }
+
+    /// Checks if the buffer contains the maximum number of elements.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn is_full(&self) -> bool {
+        self.len == self.capacity()
+    }
+
+    /// Adds a new element to the back of the buffer according to the overflow policy.
+    /// Returns the evicted element, if any: the oldest one for [`Overflow::EvictFront`],
+    /// the new one for [`Overflow::EvictBack`].
+    /// Error returns, if the buffer is full and the policy is [`Overflow::Reject`].
+    ///
+    /// **Efficiency**: O(1)
+    pub fn try_push(&mut self, payload: T) -> Result<Option<T>> {
+        if !self.is_full() {
+            self.write_back(payload);
+            return Ok(None);
+        }
+        match self.policy {
+            Overflow::Reject => Err(DSError::CapacityExceeded {
+                capacity: self.capacity(),
+            }),
+            Overflow::EvictFront if self.len > 0 => {
+                let evicted = List::pop_front(self);
+                self.write_back(payload);
+                Ok(evicted)
+            }
+            _ => Ok(Some(payload)),
+        }
+    }
+
+    /// Adds a new element to the front of the buffer according to the overflow policy.
+    /// Returns the evicted element, if any: the new one for [`Overflow::EvictFront`],
+    /// the back one for [`Overflow::EvictBack`].
+    /// Error returns, if the buffer is full and the policy is [`Overflow::Reject`].
+    ///
+    /// **Efficiency**: O(1)
+    pub fn try_push_front(&mut self, payload: T) -> Result<Option<T>> {
+        if !self.is_full() {
+            self.write_front(payload);
+            return Ok(None);
+        }
+        match self.policy {
+            Overflow::Reject => Err(DSError::CapacityExceeded {
+                capacity: self.capacity(),
+            }),
+            Overflow::EvictBack if self.len > 0 => {
+                let evicted = List::pop_back(self);
+                self.write_front(payload);
+                Ok(evicted)
+            }
+            _ => Ok(Some(payload)),
+        }
+    }
+
+    /// Adds a new element to the front of the buffer.
+    /// If the buffer is full, the overflow policy is applied
+    /// (the evicted or rejected element is dropped).
+    ///
+    /// **Efficiency**: O(1)
+    pub fn push_front(&mut self, payload: T) {
+        let _ = self.try_push_front(payload);
+    }
+
+    /// Returns the elements in logical order as two slices: the second one is not empty,
+    /// if the elements wrap around the end of the storage.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        unsafe { self.slices() }
+    }
+
+    /// Returns the mutable elements in logical order as two slices
+    /// (see [`as_slices`](RingBuffer::as_slices)).
+    ///
+    /// **Efficiency**: O(1)
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        unsafe { self.slices_mut() }
+    }
+
+    /// Moves the elements so they do not wrap around the end of the storage,
+    /// and returns them as one slice in logical order.
+    ///
+    /// **Efficiency**: O(1) if the elements are contiguous already, otherwise O(capacity)
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        if self.head + self.len > self.capacity() {
+            self.buffer.rotate_left(self.head);
+            self.head = 0;
+        }
+        unsafe { self.slices_mut() }.0
+    }
+
+    // Returns the storage index of the element with the logical `index` (up to the capacity).
+    fn slot(&self, index: usize) -> usize {
+        let slot = self.head + index;
+        if slot >= self.capacity() {
+            slot - self.capacity()
+        } else {
+            slot
+        }
+    }
+
+    // Returns the storage ranges of the elements in logical order.
+    fn ranges(&self) -> (Range<usize>, Range<usize>) {
+        let end = self.head + self.len;
+        if end <= self.capacity() {
+            (self.head..end, 0..0)
+        } else {
+            (self.head..self.capacity(), 0..end - self.capacity())
+        }
+    }
+
+    // Returns the elements as slices with the lifetime required by the caller.
+    unsafe fn slices<'a>(&self) -> (&'a [T], &'a [T]) {
+        let (front, back) = self.ranges();
+        let data = self.buffer.as_ptr().cast::<T>();
+        unsafe {
+            (
+                slice::from_raw_parts(data.add(front.start), front.len()),
+                slice::from_raw_parts(data.add(back.start), back.len()),
+            )
+        }
+    }
+
+    // Returns the mutable elements as slices with the lifetime required by the caller.
+    unsafe fn slices_mut<'a>(&mut self) -> (&'a mut [T], &'a mut [T]) {
+        let (front, back) = self.ranges();
+        let data = self.buffer.as_mut_ptr().cast::<T>();
+        unsafe {
+            (
+                slice::from_raw_parts_mut(data.add(front.start), front.len()),
+                slice::from_raw_parts_mut(data.add(back.start), back.len()),
+            )
+        }
+    }
+
+    // Writes the element after the last one, the buffer must not be full.
+    fn write_back(&mut self, payload: T) {
+        let slot = self.slot(self.len);
+        self.buffer[slot].write(payload);
+        self.len += 1;
+    }
+
+    // Writes the element before the first one, the buffer must not be full.
+    fn write_front(&mut self, payload: T) {
+        self.head = if self.head == 0 { self.capacity() - 1 } else { self.head - 1 };
+        self.buffer[self.head].write(payload);
+        self.len += 1;
+    }
+
+    // Drops all elements, the back slice is dropped even if a destructor panics.
+    fn drop_all(&mut self) {
+        struct DropSlice<T>(*mut [T]);
+
+        impl<T> Drop for DropSlice<T> {
+            fn drop(&mut self) {
+                unsafe { ptr::drop_in_place(self.0) };
+            }
+        }
+
+        let (front, back) = unsafe { self.slices_mut() };
+        self.head = 0;
+        self.len = 0;
+        let _back = DropSlice(back as *mut [T]);
+        drop(DropSlice(front as *mut [T]));
+    }
+}
+
+impl<T> Drop for RingBuffer<T> {
+    fn drop(&mut self) {
+        self.drop_all();
+    }
+}
+
+impl<T> Extend<T> for RingBuffer<T> {
+    /// Adds the elements to the back of the buffer.
+    /// If the buffer is full, the overflow policy is applied.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for payload in iter {
+            List::push(self, payload);
+        }
+    }
+}
+
+impl<T> List<T> for RingBuffer<T> {
+    /// Returns buffer size.
+    ///
+    /// **Efficiency**: O(1)
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the first element of the buffer.
+    ///
+    /// **Efficiency**: O(1)
+    fn head(&self) -> Option<&T> {
+        self.get(0).ok()
+    }
+
+    /// Returns the last element of the buffer.
+    ///
+    /// **Efficiency**: O(1)
+    fn last(&self) -> Option<&T> {
+        self.get(self.len.checked_sub(1)?).ok()
+    }
+
+    /// Returns an element by index, or error if index out of bounds.
+    ///
+    /// **Efficiency**: O(1)
+    fn get(&self, index: usize) -> Result<&T> {
+        if index >= self.len {
+            return Err(DSError::IndexOutOfBounds { index, len: self.len });
+        }
+        let slot = self.slot(index);
+        Ok(unsafe { &*self.buffer.as_ptr().add(slot).cast::<T>() })
+    }
+
+    /// Returns a mutable element by index, or error if index out of bounds.
+    ///
+    /// **Efficiency**: O(1)
+    fn get_mut(&mut self, index: usize) -> Result<&mut T> {
+        if index >= self.len {
+            return Err(DSError::IndexOutOfBounds { index, len: self.len });
+        }
+        let slot = self.slot(index);
+        Ok(unsafe { &mut *self.buffer.as_mut_ptr().add(slot).cast::<T>() })
+    }
+
+    /// Returns an iterator over the immutable elements in logical order.
+    fn iter<'a>(&'a self) -> impl Iterator<Item = &'a T>
+    where
+        T: 'a,
+    {
+        let (front, back) = unsafe { self.slices() };
+        front.iter().chain(back)
+    }
+
+    /// Returns an iterator over the mutable elements in logical order.
+    fn iter_mut<'a>(&'a mut self) -> impl Iterator<Item = &'a mut T>
+    where
+        T: 'a,
+    {
+        let (front, back) = unsafe { self.slices_mut() };
+        front.iter_mut().chain(back)
+    }
+
+    /// Returns an iterator that consumes the buffer.
+    fn into_iter(mut self) -> impl Iterator<Item = T> {
+        std::iter::from_fn(move || List::pop_front(&mut self))
+    }
+
+    /// Adds a new element to the back of the buffer.
+    /// If the buffer is full, the overflow policy is applied
+    /// (the evicted or rejected element is dropped).
+    ///
+    /// **Efficiency**: O(1)
+    fn push(&mut self, payload: T) {
+        let _ = self.try_push(payload);
+    }
+
+    /// Removes the last element and returns it.
+    ///
+    /// **Efficiency**: O(1)
+    fn pop_back(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        let slot = self.slot(self.len - 1);
+        self.len -= 1;
+        Some(unsafe { self.buffer[slot].assume_init_read() })
+    }
+
+    /// Removes the first element and returns it.
+    ///
+    /// **Efficiency**: O(1)
+    fn pop_front(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        let slot = self.head;
+        self.head = self.slot(1);
+        self.len -= 1;
+        Some(unsafe { self.buffer[slot].assume_init_read() })
+    }
+
+    /// Removes an element by index, or returns error if index out of bounds.
+    /// The elements on the shorter side of the removed one are shifted.
+    ///
+    /// **Efficiency**: O(min(i, n - i))
+    fn remove(&mut self, index: usize) -> Result<T> {
+        if index >= self.len {
+            return Err(DSError::IndexOutOfBounds { index, len: self.len });
+        }
+        let payload = unsafe { self.buffer[self.slot(index)].assume_init_read() };
+        // Move the empty slot to the nearest end
+        if index < self.len / 2 {
+            for i in (0..index).rev() {
+                let (from, to) = (self.slot(i), self.slot(i + 1));
+                self.buffer.swap(from, to);
+            }
+            self.head = self.slot(1);
+        } else {
+            for i in index + 1..self.len {
+                let (from, to) = (self.slot(i), self.slot(i - 1));
+                self.buffer.swap(from, to);
+            }
+        }
+        self.len -= 1;
+        Ok(payload)
+    }
+
+    /// Removes all elements from the buffer.
+    ///
+    /// **Efficiency**: O(n)
+    fn clear(&mut self) {
+        self.drop_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::list_conformance_tests!(conformance, || RingBuffer::new(16, Overflow::Reject));
+    crate::list_conformance_tests!(overwrite_conformance, || RingBuffer::new(16, Overflow::EvictFront));
+
+    // Creates the buffer whose elements wrap around the end of the storage: [0, 1, 2, 3, 4]
+    fn setup_wrapped(capacity: usize) -> RingBuffer<usize> {
+        let mut buffer = RingBuffer::new(capacity, Overflow::Reject);
+        for i in 2..5 {
+            buffer.push(i);
+        }
+        buffer.push_front(1);
+        buffer.push_front(0);
+        buffer
+    }
+
+    fn values(buffer: &RingBuffer<usize>) -> Vec<usize> {
+        buffer.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_creation() {
+        let buffer: RingBuffer<u8> = RingBuffer::new(4, Overflow::Reject);
+        assert_eq!(buffer.capacity(), 4);
+        assert_eq!(buffer.policy(), Overflow::Reject);
+        assert_eq!(buffer.len(), 0, "not zero length after creation");
+        assert!(!buffer.is_full());
+        assert_eq!(buffer.as_slices(), (&[][..], &[][..]));
+    }
+
+    mod overflow {
+        use super::*;
+
+        #[test]
+        fn test_overwrite() {
+            let mut buffer = RingBuffer::new(3, Overflow::EvictFront);
+            for i in 0..3 {
+                assert_eq!(buffer.try_push(i), Ok(None));
+            }
+            assert!(buffer.is_full());
+            assert_eq!(buffer.try_push(3), Ok(Some(0)), "the oldest element must be overwritten");
+            buffer.push(4);
+            assert_eq!(values(&buffer), [2, 3, 4]);
+            assert_eq!(buffer.len(), 3);
+
+            assert_eq!(buffer.try_push_front(9), Ok(Some(9)), "new front element must be evicted");
+            assert_eq!(values(&buffer), [2, 3, 4]);
+        }
+
+        #[test]
+        fn test_evict_back() {
+            let mut buffer = RingBuffer::new(3, Overflow::EvictBack);
+            buffer.extend([1, 2, 3]);
+            assert_eq!(buffer.try_push(4), Ok(Some(4)), "new back element must be evicted");
+            assert_eq!(buffer.try_push_front(0), Ok(Some(3)), "the back element must be evicted");
+            assert_eq!(values(&buffer), [0, 1, 2]);
+        }
+
+        #[test]
+        fn test_reject() {
+            let mut buffer = setup_wrapped(5);
+            assert_eq!(buffer.try_push(5), Err(DSError::CapacityExceeded { capacity: 5 }));
+            assert_eq!(buffer.try_push_front(5), Err(DSError::CapacityExceeded { capacity: 5 }));
+            buffer.push(5);
+            assert_eq!(values(&buffer), [0, 1, 2, 3, 4], "rejected element must not be added");
+
+            assert_eq!(buffer.pop_front(), Some(0));
+            assert_eq!(buffer.try_push(5), Ok(None), "free slot must be reused");
+            assert_eq!(values(&buffer), [1, 2, 3, 4, 5]);
+        }
+
+        #[test]
+        fn test_zero_capacity() {
+            for policy in [Overflow::EvictFront, Overflow::EvictBack] {
+                let mut buffer = RingBuffer::new(0, policy);
+                assert!(buffer.is_full());
+                assert_eq!(buffer.try_push(1), Ok(Some(1)));
+                assert_eq!(buffer.try_push_front(1), Ok(Some(1)));
+                assert_eq!(buffer.len(), 0);
+            }
+            let mut buffer = RingBuffer::new(0, Overflow::Reject);
+            assert_eq!(buffer.try_push(1), Err(DSError::CapacityExceeded { capacity: 0 }));
+            assert_eq!(buffer.pop_front(), None);
+            assert_eq!(buffer.make_contiguous(), &[]);
+        }
+    }
+
+    mod slices {
+        use super::*;
+
+        #[test]
+        fn test_as_slices() {
+            let buffer = setup_wrapped(5);
+            assert_eq!(buffer.as_slices(), (&[0, 1][..], &[2, 3, 4][..]), "elements must wrap around");
+            assert_eq!(values(&buffer), [0, 1, 2, 3, 4], "iter() must go in logical order");
+
+            let buffer = setup_wrapped(8);
+            let (front, back) = buffer.as_slices();
+            assert_eq!([front, back].concat(), [0, 1, 2, 3, 4]);
+        }
+
+        #[test]
+        fn test_as_mut_slices() {
+            let mut buffer = setup_wrapped(5);
+            let (front, back) = buffer.as_mut_slices();
+            front[0] = 10;
+            back[2] = 14;
+            assert_eq!(values(&buffer), [10, 1, 2, 3, 14]);
+        }
+
+        #[test]
+        fn test_make_contiguous() {
+            let mut buffer = setup_wrapped(6);
+            assert_eq!(buffer.make_contiguous(), &mut [0, 1, 2, 3, 4]);
+            assert_eq!(buffer.as_slices(), (&[0, 1, 2, 3, 4][..], &[][..]));
+
+            buffer.push(5);
+            assert_eq!(buffer.pop_front(), Some(0));
+            assert_eq!(buffer.make_contiguous(), &mut [1, 2, 3, 4, 5], "contiguous elements stay in place");
+
+            buffer.make_contiguous().sort_by(|a, b| b.cmp(a));
+            assert_eq!(values(&buffer), [5, 4, 3, 2, 1]);
+            assert_eq!(buffer.get(4), Ok(&1));
+        }
+    }
+
+    mod access {
+        use super::*;
+
+        #[test]
+        fn test_get() {
+            let mut buffer = setup_wrapped(5);
+            for i in 0..5 {
+                assert_eq!(buffer.get(i), Ok(&i));
+            }
+            assert_eq!(buffer.get(5), Err(DSError::IndexOutOfBounds { index: 5, len: 5 }));
+            *buffer.get_mut(3).unwrap() = 13;
+            assert_eq!(buffer.head(), Some(&0));
+            assert_eq!(buffer.last(), Some(&4));
+            assert_eq!(values(&buffer), [0, 1, 2, 13, 4]);
+        }
+
+        #[test]
+        fn test_pop_across_the_end() {
+            let mut buffer = setup_wrapped(5);
+            assert_eq!(buffer.pop_back(), Some(4));
+            assert_eq!(buffer.pop_back(), Some(3));
+            assert_eq!(buffer.pop_back(), Some(2));
+            assert_eq!(buffer.pop_back(), Some(1), "pop_back() must cross the end of the storage");
+            assert_eq!(buffer.pop_back(), Some(0));
+            assert_eq!(buffer.pop_back(), None);
+
+            let mut buffer = setup_wrapped(5);
+            let popped: Vec<_> = std::iter::from_fn(|| buffer.pop_front()).collect();
+            assert_eq!(popped, [0, 1, 2, 3, 4]);
+        }
+
+        #[test]
+        fn test_remove() {
+            for index in 0..5 {
+                let mut buffer = setup_wrapped(5);
+                assert_eq!(buffer.remove(index), Ok(index));
+                let expected: Vec<_> = (0..5).filter(|i| *i != index).collect();
+                assert_eq!(values(&buffer), expected, "remove({index}) must keep the order");
+                buffer.push(5);
+                assert_eq!(buffer.last(), Some(&5), "free slot must be reused after remove({index})");
+            }
+            let mut buffer = setup_wrapped(5);
+            assert_eq!(buffer.remove(5), Err(DSError::IndexOutOfBounds { index: 5, len: 5 }));
+        }
+    }
+
+    mod memory_leaks {
+        use super::*;
+        use drop_tracker::DropTracker;
+
+        #[test]
+        fn test_memory_leaks() {
+            let mut tracker = DropTracker::new();
+
+            let mut buffer = RingBuffer::new(8, Overflow::EvictFront);
+            for i in 0..20 {
+                buffer.push(tracker.track(i));
+            }
+            assert_eq!(tracker.alive().count(), 8, "overwritten elements must be dropped");
+            drop(buffer.remove(3));
+            drop(buffer.pop_back());
+            assert_eq!(tracker.alive().count(), 6);
+
+            drop(buffer);
+            assert_eq!(tracker.alive().count(), 0);
+            assert_eq!(tracker.dropped().count(), 20);
+        }
+
+        #[test]
+        fn test_into_iter_and_clear() {
+            let mut tracker = DropTracker::new();
+
+            let mut buffer = RingBuffer::new(4, Overflow::Reject);
+            for i in 0..4 {
+                buffer.push(tracker.track(i));
+            }
+            let mut iter = List::into_iter(buffer);
+            drop(iter.next());
+            drop(iter);
+            assert_eq!(tracker.alive().count(), 0, "partially consumed iterator must drop the rest");
+
+            let mut buffer = RingBuffer::new(4, Overflow::Reject);
+            for i in 4..8 {
+                buffer.push_front(tracker.track(i));
+            }
+            buffer.clear();
+            assert_eq!(tracker.alive().count(), 0);
+            assert!(buffer.is_empty());
+            buffer.push(tracker.track(8));
+            assert_eq!(buffer.len(), 1, "buffer must be usable after clear()");
+        }
+
+        #[test]
+        fn test_drop_with_panicking_destructor() {
+            use drop_tracker::DropItem;
+            use std::panic::{self, AssertUnwindSafe};
+
+            struct Bomb {
+                _item: DropItem<u32>,
+                explode: bool,
+            }
+
+            impl Drop for Bomb {
+                fn drop(&mut self) {
+                    if self.explode {
+                        panic!("drop failed");
+                    }
+                }
+            }
+
+            let mut tracker = DropTracker::new();
+            let mut buffer = RingBuffer::new(6, Overflow::Reject);
+            for i in 0..6 {
+                let item = Bomb {
+                    _item: tracker.track(i),
+                    explode: i == 1,
+                };
+                // The elements wrap around the end of the storage
+                if i < 3 {
+                    buffer.push_front(item);
+                } else {
+                    buffer.push(item);
+                }
+            }
+
+            let result = panic::catch_unwind(AssertUnwindSafe(|| drop(buffer)));
+            assert!(result.is_err(), "panic of the destructor must be propagated");
+            assert_eq!(tracker.alive().count(), 0, "the rest of the elements must be dropped");
+        }
+    }
+}
@@ -0,0 +1,521 @@
+//! This module contains self-organizing list implementation.
+
+use std::{mem, ptr};
+
+use super::common::ListCommon;
+use super::node_one_link::Node;
+use crate::core::{InvariantViolation, debug_check_invariants};
+
+/// Defines how [`SelfOrganizingList`] reorganizes itself when an element is found.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Heuristic {
+    /// The found element is moved to the front of the list.
+    /// Adapts fast to the changes of the access pattern.
+    #[default]
+    MoveToFront,
+    /// The found element is swapped with the previous one.
+    /// Adapts slowly, but is not disturbed by the rare accesses.
+    Transpose,
+    /// The elements are ordered by their access counts (from the most accessed);
+    /// the found element goes before the elements which are accessed less often.
+    FrequencyCount,
+}
+
+/// Access statistics of [`SelfOrganizingList`] for tuning of the heuristic.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AccessStats {
+    /// The number of lookups with `find` and `find_if`.
+    pub lookups: u64,
+    /// The number of successful lookups.
+    pub hits: u64,
+    /// The number of elements examined by all lookups.
+    pub probes: u64,
+}
+
+impl AccessStats {
+    /// Returns the number of failed lookups.
+    pub fn misses(&self) -> u64 {
+        self.lookups - self.hits
+    }
+
+    /// Returns the average number of elements examined per lookup (0 without lookups).
+    pub fn mean_probes(&self) -> f64 {
+        if self.lookups == 0 {
+            0.0
+        } else {
+            self.probes as f64 / self.lookups as f64
+        }
+    }
+}
+
+// The element with the number of times it was found
+struct Entry<T> {
+    payload: T,
+    hits: u64,
+}
+
+type EntryNode<T> = Node<Entry<T>>;
+
+/// A singly-linked list which moves the found elements closer to the front, so the elements
+/// which are looked up often are found in a few steps. It suits the small lookup tables
+/// with skewed access patterns.
+///
+/// The reorganization is done by [`find`](SelfOrganizingList::find) and
+/// [`find_if`](SelfOrganizingList::find_if) according to the [`Heuristic`]. The lookups
+/// are counted for every element and in total (see [`stats`](SelfOrganizingList::stats)).
+///
+/// # Type Parameters
+/// * `T`: The type of elements stored in the list.
+///
+/// # Examples
+/// ```
+/// use plain_ds::list::{Heuristic, SelfOrganizingList};
+///
+/// let mut list: SelfOrganizingList<_> = ["ftp", "http", "ssh"].into_iter().collect();
+/// assert_eq!(list.find(&"ssh"), Some(&mut "ssh"));
+/// assert_eq!(list.iter().copied().collect::<Vec<_>>(), ["ssh", "ftp", "http"]);
+///
+/// let mut list = SelfOrganizingList::new().with_heuristic(Heuristic::Transpose);
+/// list.extend([1, 2, 3]);
+/// list.find(&3);
+/// assert_eq!(list.iter().copied().collect::<Vec<_>>(), [1, 3, 2]);
+/// assert_eq!(list.stats().probes, 3);
+/// ```
+pub struct SelfOrganizingList<T> {
+    list: ListCommon<Entry<T>>,
+    heuristic: Heuristic,
+    stats: AccessStats,
+}
+
+impl<T> SelfOrganizingList<T> {
+    /// Creates empty list with [`Heuristic::MoveToFront`].
+    pub fn new() -> Self {
+        Self {
+            list: ListCommon::new(),
+            heuristic: Heuristic::default(),
+            stats: AccessStats::default(),
+        }
+    }
+
+    /// Sets the heuristic applied on the following lookups.
+    ///
+    /// [`Heuristic::FrequencyCount`] expects the elements to be ordered by their access counts,
+    /// so it should be set before the first lookup.
+    pub fn with_heuristic(mut self, heuristic: Heuristic) -> Self {
+        self.heuristic = heuristic;
+        self
+    }
+
+    /// Returns the heuristic of the list.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn heuristic(&self) -> Heuristic {
+        self.heuristic
+    }
+
+    /// Returns the number of elements in the list.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    /// Checks if the list is empty.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Adds a new element to the end of the list (as the least accessed one).
+    ///
+    /// **Efficiency**: O(1)
+    pub fn push(&mut self, payload: T) {
+        self.list.push_back(Entry { payload, hits: 0 });
+        debug_check_invariants!(self);
+    }
+
+    /// Finds the first element equal to `value` and moves it closer to the front.
+    ///
+    /// **Efficiency**: O(i), where i is the position of the element
+    pub fn find(&mut self, value: &T) -> Option<&mut T>
+    where
+        T: PartialEq,
+    {
+        self.find_if(|payload| payload == value)
+    }
+
+    /// Finds the first element which satisfies the predicate and moves it closer to the front.
+    ///
+    /// **Efficiency**: O(i), where i is the position of the element
+    pub fn find_if(&mut self, predicate: impl Fn(&T) -> bool) -> Option<&mut T> {
+        let node = self.access(predicate)?;
+        Some(unsafe { &mut (*node).payload.payload })
+    }
+
+    /// Checks if the list contains an element equal to `value`.
+    /// The list is not reorganized and the lookup is not counted.
+    ///
+    /// **Efficiency**: O(n)
+    pub fn contains(&self, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter().any(|payload| payload == value)
+    }
+
+    /// Removes the first element equal to `value` and returns it.
+    ///
+    /// **Efficiency**: O(n)
+    pub fn remove(&mut self, value: &T) -> Option<T>
+    where
+        T: PartialEq,
+    {
+        let index = self.iter().position(|payload| payload == value)?;
+        let entry = self.list.remove(index).ok()?;
+        debug_check_invariants!(self);
+        Some(entry.payload)
+    }
+
+    /// Removes all elements from the list. The access statistics are kept.
+    ///
+    /// **Efficiency**: O(n)
+    pub fn clear(&mut self) {
+        self.list = ListCommon::new();
+    }
+
+    /// Returns an iterator over the elements from the front (the hot ones) to the end.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.list.iter().map(|entry| &entry.payload)
+    }
+
+    /// Returns an iterator over the elements with the number of times each of them was found.
+    pub fn iter_with_hits(&self) -> impl Iterator<Item = (&T, u64)> {
+        self.list.iter().map(|entry| (&entry.payload, entry.hits))
+    }
+
+    /// Returns the statistics of all lookups since creation or [`reset_stats`](Self::reset_stats).
+    ///
+    /// **Efficiency**: O(1)
+    pub fn stats(&self) -> AccessStats {
+        self.stats
+    }
+
+    /// Resets the total statistics. The access counts of the elements are kept,
+    /// because [`Heuristic::FrequencyCount`] orders the elements by them.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn reset_stats(&mut self) {
+        self.stats = AccessStats::default();
+    }
+
+    /// Checks the internal structure of the list: `size` is equal to the number of nodes,
+    /// `last` is the tail node and the node chain has no cycles.
+    /// With the `check-invariants` feature, it runs after every mutation in debug builds.
+    ///
+    /// **Efficiency**: O(n)
+    pub fn check_invariants(&self) -> Result<(), InvariantViolation> {
+        self.list.check_invariants()
+    }
+
+    // Finds the first node which satisfies the predicate, counts the lookup and reorganizes
+    // the list according to the heuristic. Returns the node with the found element.
+    fn access(&mut self, predicate: impl Fn(&T) -> bool) -> Option<*mut EntryNode<T>> {
+        self.stats.lookups += 1;
+        let mut prev: *mut EntryNode<T> = ptr::null_mut();
+        let mut current = self.list.head;
+        while !current.is_null() {
+            self.stats.probes += 1;
+            if predicate(unsafe { &(*current).payload.payload }) {
+                break;
+            }
+            prev = current;
+            current = unsafe { (*current).next };
+        }
+        if current.is_null() {
+            return None;
+        }
+
+        self.stats.hits += 1;
+        let hits = unsafe {
+            (*current).payload.hits += 1;
+            (*current).payload.hits
+        };
+        if prev.is_null() {
+            return Some(current);
+        }
+
+        let node = match self.heuristic {
+            Heuristic::MoveToFront => {
+                self.relink(prev, current, ptr::null_mut());
+                current
+            }
+            Heuristic::Transpose => {
+                // The entries are swapped instead of the nodes
+                unsafe { mem::swap(&mut (*prev).payload, &mut (*current).payload) };
+                prev
+            }
+            Heuristic::FrequencyCount => {
+                // The node goes after the last one which is accessed at least as often
+                let mut target: *mut EntryNode<T> = ptr::null_mut();
+                let mut node = self.list.head;
+                while node != current && unsafe { (*node).payload.hits } >= hits {
+                    target = node;
+                    node = unsafe { (*node).next };
+                }
+                if target != prev {
+                    self.relink(prev, current, target);
+                }
+                current
+            }
+        };
+        self.list.reset_finger();
+        debug_check_invariants!(self);
+        Some(node)
+    }
+
+    // Moves the `node` which follows `prev` to the position after `target`
+    // (null `target` means the front). `target` must precede `prev`.
+    fn relink(&mut self, prev: *mut EntryNode<T>, node: *mut EntryNode<T>, target: *mut EntryNode<T>) {
+        unsafe {
+            (*prev).next = (*node).next;
+            if self.list.last == node {
+                self.list.last = prev;
+            }
+            if target.is_null() {
+                (*node).next = self.list.head;
+                self.list.head = node;
+            } else {
+                (*node).next = (*target).next;
+                (*target).next = node;
+            }
+        }
+    }
+}
+
+impl<T> Default for SelfOrganizingList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FromIterator<T> for SelfOrganizingList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> Extend<T> for SelfOrganizingList<T> {
+    /// Adds the elements to the end of the list.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for payload in iter {
+            self.push(payload);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup(heuristic: Heuristic, n: u32) -> SelfOrganizingList<u32> {
+        let mut list = SelfOrganizingList::new().with_heuristic(heuristic);
+        list.extend(0..n);
+        list
+    }
+
+    fn values(list: &SelfOrganizingList<u32>) -> Vec<u32> {
+        list.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_creation() {
+        let list: SelfOrganizingList<u8> = SelfOrganizingList::default();
+        assert_eq!(list.len(), 0, "not zero length after creation");
+        assert!(list.is_empty());
+        assert_eq!(list.heuristic(), Heuristic::MoveToFront, "move-to-front must be the default");
+        assert_eq!(list.stats(), AccessStats::default());
+    }
+
+    #[test]
+    fn test_not_found() {
+        for heuristic in [Heuristic::MoveToFront, Heuristic::Transpose, Heuristic::FrequencyCount] {
+            let mut list = setup(heuristic, 4);
+            assert_eq!(list.find(&7), None);
+            assert_eq!(values(&list), [0, 1, 2, 3], "failed lookup must not change the order");
+            assert_eq!(list.stats().misses(), 1);
+            assert_eq!(list.stats().probes, 4, "failed lookup examines all elements");
+        }
+    }
+
+    mod move_to_front {
+        use super::*;
+
+        #[test]
+        fn test_find() {
+            let mut list = setup(Heuristic::MoveToFront, 5);
+            assert_eq!(list.find(&3), Some(&mut 3));
+            assert_eq!(values(&list), [3, 0, 1, 2, 4]);
+            assert_eq!(list.find(&4), Some(&mut 4), "find the last element");
+            assert_eq!(values(&list), [4, 3, 0, 1, 2]);
+            assert_eq!(list.find(&4), Some(&mut 4), "find the first element");
+            assert_eq!(values(&list), [4, 3, 0, 1, 2]);
+            assert_eq!(list.check_invariants(), Ok(()));
+
+            list.push(5);
+            assert_eq!(values(&list), [4, 3, 0, 1, 2, 5], "last must be updated after moving");
+        }
+
+        #[test]
+        fn test_find_if() {
+            let mut list = setup(Heuristic::MoveToFront, 5);
+            *list.find_if(|value| value % 2 == 1).unwrap() = 11;
+            assert_eq!(values(&list), [11, 0, 2, 3, 4]);
+            assert!(list.contains(&11));
+            assert_eq!(list.stats().lookups, 1, "contains() must not be counted");
+        }
+    }
+
+    mod transpose {
+        use super::*;
+
+        #[test]
+        fn test_find() {
+            let mut list = setup(Heuristic::Transpose, 4);
+            assert_eq!(list.find(&3), Some(&mut 3));
+            assert_eq!(values(&list), [0, 1, 3, 2]);
+            list.find(&3);
+            list.find(&3);
+            assert_eq!(values(&list), [3, 0, 1, 2], "element must move one step per lookup");
+            list.find(&3);
+            assert_eq!(values(&list), [3, 0, 1, 2]);
+            assert_eq!(list.check_invariants(), Ok(()));
+        }
+
+        #[test]
+        fn test_hits_follow_elements() {
+            let mut list = setup(Heuristic::Transpose, 3);
+            list.find(&2);
+            assert_eq!(values(&list), [0, 2, 1]);
+            list.find(&1);
+            let hits: Vec<_> = list.iter_with_hits().map(|(value, hits)| (*value, hits)).collect();
+            assert_eq!(hits, [(0, 0), (1, 1), (2, 1)], "access counts must be swapped with the elements");
+        }
+    }
+
+    mod frequency_count {
+        use super::*;
+
+        #[test]
+        fn test_find() {
+            let mut list = setup(Heuristic::FrequencyCount, 4);
+            list.find(&3);
+            assert_eq!(values(&list), [3, 0, 1, 2]);
+            list.find(&2);
+            assert_eq!(values(&list), [3, 2, 0, 1], "equally accessed elements keep their order");
+            list.find(&2);
+            assert_eq!(values(&list), [2, 3, 0, 1]);
+            list.find(&1);
+            list.find(&1);
+            list.find(&1);
+            assert_eq!(values(&list), [1, 2, 3, 0]);
+            assert_eq!(list.check_invariants(), Ok(()));
+
+            let hits: Vec<_> = list.iter_with_hits().map(|(_, hits)| hits).collect();
+            assert_eq!(hits, [3, 2, 1, 0], "elements must be ordered by access counts");
+        }
+
+        #[test]
+        fn test_last_update() {
+            let mut list = setup(Heuristic::FrequencyCount, 3);
+            list.find(&2);
+            list.push(3);
+            assert_eq!(values(&list), [2, 0, 1, 3]);
+            assert_eq!(list.check_invariants(), Ok(()));
+        }
+    }
+
+    mod stats {
+        use super::*;
+
+        #[test]
+        fn test_skewed_access() {
+            for heuristic in [Heuristic::MoveToFront, Heuristic::Transpose, Heuristic::FrequencyCount] {
+                let mut list = setup(heuristic, 100);
+                // Transpose needs 99 lookups to move the element to the front
+                for _ in 0..200 {
+                    list.find(&99);
+                }
+                let stats = list.stats();
+                assert_eq!(stats.lookups, 200);
+                assert_eq!(stats.hits, 200);
+                assert!(
+                    stats.mean_probes() < 50.0,
+                    "hot element must become cheap to find with {heuristic:?}"
+                );
+            }
+        }
+
+        #[test]
+        fn test_reset_stats() {
+            let mut list = setup(Heuristic::MoveToFront, 3);
+            list.find(&2);
+            list.find(&5);
+            assert_eq!(
+                list.stats(),
+                AccessStats {
+                    lookups: 2,
+                    hits: 1,
+                    probes: 6
+                }
+            );
+            assert_eq!(list.stats().mean_probes(), 3.0);
+
+            list.reset_stats();
+            assert_eq!(list.stats(), AccessStats::default());
+            assert_eq!(list.stats().mean_probes(), 0.0);
+            assert_eq!(list.iter_with_hits().next(), Some((&2, 1)), "access counts must be kept");
+        }
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut list = setup(Heuristic::MoveToFront, 4);
+        assert_eq!(list.remove(&2), Some(2));
+        assert_eq!(list.remove(&3), Some(3), "remove the last element");
+        assert_eq!(list.remove(&3), None);
+        assert_eq!(values(&list), [0, 1]);
+        list.push(4);
+        assert_eq!(values(&list), [0, 1, 4]);
+        list.clear();
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_memory_leaks() {
+        use drop_tracker::DropTracker;
+
+        let mut tracker = DropTracker::new();
+
+        let mut list = SelfOrganizingList::new().with_heuristic(Heuristic::FrequencyCount);
+        for i in 0..10 {
+            list.push(tracker.track(i));
+        }
+        for i in [5, 7, 5, 9] {
+            list.find_if(|item| **item == i);
+        }
+        assert_eq!(tracker.alive().count(), 10, "reorganization must not drop elements");
+        list.clear();
+        assert_eq!(tracker.alive().count(), 0);
+
+        for i in 10..15 {
+            list.push(tracker.track(i));
+        }
+        list.find_if(|item| **item == 14);
+        drop(list);
+        assert_eq!(tracker.alive().count(), 0);
+        assert_eq!(tracker.dropped().count(), 15);
+    }
+}
@@ -1,11 +1,12 @@
 //! This module contains singly-linked list implementation.
 
-use std::ptr;
-
 use super::api::List;
+use super::bounded::{Bound, Overflow};
 use super::common::ListCommon;
-use super::node_one_link::{Node, merge_sort};
-use crate::core::{DSError, Result};
+use super::handle::ListHandle;
+use super::node_one_link::{Iter, Node};
+use crate::core::{DSError, InvariantViolation, Result, debug_check_invariants};
+use std::collections::BTreeMap;
 
 /// A singly-linked list implementation with efficient insertion at the front and back.
 ///
@@ -18,18 +19,19 @@ use crate::core::{DSError, Result};
 ///
 /// # Examples
 /// ```
-/// use plain_ds::SinglyLinkedList;
+/// use plain_ds::{List, SinglyLinkedList};
 ///
 /// let mut list = SinglyLinkedList::new();
 /// list.push(1);
 /// list.push(2);
 /// list.push(3);
 ///
-/// assert_eq!(list.pop(), Some(3));
+/// assert_eq!(list.pop_back(), Some(3));
 /// assert_eq!(list.len(), 2);
 /// ```
 pub struct SinglyLinkedList<T> {
     state: ListCommon<T>,
+    bound: Option<Bound>,
 }
 
 impl<T> SinglyLinkedList<T> {
@@ -37,9 +39,33 @@ impl<T> SinglyLinkedList<T> {
     pub fn new() -> Self {
         Self {
             state: ListCommon::new(),
+            bound: None,
         }
     }
 
+    /// Creates empty singly-linked list which never grows beyond `capacity` elements.
+    /// The `policy` defines what happens when a new element is added to the full list.
+    pub fn bounded(capacity: usize, policy: Overflow) -> Self {
+        Self {
+            state: ListCommon::new(),
+            bound: Some(Bound { capacity, policy }),
+        }
+    }
+
+    /// Returns the maximum number of elements, or `None` if the list is unbounded.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn capacity(&self) -> Option<usize> {
+        self.bound.map(|bound| bound.capacity)
+    }
+
+    /// Checks if the list is bounded and contains the maximum number of elements.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn is_full(&self) -> bool {
+        self.capacity().is_some_and(|capacity| self.len() >= capacity)
+    }
+
     /// Creates list from slice.
     ///
     /// **Efficiency**: O(n)
@@ -65,21 +91,49 @@ impl<T> SinglyLinkedList<T> {
     }
 
     /// Adds a new node to the front of the list.
+    /// If the list is bounded, the overflow policy is applied
+    /// (the evicted or rejected element is dropped): the new element is rejected
+    /// by the full list with [`Overflow::Reject`] or [`Overflow::EvictFront`] policy.
     ///
     /// **Efficiency**: O(1)
     pub fn push_front(&mut self, payload: T) {
-        let ptr = Box::into_raw(Box::new(Node::new(payload)));
-        if self.is_empty() {
-            self.state.last = ptr;
-        } else {
-            unsafe { (*ptr).next = self.state.head }
+        let _ = self.try_push_front(payload);
+    }
+
+    /// Adds a new node to the end of the list according to the overflow policy.
+    /// Returns the evicted element, if any.
+    /// Error returns, if the list is full and the policy is [`Overflow::Reject`].
+    ///
+    /// **Efficiency**: O(1)
+    pub fn try_push(&mut self, payload: T) -> Result<Option<T>> {
+        Bound::check(self.bound, self.len())?;
+        if Bound::evicts_new_back(self.bound, self.len()) {
+            return Ok(Some(payload));
         }
-        self.state.head = ptr;
-        self.state.size += 1;
+        self.state.push_back(payload);
+        let evicted = Bound::trim(self.bound, &mut self.state);
+        debug_check_invariants!(self);
+        Ok(evicted)
+    }
+
+    /// Adds a new node to the front of the list according to the overflow policy.
+    /// Returns the evicted element, if any.
+    /// Error returns, if the list is full and the policy is [`Overflow::Reject`],
+    /// or [`Overflow::EvictFront`], which would evict the new element itself.
+    ///
+    /// **Efficiency**: O(1), or O(n) for [`Overflow::EvictBack`] policy
+    pub fn try_push_front(&mut self, payload: T) -> Result<Option<T>> {
+        Bound::check_front(self.bound, self.len())?;
+        self.link_front(payload);
+        let evicted = Bound::trim(self.bound, &mut self.state);
+        debug_check_invariants!(self);
+        Ok(evicted)
     }
 
     /// Insert a new node at the specified location in the list.
-    /// Error returns, if the index out of bounds.
+    /// If the list is bounded, the overflow policy is applied (the evicted element is dropped).
+    /// Error returns, if the index out of bounds, or the list is full
+    /// and the policy is [`Overflow::Reject`] (or [`Overflow::EvictFront`] for index 0).
     ///
     /// **Efficiency**: O(n)
     pub fn insert(&mut self, index: usize, payload: T) -> Result<()> {
@@ -89,24 +143,100 @@ impl<T> SinglyLinkedList<T> {
                 len: self.state.size,
             });
         }
+        if index == 0 {
+            Bound::check_front(self.bound, self.len())?;
+        } else {
+            Bound::check(self.bound, self.len())?;
+        }
+        self.insert_unbounded(index, payload);
+        let _ = Bound::trim(self.bound, &mut self.state);
+        debug_check_invariants!(self);
+        Ok(())
+    }
+
+    /// Adds a new node to the end of the list like [`try_push`](Self::try_push)
+    /// and returns the handle of the new element, which stays valid until the element is removed.
+    /// If the new element is evicted itself, the returned handle is already invalid.
+    /// Error returns, if the list is full and the policy is [`Overflow::Reject`].
+    ///
+    /// **Efficiency**: O(1)
+    ///
+    /// # Examples
+    /// ```
+    /// use plain_ds::{List, SinglyLinkedList};
+    ///
+    /// let mut list = SinglyLinkedList::new();
+    /// let first = list.push_with_handle("first").unwrap();
+    /// let second = list.push_with_handle("second").unwrap();
+    /// list.push_front("zero");
+    ///
+    /// assert_eq!(list.get_by_handle(second), Ok(&"second"));
+    /// assert_eq!(list.remove_by_handle(first), Ok("first"));
+    /// assert!(list.get_by_handle(first).is_err());
+    /// assert_eq!(list.to_vec(), vec!["zero", "second"]);
+    /// ```
+    pub fn push_with_handle(&mut self, payload: T) -> Result<ListHandle> {
+        Bound::check(self.bound, self.len())?;
+        if Bound::evicts_new_back(self.bound, self.len()) {
+            return Ok(ListHandle::detached());
+        }
+        self.state.push_back(payload);
+        let handle = self.state.handles.register(self.state.last);
+        let _ = Bound::trim(self.bound, &mut self.state);
+        debug_check_invariants!(self);
+        Ok(handle)
+    }
+
+    /// Returns the element of the handle.
+    /// Error returns, if the element was removed from the list.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn get_by_handle(&self, handle: ListHandle) -> Result<&T> {
+        let node = self.state.handles.node(handle)?;
+        Ok(unsafe { &(*node).payload })
+    }
+
+    /// Removes the element of the handle and returns it.
+    /// Error returns, if the element was removed from the list.
+    ///
+    /// The next element takes the place of the removed one, so the last element,
+    /// which has no next one, needs the search of its predecessor from the head.
+    ///
+    /// **Efficiency**: O(1), or O(n) for the last element
+    pub fn remove_by_handle(&mut self, handle: ListHandle) -> Result<T> {
+        let node = self.state.handles.node(handle)?;
+        let payload = self.state.remove_node(node);
+        debug_check_invariants!(self);
+        Ok(payload)
+    }
+
+    /// Replaces the element of the handle and returns the old one. The handle stays valid.
+    /// Error returns, if the element was removed from the list.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn replace_by_handle(&mut self, handle: ListHandle, payload: T) -> Result<T> {
+        let node = self.state.handles.node(handle)?;
+        Ok(unsafe { std::mem::replace(&mut (*node).payload, payload) })
+    }
+
+    // Adds a new node to the front of the list ignoring the capacity limit.
+    fn link_front(&mut self, payload: T) {
+        self.state.push_front(payload);
+    }
+
+    // Inserts a new node at the valid location ignoring the capacity limit.
+    fn insert_unbounded(&mut self, index: usize, payload: T) {
         if index == self.state.size {
-            self.push(payload);
-            return Ok(());
+            self.state.push_back(payload);
+            return;
         }
         if index == 0 {
-            self.push_front(payload);
-            return Ok(());
+            self.link_front(payload);
+            return;
         }
 
-        // Finding the insert point
-        let mut current = self.state.head;
-        let mut index = index;
-        unsafe {
-            while index > 1 {
-                current = (*current).next;
-                index -= 1;
-            }
-        }
+        // Finding the insert point (the cached position stays valid)
+        let current = self.state.node_at(index - 1);
 
         let mut boxed = Box::new(Node::new(payload));
         unsafe {
@@ -115,7 +245,6 @@ impl<T> SinglyLinkedList<T> {
         }
 
         self.state.size += 1;
-        Ok(())
     }
 
     /// Finds the first node whose payload satisfies the predicate and returns its index.
@@ -133,53 +262,384 @@ impl<T> SinglyLinkedList<T> {
     ///
     /// **Efficiency**: O(n log n)
     ///
-    /// Space complexity: O(log n) due to recursion stack
+    /// Space complexity: O(1), the sort is bottom-up
+    ///
+    /// If a comparison panics, the list keeps all its elements (in unspecified order).
     pub fn sort(&mut self)
     where
-        T: PartialOrd + Default,
+        T: PartialOrd,
     {
-        if self.state.len() <= 1 {
-            return; // Already sorted
+        self.state.sort();
+        debug_check_invariants!(self);
+    }
+
+    /// Returns the mutable payload of the first node in the list.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn first_mut(&mut self) -> Option<&mut T> {
+        unsafe { self.state.head.as_mut() }.map(|node| &mut node.payload)
+    }
+
+    /// Returns the mutable payload of the last node in the list.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn last_mut(&mut self) -> Option<&mut T> {
+        unsafe { self.state.last.as_mut() }.map(|node| &mut node.payload)
+    }
+
+    /// Swaps two elements of the list (the payloads are swapped, the nodes stay in place).
+    /// The handles of the elements follow them.
+    /// Error returns, if any index out of bounds.
+    ///
+    /// **Efficiency**: O(max(i, j)), O(|i - j|) amortized for nearby positions
+    pub fn swap(&mut self, i: usize, j: usize) -> Result<()> {
+        let len = self.len();
+        for index in [i, j] {
+            if index >= len {
+                return Err(DSError::IndexOutOfBounds { index, len });
+            }
+        }
+        if i != j {
+            let first = self.state.node_at(i.min(j));
+            let second = self.state.node_at(i.max(j));
+            unsafe { std::mem::swap(&mut (*first).payload, &mut (*second).payload) };
+            self.state.handles.swap(first, second);
         }
+        Ok(())
+    }
 
-        // Extract the head and reset the list
-        let head = self.state.head;
-        self.state.head = ptr::null_mut();
-        self.state.last = ptr::null_mut();
-        self.state.size = 0;
+    /// Checks if the list contains an element equal to `value`.
+    ///
+    /// **Efficiency**: O(n)
+    pub fn contains(&self, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.state.iter().any(|payload| payload == value)
+    }
 
-        // Sort the extracted nodes and get new head
-        let sorted_head = merge_sort(head);
+    /// Checks if the list starts with the elements of `needle`.
+    ///
+    /// **Efficiency**: O(m), where m is the length of `needle`
+    pub fn starts_with(&self, needle: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        needle.len() <= self.len() && self.state.iter().zip(needle).all(|(a, b)| a == b)
+    }
 
-        // Reconstruct the list with sorted nodes
-        self.rebuild_from_sorted_list(sorted_head);
+    /// Checks if the list ends with the elements of `needle`.
+    ///
+    /// **Efficiency**: O(n)
+    pub fn ends_with(&self, needle: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        needle.len() <= self.len()
+            && self
+                .state
+                .iter()
+                .skip(self.len() - needle.len())
+                .zip(needle)
+                .all(|(a, b)| a == b)
     }
 
-    /// Rebuilds the list from a sorted list of nodes
-    fn rebuild_from_sorted_list(&mut self, head: *mut Node<T>) {
-        self.state.head = head;
-        self.state.size = 0;
+    /// Splits the list into two at `mid` by relinking of the nodes: the first list contains
+    /// the elements `[0, mid)`, the second one - `[mid, len)`. Both lists keep the capacity limit.
+    /// Error returns, if `mid` is greater than the list size.
+    ///
+    /// **Efficiency**: O(mid)
+    pub fn split_at(mut self, mid: usize) -> Result<(Self, Self)> {
+        if mid > self.len() {
+            return Err(DSError::IndexOutOfBounds {
+                index: mid,
+                len: self.len(),
+            });
+        }
+        let front = Self {
+            state: self.state.split_front(mid),
+            bound: self.bound,
+        };
+        debug_check_invariants!(front);
+        debug_check_invariants!(self);
+        Ok((front, self))
+    }
 
-        if head.is_null() {
-            self.state.last = std::ptr::null_mut();
-            return;
+    /// Returns an iterator over the chunks of `size` elements (the last chunk may be shorter).
+    /// Every chunk is an iterator over the elements borrowed from the list.
+    ///
+    /// **Efficiency**: O(size) per chunk
+    ///
+    /// # Panics
+    /// Panics if `size` is 0.
+    pub fn chunks(&self, size: usize) -> impl Iterator<Item = impl Iterator<Item = &T> + Clone> {
+        assert!(size > 0, "chunk size must be non-zero");
+        let mut current = self.state.head as *const Node<T>;
+        std::iter::from_fn(move || {
+            if current.is_null() {
+                return None;
+            }
+            let chunk = Iter::new(current).take(size);
+            for _ in 0..size {
+                if current.is_null() {
+                    break;
+                }
+                current = unsafe { (*current).next };
+            }
+            Some(chunk)
+        })
+    }
+
+    /// Returns an iterator over the overlapping windows of `size` elements.
+    /// Every window is an iterator over the elements borrowed from the list.
+    /// There are no windows, if the list is shorter than `size`.
+    ///
+    /// **Efficiency**: O(1) per window, O(size) to iterate it
+    ///
+    /// # Panics
+    /// Panics if `size` is 0.
+    pub fn windows(&self, size: usize) -> impl Iterator<Item = impl Iterator<Item = &T> + Clone> {
+        assert!(size > 0, "window size must be non-zero");
+        let mut current = self.state.head as *const Node<T>;
+        let mut remaining = self.len();
+        std::iter::from_fn(move || {
+            if remaining < size {
+                return None;
+            }
+            let window = Iter::new(current).take(size);
+            current = unsafe { (*current).next };
+            remaining -= 1;
+            Some(window)
+        })
+    }
+
+    /// Returns an iterator over the runs of elements where `predicate` is true for every pair
+    /// of adjacent elements. Every run is an iterator over the elements borrowed from the list.
+    ///
+    /// **Efficiency**: O(n) for all runs
+    pub fn chunk_by(
+        &self,
+        mut predicate: impl FnMut(&T, &T) -> bool,
+    ) -> impl Iterator<Item = impl Iterator<Item = &T> + Clone> {
+        let mut current = self.state.head as *const Node<T>;
+        std::iter::from_fn(move || {
+            if current.is_null() {
+                return None;
+            }
+            let start = current;
+            let len = unsafe { run_len(&mut current, &mut predicate) };
+            Some(Iter::new(start).take(len))
+        })
+    }
+
+    /// Consumes the list and returns an iterator over the lists of `size` elements
+    /// (the last list may be shorter), built by relinking of the nodes.
+    /// The lists keep the capacity limit.
+    ///
+    /// **Efficiency**: O(size) per chunk
+    ///
+    /// # Panics
+    /// Panics if `size` is 0.
+    pub fn into_chunks(mut self, size: usize) -> impl Iterator<Item = Self> {
+        assert!(size > 0, "chunk size must be non-zero");
+        std::iter::from_fn(move || {
+            if self.is_empty() {
+                return None;
+            }
+            Some(Self {
+                state: self.state.split_front(size),
+                bound: self.bound,
+            })
+        })
+    }
+
+    /// Consumes the list and returns an iterator over the lists of the runs of elements
+    /// where `predicate` is true for every pair of adjacent elements, built by relinking
+    /// of the nodes. The lists keep the capacity limit.
+    ///
+    /// **Efficiency**: O(n) for all runs
+    pub fn into_chunk_by(mut self, mut predicate: impl FnMut(&T, &T) -> bool) -> impl Iterator<Item = Self> {
+        std::iter::from_fn(move || {
+            if self.is_empty() {
+                return None;
+            }
+            let mut current = self.state.head as *const Node<T>;
+            let len = unsafe { run_len(&mut current, &mut predicate) };
+            Some(Self {
+                state: self.state.split_front(len),
+                bound: self.bound,
+            })
+        })
+    }
+
+    /// Consumes the list and splits it into the list of the elements which satisfy
+    /// the predicate and the list of the other elements, by relinking of the nodes
+    /// (no reallocation or cloning). Both lists keep the relative order of the elements,
+    /// the capacity limit and the handles of the elements.
+    ///
+    /// **Efficiency**: O(n)
+    ///
+    /// # Examples
+    /// ```
+    /// use plain_ds::SinglyLinkedList;
+    ///
+    /// let list = SinglyLinkedList::from_slice(&[1, 2, 3, 4, 5]);
+    /// let (even, odd) = list.partition(|x| x % 2 == 0);
+    /// assert_eq!(even.to_vec(), vec![2, 4]);
+    /// assert_eq!(odd.to_vec(), vec![1, 3, 5]);
+    /// ```
+    pub fn partition(mut self, mut predicate: impl FnMut(&T) -> bool) -> (Self, Self) {
+        let mut matched = Self {
+            state: ListCommon::new(),
+            bound: self.bound,
+        };
+        let mut rest = Self {
+            state: ListCommon::new(),
+            bound: self.bound,
+        };
+        while let Some(payload) = self.state.head() {
+            let target = if predicate(payload) { &mut matched } else { &mut rest };
+            target.state.append(self.state.split_front(1));
+        }
+        debug_check_invariants!(matched);
+        debug_check_invariants!(rest);
+        (matched, rest)
+    }
+
+    /// Moves the elements which satisfy the predicate before the other elements
+    /// by relinking of the nodes and returns their number. Both parts keep
+    /// the relative order of the elements.
+    ///
+    /// **Efficiency**: O(n)
+    ///
+    /// If the predicate panics, the list keeps all its elements (in unspecified order).
+    ///
+    /// # Examples
+    /// ```
+    /// use plain_ds::SinglyLinkedList;
+    ///
+    /// let mut list = SinglyLinkedList::from_slice(&[1, 2, 3, 4, 5]);
+    /// assert_eq!(list.partition_in_place(|x| x % 2 == 0), 2);
+    /// assert_eq!(list.to_vec(), vec![2, 4, 1, 3, 5]);
+    /// ```
+    pub fn partition_in_place(&mut self, mut predicate: impl FnMut(&T) -> bool) -> usize {
+        let len = self.len();
+        let mut count = 0;
+        let mut rest = Appended {
+            list: &mut self.state,
+            tail: ListCommon::new(),
+        };
+        for _ in 0..len {
+            // The matched elements are rotated to the end, the others are moved aside
+            let matches = rest.list.head().is_some_and(&mut predicate);
+            let front = rest.list.split_front(1);
+            if matches {
+                rest.list.append(front);
+                count += 1;
+            } else {
+                rest.tail.append(front);
+            }
+        }
+        drop(rest);
+        debug_check_invariants!(self);
+        count
+    }
+
+    /// Consumes the list and groups its elements by the key into the lists,
+    /// by relinking of the nodes (no reallocation or cloning). Every list keeps
+    /// the relative order of the elements, the capacity limit and the handles of the elements.
+    ///
+    /// **Efficiency**: O(n log k), where k is the number of groups
+    ///
+    /// # Examples
+    /// ```
+    /// use plain_ds::SinglyLinkedList;
+    ///
+    /// let list = SinglyLinkedList::from_slice(&["apple", "bean", "avocado", "cherry", "banana"]);
+    /// let groups = list.group_into(|word| word.chars().next());
+    /// assert_eq!(groups[&Some('a')].to_vec(), vec!["apple", "avocado"]);
+    /// assert_eq!(groups[&Some('b')].to_vec(), vec!["bean", "banana"]);
+    /// assert_eq!(groups.len(), 3);
+    /// ```
+    pub fn group_into<K: Ord>(mut self, mut key: impl FnMut(&T) -> K) -> BTreeMap<K, Self> {
+        let mut groups = BTreeMap::new();
+        while let Some(payload) = self.state.head() {
+            let group = groups.entry(key(payload)).or_insert_with(|| Self {
+                state: ListCommon::new(),
+                bound: self.bound,
+            });
+            group.state.append(self.state.split_front(1));
+            debug_check_invariants!(group);
         }
+        groups
+    }
 
-        // Traverse to find the last node and count size
-        let mut current = head;
-        self.state.size = 1;
+    /// Checks the internal structure of the list: `size` is equal to the number of nodes,
+    /// `last` is the tail node, the node chain has no cycles.
+    /// With the `check-invariants` feature, it runs after every mutation in debug builds.
+    ///
+    /// **Efficiency**: O(n)
+    pub fn check_invariants(&self) -> std::result::Result<(), InvariantViolation> {
+        self.state.check_invariants()
+    }
+}
 
-        unsafe {
-            while !(*current).next.is_null() {
-                current = (*current).next;
-                self.state.size += 1;
+// Returns the length of the run of elements starting at the non-null `current` node, where
+// `predicate` is true for every pair of adjacent elements, and moves `current` to the next run.
+unsafe fn run_len<T>(current: &mut *const Node<T>, predicate: &mut impl FnMut(&T, &T) -> bool) -> usize {
+    let mut len = 1;
+    unsafe {
+        loop {
+            let next = (**current).next;
+            if next.is_null() || !predicate(&(**current).payload, &(*next).payload) {
+                *current = next;
+                return len;
             }
-            self.state.last = current;
+            *current = next;
+            len += 1;
+        }
+    }
+}
+
+// Moves the nodes of `tail` to the end of the list on drop, even if the relinking
+// is interrupted by a panic.
+struct Appended<'a, T> {
+    list: &'a mut ListCommon<T>,
+    tail: ListCommon<T>,
+}
+
+impl<T> Drop for Appended<'_, T> {
+    fn drop(&mut self) {
+        self.list.append(std::mem::replace(&mut self.tail, ListCommon::new()));
+    }
+}
+
+impl<T> Default for SinglyLinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FromIterator<T> for SinglyLinkedList<T> {
+    /// Pushes the items to the end of the list in the iteration order.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = SinglyLinkedList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> Extend<T> for SinglyLinkedList<T> {
+    /// Pushes the items to the end of the list in the iteration order.
+    /// If the list is bounded, the overflow policy is applied.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for payload in iter {
+            List::push(self, payload);
         }
     }
 }
 
-impl<'a, T: 'a> List<'a, T> for SinglyLinkedList<T> {
+impl<T> List<T> for SinglyLinkedList<T> {
     /// Returns list size.
     ///
     /// **Efficiency**: O(1)
@@ -201,13 +661,33 @@ impl<'a, T: 'a> List<'a, T> for SinglyLinkedList<T> {
         self.state.last()
     }
 
+    /// Returns a list item by index, or error if index out of bounds.
+    ///
+    /// **Efficiency**: O(n), O(1) amortized for sequential access
+    fn get(&self, index: usize) -> Result<&T> {
+        self.state.get(index)
+    }
+
+    /// Returns a mutable list item by index, or error if index out of bounds.
+    ///
+    /// **Efficiency**: O(n), O(1) amortized for sequential access
+    fn get_mut(&mut self, index: usize) -> Result<&mut T> {
+        self.state.get_mut(index)
+    }
+
     /// Returns an iterator over the immutable items of the list.
-    fn iter(&self) -> impl Iterator<Item = &'a T> {
+    fn iter<'a>(&'a self) -> impl Iterator<Item = &'a T>
+    where
+        T: 'a,
+    {
         self.state.iter()
     }
 
     /// Returns an iterator over the mutable items of the list.
-    fn iter_mut(&mut self) -> impl Iterator<Item = &'a mut T> {
+    fn iter_mut<'a>(&'a mut self) -> impl Iterator<Item = &'a mut T>
+    where
+        T: 'a,
+    {
         self.state.iter_mut()
     }
 
@@ -217,24 +697,30 @@ impl<'a, T: 'a> List<'a, T> for SinglyLinkedList<T> {
     }
 
     /// Adds a new node to the end of the list.
+    /// If the list is bounded, the overflow policy is applied
+    /// (the evicted or rejected element is dropped).
     ///
     /// **Efficiency**: O(1)
     fn push(&mut self, payload: T) {
-        self.state.push_back(payload);
+        let _ = self.try_push(payload);
     }
 
     /// Removes a node from the end of the list and returns its payload value.
     ///
     /// **Efficiency**: O(n)
     fn pop_back(&mut self) -> Option<T> {
-        self.state.pop_back()
+        let payload = self.state.pop_back();
+        debug_check_invariants!(self);
+        payload
     }
 
     /// Removes a node from the front of the list and returns its payload value.
     ///
     /// **Efficiency**: O(1)
     fn pop_front(&mut self) -> Option<T> {
-        self.state.pop_front()
+        let payload = self.state.pop_front();
+        debug_check_invariants!(self);
+        payload
     }
 
     /// Removes a node from the specified location in the list.
@@ -242,7 +728,9 @@ impl<'a, T: 'a> List<'a, T> for SinglyLinkedList<T> {
     ///
     /// **Efficiency**: O(n)
     fn remove(&mut self, index: usize) -> Result<T> {
-        self.state.remove(index)
+        let payload = self.state.remove(index);
+        debug_check_invariants!(self);
+        payload
     }
 }
 
@@ -250,6 +738,9 @@ impl<'a, T: 'a> List<'a, T> for SinglyLinkedList<T> {
 mod tests {
     use super::*;
 
+    crate::list_conformance_tests!(conformance, SinglyLinkedList::new);
+    crate::list_conformance_tests!(bounded_conformance, || SinglyLinkedList::bounded(16, Overflow::Reject));
+
     // Helper function to create a list with values [0, 1, 2, ..., n-1]
     fn setup_list(n: usize) -> SinglyLinkedList<usize> {
         let mut list = SinglyLinkedList::new();
@@ -419,6 +910,23 @@ mod tests {
             );
         }
 
+        #[test]
+        fn test_get_after_insert_and_push_front() {
+            let mut list = setup_list(6); // [0, 1, 2, 3, 4, 5]
+            assert_eq!(list.get(3), Ok(&3));
+
+            list.push_front(10); // [10, 0, 1, 2, 3, 4, 5]
+            assert_eq!(list.get(4), Ok(&3), "cached position must be shifted by push_front()");
+
+            list.insert(2, 20).unwrap(); // [10, 0, 20, 1, 2, 3, 4, 5]
+            assert_eq!(list.get(3), Ok(&1), "get() after insert() returns wrong item");
+            assert_eq!(list.get(2), Ok(&20));
+
+            list.sort(); // [0, 1, 2, 3, 4, 5, 10, 20]
+            let values: Vec<_> = (0..list.len()).map(|i| *list.get(i).unwrap()).collect();
+            assert_eq!(values, [0, 1, 2, 3, 4, 5, 10, 20], "get() after sort() returns wrong items");
+        }
+
         #[test]
         fn test_get_mut_empty_list() {
             let mut list: SinglyLinkedList<i32> = SinglyLinkedList::new();
@@ -960,6 +1468,7 @@ mod tests {
         }
 
         #[test]
+        #[allow(clippy::map_clone)]
         fn test_insert_with_complex_types_string() {
             let mut list = SinglyLinkedList::new();
             list.push("first".to_string());
@@ -1502,6 +2011,156 @@ mod tests {
         }
     }
 
+    mod bounded {
+        use super::*;
+        use crate::list::Overflow;
+
+        fn setup_bounded(capacity: usize, policy: Overflow, n: usize) -> SinglyLinkedList<usize> {
+            let mut list = SinglyLinkedList::bounded(capacity, policy);
+            for i in 0..n {
+                list.push(i);
+            }
+            list
+        }
+
+        #[test]
+        fn test_unbounded_by_default() {
+            let list = setup_list(3);
+            assert_eq!(list.capacity(), None, "list must be unbounded by default");
+            assert!(!list.is_full(), "unbounded list is never full");
+        }
+
+        #[test]
+        fn test_capacity_and_is_full() {
+            let list = setup_bounded(3, Overflow::Reject, 2);
+            assert_eq!(list.capacity(), Some(3));
+            assert!(!list.is_full(), "list with free space must not be full");
+
+            let list = setup_bounded(3, Overflow::Reject, 3);
+            assert!(list.is_full(), "list must be full");
+        }
+
+        #[test]
+        fn test_reject_policy() {
+            let mut list = setup_bounded(3, Overflow::Reject, 3);
+
+            assert_eq!(
+                list.try_push(10),
+                Err(DSError::CapacityExceeded { capacity: 3 }),
+                "try_push() into full list must be rejected"
+            );
+            assert_eq!(
+                list.try_push_front(10),
+                Err(DSError::CapacityExceeded { capacity: 3 }),
+                "try_push_front() into full list must be rejected"
+            );
+            assert_eq!(
+                list.insert(1, 10),
+                Err(DSError::CapacityExceeded { capacity: 3 }),
+                "insert() into full list must be rejected"
+            );
+
+            list.push(10);
+            list.push_front(10);
+            assert_eq!(list.to_vec(), [0, 1, 2], "rejected elements must not be added");
+        }
+
+        #[test]
+        fn test_evict_front_policy() {
+            let mut list = setup_bounded(3, Overflow::EvictFront, 3);
+
+            assert_eq!(list.try_push(3), Ok(Some(0)), "the oldest element must be evicted");
+            list.push(4);
+            assert_eq!(list.to_vec(), [2, 3, 4]);
+
+            list.insert(1, 10).unwrap();
+            assert_eq!(list.to_vec(), [10, 3, 4], "insert() must evict the front element");
+            assert_eq!(list.len(), 3, "size must not exceed the capacity");
+        }
+
+        #[test]
+        fn test_evict_front_rejects_front_insertion() {
+            let mut list = setup_bounded(3, Overflow::EvictFront, 3);
+            let full = || DSError::CapacityExceeded { capacity: 3 };
+
+            assert_eq!(list.try_push_front(10), Err(full()), "the new element must not be evicted itself");
+            assert_eq!(list.insert(0, 10), Err(full()));
+            list.push_front(10);
+            assert_eq!(list.to_vec(), [0, 1, 2], "the oldest elements must be kept");
+
+            list.pop_front();
+            assert_eq!(list.try_push_front(10), Ok(None), "a free slot must accept the element");
+            assert_eq!(list.to_vec(), [10, 1, 2]);
+        }
+
+        #[test]
+        fn test_evict_back_policy() {
+            let mut list = setup_bounded(3, Overflow::EvictBack, 3);
+
+            assert_eq!(list.try_push_front(10), Ok(Some(2)), "the back element must be evicted");
+            assert_eq!(list.to_vec(), [10, 0, 1]);
+            assert_eq!(list.last(), Some(&1), "last must be updated after eviction");
+
+            assert_eq!(list.try_push(20), Ok(Some(20)), "the pushed element is the back one");
+            assert_eq!(list.to_vec(), [10, 0, 1]);
+        }
+
+        #[test]
+        fn test_free_space_no_eviction() {
+            let mut list = setup_bounded(3, Overflow::EvictFront, 1);
+            assert_eq!(list.try_push(1), Ok(None), "nothing to evict while not full");
+            assert_eq!(list.try_push_front(2), Ok(None));
+            assert_eq!(list.to_vec(), [2, 0, 1]);
+        }
+
+        #[test]
+        fn test_zero_capacity() {
+            let mut list = SinglyLinkedList::bounded(0, Overflow::EvictFront);
+            assert_eq!(list.try_push(1), Ok(Some(1)), "the new element must be evicted at once");
+            assert!(list.is_empty(), "zero capacity list must stay empty");
+            assert_eq!(list.head(), None);
+            assert_eq!(list.last(), None);
+        }
+
+        #[test]
+        fn test_evicted_back_push_is_not_linked() {
+            use drop_tracker::DropTracker;
+
+            let mut tracker = DropTracker::new();
+            let mut list = SinglyLinkedList::bounded(2, Overflow::EvictBack);
+            list.push(tracker.track(1));
+            list.push(tracker.track(2));
+
+            let evicted = list.try_push(tracker.track(3)).unwrap().expect("the new element must be returned");
+            assert_eq!(evicted, 3);
+            assert_eq!(tracker.alive().count(), 3, "the returned element must not be dropped");
+            assert_eq!(list.iter().collect::<Vec<_>>(), [&1, &2]);
+            assert_eq!(list.last().map(|item| **item), Some(2), "last must not be changed");
+
+            let handle = list.push_with_handle(tracker.track(4)).unwrap();
+            assert_eq!(list.get_by_handle(handle), Err(DSError::InvalidHandle));
+            assert_eq!(tracker.dropped().count(), 1, "the element of the invalid handle must be dropped");
+            assert_eq!(list.len(), 2);
+        }
+
+        #[test]
+        fn test_bounded_memory_leaks() {
+            use drop_tracker::DropTracker;
+
+            let mut tracker = DropTracker::new();
+
+            let mut list = SinglyLinkedList::bounded(5, Overflow::EvictFront);
+            for i in 0..20 {
+                list.push(tracker.track(i));
+            }
+            assert_eq!(tracker.alive().count(), 5, "evicted elements must be dropped");
+
+            drop(list);
+            assert_eq!(tracker.alive().count(), 0);
+            assert_eq!(tracker.dropped().count(), 20);
+        }
+    }
+
     mod memory_leaks {
         use super::*;
         use drop_tracker::DropTracker;
@@ -1902,4 +2561,460 @@ mod tests {
             );
         }
     }
+
+    mod slice_api {
+        use super::*;
+
+        fn collect<'a>(parts: impl Iterator<Item = impl Iterator<Item = &'a usize>>) -> Vec<Vec<usize>> {
+            parts.map(|part| part.copied().collect()).collect()
+        }
+
+        #[test]
+        fn test_first_last_mut() {
+            let mut list = setup_list(3);
+            *list.first_mut().unwrap() = 10;
+            *list.last_mut().unwrap() = 20;
+            assert_eq!(list.to_vec(), [10, 1, 20]);
+
+            let mut empty: SinglyLinkedList<u8> = SinglyLinkedList::new();
+            assert_eq!(empty.first_mut(), None);
+            assert_eq!(empty.last_mut(), None);
+        }
+
+        #[test]
+        fn test_swap() {
+            let mut list = setup_list(5);
+            assert_eq!(list.swap(4, 1), Ok(()));
+            assert_eq!(list.to_vec(), [0, 4, 2, 3, 1]);
+            assert_eq!(list.swap(2, 2), Ok(()), "swap() of the same index");
+            assert_eq!(list.swap(0, 5), Err(DSError::IndexOutOfBounds { index: 5, len: 5 }));
+            assert_eq!(list.swap(7, 0), Err(DSError::IndexOutOfBounds { index: 7, len: 5 }));
+            assert_eq!(list.to_vec(), [0, 4, 2, 3, 1], "failed swap() must not change the list");
+            assert_eq!(list.last(), Some(&1));
+        }
+
+        #[test]
+        fn test_contains_starts_ends_with() {
+            let list = setup_list(5);
+            assert!(list.contains(&3));
+            assert!(!list.contains(&5));
+
+            assert!(list.starts_with(&[]));
+            assert!(list.starts_with(&[0, 1]));
+            assert!(!list.starts_with(&[1]));
+            assert!(list.starts_with(&[0, 1, 2, 3, 4]));
+            assert!(!list.starts_with(&[0, 1, 2, 3, 4, 5]), "needle longer than the list");
+
+            assert!(list.ends_with(&[]));
+            assert!(list.ends_with(&[3, 4]));
+            assert!(!list.ends_with(&[3]));
+            assert!(list.ends_with(&[0, 1, 2, 3, 4]));
+            assert!(!list.ends_with(&[9, 0, 1, 2, 3, 4]), "needle longer than the list");
+        }
+
+        #[test]
+        fn test_split_at() {
+            let (front, back) = setup_list(5).split_at(2).unwrap();
+            assert_eq!(front.to_vec(), [0, 1]);
+            assert_eq!(back.to_vec(), [2, 3, 4]);
+            assert_eq!(front.last(), Some(&1), "last of the front part must be set");
+            assert_eq!(back.get(0), Ok(&2));
+
+            let (front, back) = setup_list(3).split_at(0).unwrap();
+            assert!(front.is_empty());
+            assert_eq!(back.len(), 3);
+            let (front, back) = setup_list(3).split_at(3).unwrap();
+            assert_eq!(front.len(), 3);
+            assert!(back.is_empty());
+
+            assert!(matches!(
+                setup_list(3).split_at(4),
+                Err(DSError::IndexOutOfBounds { index: 4, len: 3 })
+            ));
+
+            let mut list = SinglyLinkedList::bounded(4, Overflow::Reject);
+            list.extend([1, 2, 3, 4]);
+            let (mut front, _) = list.split_at(1).unwrap();
+            assert_eq!(front.capacity(), Some(4), "parts must keep the capacity limit");
+            front.push(5);
+            assert_eq!(front.to_vec(), [1, 5]);
+        }
+
+        #[test]
+        fn test_chunks() {
+            let list = setup_list(7);
+            assert_eq!(collect(list.chunks(3)), [vec![0, 1, 2], vec![3, 4, 5], vec![6]]);
+            assert_eq!(collect(list.chunks(7)), [vec![0, 1, 2, 3, 4, 5, 6]]);
+            assert_eq!(collect(list.chunks(10)).len(), 1);
+            assert_eq!(collect(setup_list(0).chunks(2)).len(), 0);
+
+            let chunk = list.chunks(2).nth(1).unwrap();
+            assert_eq!(chunk.clone().count(), 2, "chunk must be cloneable");
+            assert_eq!(chunk.sum::<usize>(), 5);
+        }
+
+        #[test]
+        #[should_panic(expected = "chunk size must be non-zero")]
+        fn test_chunks_zero_size() {
+            let _ = setup_list(3).chunks(0);
+        }
+
+        #[test]
+        fn test_windows() {
+            let list = setup_list(4);
+            assert_eq!(collect(list.windows(2)), [vec![0, 1], vec![1, 2], vec![2, 3]]);
+            assert_eq!(collect(list.windows(4)), [vec![0, 1, 2, 3]]);
+            assert_eq!(collect(list.windows(5)).len(), 0, "no windows longer than the list");
+            assert_eq!(
+                list.windows(3).map(|window| window.sum::<usize>()).collect::<Vec<_>>(),
+                [3, 6]
+            );
+        }
+
+        #[test]
+        fn test_chunk_by() {
+            let list = SinglyLinkedList::from_slice(&[1, 1, 2, 3, 3, 3, 1]);
+            let runs: Vec<Vec<i32>> = list
+                .chunk_by(|a, b| a == b)
+                .map(|run| run.copied().collect())
+                .collect();
+            assert_eq!(runs, [vec![1, 1], vec![2], vec![3, 3, 3], vec![1]]);
+
+            let ascending: Vec<usize> = SinglyLinkedList::from_slice(&[1, 2, 3, 2, 5, 1])
+                .chunk_by(|a, b| a < b)
+                .map(|run| run.count())
+                .collect();
+            assert_eq!(ascending, [3, 2, 1]);
+            assert_eq!(SinglyLinkedList::<u8>::new().chunk_by(|_, _| true).count(), 0);
+        }
+
+        #[test]
+        fn test_into_chunks() {
+            let parts: Vec<_> = setup_list(7).into_chunks(3).collect();
+            assert_eq!(parts.len(), 3);
+            assert_eq!(parts[0].to_vec(), [0, 1, 2]);
+            assert_eq!(parts[2].to_vec(), [6]);
+            for part in &parts {
+                assert_eq!(part.check_invariants(), Ok(()));
+            }
+            assert_eq!(setup_list(0).into_chunks(3).count(), 0);
+        }
+
+        #[test]
+        fn test_into_chunk_by() {
+            let list = SinglyLinkedList::from_slice(&[1, 1, 2, 3, 3, 3, 1]);
+            let parts: Vec<_> = list.into_chunk_by(|a, b| a == b).map(|part| part.to_vec()).collect();
+            assert_eq!(parts, [vec![1, 1], vec![2], vec![3, 3, 3], vec![1]]);
+        }
+
+        #[test]
+        fn test_owned_splits_memory_leaks() {
+            use drop_tracker::DropTracker;
+
+            let mut tracker = DropTracker::new();
+
+            let list: SinglyLinkedList<_> = (0..10).map(|i| tracker.track(i)).collect();
+            let mut parts = list.into_chunks(3);
+            let first = parts.next().unwrap();
+            drop(parts);
+            assert_eq!(tracker.alive().count(), 3, "the rest of the list must be dropped with the iterator");
+            let (front, back) = first.split_at(1).unwrap();
+            drop(front);
+            assert_eq!(back.len(), 2);
+            drop(back);
+
+            let list: SinglyLinkedList<_> = (10..20).map(|i| tracker.track(i)).collect();
+            let parts: Vec<_> = list.into_chunk_by(|a, b| **a / 3 == **b / 3).collect();
+            assert_eq!(parts.len(), 4);
+            drop(parts);
+
+            assert_eq!(tracker.alive().count(), 0);
+            assert_eq!(tracker.dropped().count(), 20);
+        }
+    }
+
+    mod handles {
+        use super::*;
+        use crate::list::ListHandle;
+        use drop_tracker::DropTracker;
+
+        fn setup_with_handles(n: usize) -> (SinglyLinkedList<usize>, Vec<ListHandle>) {
+            let mut list = SinglyLinkedList::new();
+            let handles = (0..n).map(|i| list.push_with_handle(i).unwrap()).collect();
+            (list, handles)
+        }
+
+        #[test]
+        fn test_get_and_replace() {
+            let (mut list, handles) = setup_with_handles(3);
+            list.push_front(10);
+            list.insert(2, 20).unwrap();
+            assert_eq!(list.to_vec(), [10, 0, 20, 1, 2]);
+            for (value, handle) in handles.iter().enumerate() {
+                assert_eq!(list.get_by_handle(*handle), Ok(&value), "handles must not depend on indexes");
+            }
+
+            assert_eq!(list.replace_by_handle(handles[1], 11), Ok(1));
+            assert_eq!(list.get_by_handle(handles[1]), Ok(&11), "handle must stay valid after replace");
+            assert_eq!(list.to_vec(), [10, 0, 20, 11, 2]);
+        }
+
+        #[test]
+        fn test_remove() {
+            let (mut list, handles) = setup_with_handles(4);
+            assert_eq!(list.remove_by_handle(handles[1]), Ok(1), "remove from the middle");
+            assert_eq!(list.get_by_handle(handles[2]), Ok(&2), "handle of the next element must stay valid");
+            assert_eq!(list.remove_by_handle(handles[3]), Ok(3), "remove of the last");
+            assert_eq!(list.last(), Some(&2));
+            assert_eq!(list.remove_by_handle(handles[0]), Ok(0), "remove of the head");
+            assert_eq!(list.to_vec(), [2]);
+            assert_eq!(list.get_by_handle(handles[2]), Ok(&2));
+            assert_eq!(list.check_invariants(), Ok(()));
+
+            for handle in [handles[0], handles[1], handles[3]] {
+                assert_eq!(list.get_by_handle(handle), Err(DSError::InvalidHandle));
+                assert_eq!(list.remove_by_handle(handle), Err(DSError::InvalidHandle));
+                assert_eq!(list.replace_by_handle(handle, 7), Err(DSError::InvalidHandle));
+            }
+            assert_eq!(list.len(), 1, "misuse of handles must not change the list");
+        }
+
+        #[test]
+        fn test_invalid_after_removal_by_index() {
+            let (mut list, handles) = setup_with_handles(5);
+            list.pop_front();
+            list.pop_back();
+            list.remove(1).unwrap();
+            assert_eq!(list.get_by_handle(handles[0]), Err(DSError::InvalidHandle));
+            assert_eq!(list.get_by_handle(handles[2]), Err(DSError::InvalidHandle));
+            assert_eq!(list.get_by_handle(handles[4]), Err(DSError::InvalidHandle));
+            assert_eq!(list.get_by_handle(handles[1]), Ok(&1));
+            assert_eq!(list.get_by_handle(handles[3]), Ok(&3));
+
+            list.clear();
+            assert_eq!(list.get_by_handle(handles[1]), Err(DSError::InvalidHandle));
+            // The new node may reuse the memory of the freed one
+            list.push(100);
+            assert_eq!(list.get_by_handle(handles[3]), Err(DSError::InvalidHandle));
+        }
+
+        #[test]
+        fn test_handle_of_another_list() {
+            let (list, handles) = setup_with_handles(3);
+            let (other, _) = setup_with_handles(3);
+            assert_eq!(other.get_by_handle(handles[0]), Err(DSError::InvalidHandle));
+            assert_eq!(list.get_by_handle(handles[0]), Ok(&0));
+        }
+
+        #[test]
+        fn test_handles_follow_elements() {
+            let (mut list, handles) = setup_with_handles(6);
+            list.swap(0, 5).unwrap();
+            assert_eq!(list.get_by_handle(handles[0]), Ok(&0), "handle must follow the swapped element");
+            assert_eq!(list.get_by_handle(handles[5]), Ok(&5));
+            list.sort();
+            assert_eq!(list.get_by_handle(handles[5]), Ok(&5), "handle must follow the sorted element");
+
+            let (front, back) = list.split_at(2).unwrap();
+            assert_eq!(front.get_by_handle(handles[1]), Ok(&1));
+            assert_eq!(back.get_by_handle(handles[4]), Ok(&4));
+            assert_eq!(back.get_by_handle(handles[1]), Err(DSError::InvalidHandle));
+            assert_eq!(front.check_invariants(), Ok(()));
+            assert_eq!(back.check_invariants(), Ok(()));
+
+            let chunks: Vec<_> = back.into_chunks(3).collect();
+            assert_eq!(chunks[0].get_by_handle(handles[3]), Ok(&3));
+        }
+
+        #[test]
+        fn test_bounded() {
+            let mut list = SinglyLinkedList::bounded(2, Overflow::EvictFront);
+            let first = list.push_with_handle(1).unwrap();
+            let second = list.push_with_handle(2).unwrap();
+            let third = list.push_with_handle(3).unwrap();
+            assert_eq!(list.get_by_handle(first), Err(DSError::InvalidHandle), "evicted element");
+            assert_eq!(list.get_by_handle(second), Ok(&2));
+            assert_eq!(list.get_by_handle(third), Ok(&3));
+
+            let mut list = SinglyLinkedList::bounded(1, Overflow::EvictBack);
+            let first = list.push_with_handle(1).unwrap();
+            let second = list.push_with_handle(2).unwrap();
+            assert_eq!(list.get_by_handle(first), Ok(&1));
+            assert_eq!(list.get_by_handle(second), Err(DSError::InvalidHandle), "new element is evicted itself");
+
+            let mut list = SinglyLinkedList::bounded(1, Overflow::Reject);
+            list.push(1);
+            assert_eq!(list.push_with_handle(2), Err(DSError::CapacityExceeded { capacity: 1 }));
+        }
+
+        #[test]
+        fn test_drop_count() {
+            let mut tracker = DropTracker::new();
+            let mut list = SinglyLinkedList::new();
+            let handles: Vec<_> = (0..5).map(|i| list.push_with_handle(tracker.track(i)).unwrap()).collect();
+
+            drop(list.remove_by_handle(handles[2]));
+            drop(list.replace_by_handle(handles[0], tracker.track(10)));
+            assert_eq!(tracker.dropped().count(), 2);
+            assert_eq!(tracker.alive().count(), 4);
+
+            drop(list);
+            assert_eq!(tracker.alive().count(), 0);
+        }
+    }
+
+    mod partition {
+        use super::*;
+        use drop_tracker::DropTracker;
+        use std::panic::{self, AssertUnwindSafe};
+
+        #[test]
+        fn test_partition() {
+            let (even, odd) = setup_list(7).partition(|x| x % 2 == 0);
+            assert_eq!(even.to_vec(), [0, 2, 4, 6]);
+            assert_eq!(odd.to_vec(), [1, 3, 5]);
+            assert_eq!(odd.last(), Some(&5));
+
+            let (all, none) = setup_list(3).partition(|_| true);
+            assert_eq!(all.to_vec(), [0, 1, 2]);
+            assert!(none.is_empty());
+
+            let (left, right) = SinglyLinkedList::<u8>::new().partition(|_| true);
+            assert!(left.is_empty() && right.is_empty());
+        }
+
+        #[test]
+        fn test_partition_keeps_bound_and_handles() {
+            let mut list = SinglyLinkedList::bounded(4, Overflow::Reject);
+            let handles: Vec<_> = (0..4).map(|i| list.push_with_handle(i).unwrap()).collect();
+            let (mut small, big) = list.partition(|x| *x < 2);
+            assert_eq!(small.capacity(), Some(4));
+            assert_eq!(small.get_by_handle(handles[1]), Ok(&1));
+            assert_eq!(big.get_by_handle(handles[3]), Ok(&3));
+            assert_eq!(small.get_by_handle(handles[3]), Err(DSError::InvalidHandle));
+            assert_eq!(small.remove_by_handle(handles[0]), Ok(0));
+            assert_eq!(small.check_invariants(), Ok(()));
+            assert_eq!(big.check_invariants(), Ok(()));
+        }
+
+        #[test]
+        fn test_partition_in_place() {
+            let mut list = setup_list(7);
+            assert_eq!(list.partition_in_place(|x| x % 3 == 0), 3);
+            assert_eq!(list.to_vec(), [0, 3, 6, 1, 2, 4, 5]);
+            assert_eq!(list.last(), Some(&5));
+            assert_eq!(list.get(4), Ok(&2));
+
+            assert_eq!(list.partition_in_place(|_| false), 0);
+            assert_eq!(list.to_vec(), [0, 3, 6, 1, 2, 4, 5], "order must be kept without matches");
+            assert_eq!(list.partition_in_place(|_| true), 7);
+            assert_eq!(list.to_vec(), [0, 3, 6, 1, 2, 4, 5], "order must be kept with all matches");
+
+            let mut empty = SinglyLinkedList::<u8>::new();
+            assert_eq!(empty.partition_in_place(|_| true), 0);
+        }
+
+        #[test]
+        fn test_partition_in_place_with_panicking_predicate() {
+            let mut tracker = DropTracker::new();
+            let mut list: SinglyLinkedList<_> = (0..10).map(|i| tracker.track(i)).collect();
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                list.partition_in_place(|x| {
+                    assert!(**x != 6, "predicate panics");
+                    **x % 2 == 0
+                })
+            }));
+            assert!(result.is_err());
+            assert_eq!(list.len(), 10, "list must keep all elements on panic");
+            assert_eq!(list.check_invariants(), Ok(()));
+            assert_eq!(tracker.alive().count(), 10);
+            drop(list);
+            assert_eq!(tracker.dropped().count(), 10);
+        }
+
+        #[test]
+        fn test_group_into() {
+            let groups = setup_list(10).group_into(|x| x % 3);
+            assert_eq!(groups.len(), 3);
+            assert_eq!(groups[&0].to_vec(), [0, 3, 6, 9]);
+            assert_eq!(groups[&1].to_vec(), [1, 4, 7]);
+            assert_eq!(groups[&2].to_vec(), [2, 5, 8]);
+            for group in groups.values() {
+                assert_eq!(group.check_invariants(), Ok(()));
+            }
+            assert!(SinglyLinkedList::<u8>::new().group_into(|x| *x).is_empty());
+        }
+
+        #[test]
+        fn test_group_into_keeps_handles() {
+            let mut list = SinglyLinkedList::new();
+            let handles: Vec<_> = ["ab", "b", "ac", "c"].into_iter().map(|s| list.push_with_handle(s).unwrap()).collect();
+            let groups = list.group_into(|s| s.len());
+            assert_eq!(groups[&2].get_by_handle(handles[2]), Ok(&"ac"));
+            assert_eq!(groups[&1].get_by_handle(handles[3]), Ok(&"c"));
+            assert_eq!(groups[&1].get_by_handle(handles[0]), Err(DSError::InvalidHandle));
+        }
+
+        #[test]
+        fn test_no_clones_or_drops() {
+            let mut tracker = DropTracker::new();
+            let list: SinglyLinkedList<_> = (0..20).map(|i| tracker.track(i)).collect();
+            let (small, big) = list.partition(|x| **x < 10);
+            let mut groups = big.group_into(|x| **x % 2);
+            groups.get_mut(&0).unwrap().partition_in_place(|x| **x > 15);
+            assert_eq!(tracker.alive().count(), 20, "elements must be moved, not dropped");
+            assert_eq!(groups[&0].iter().map(|x| **x).collect::<Vec<_>>(), [16, 18, 10, 12, 14]);
+
+            drop(small);
+            drop(groups);
+            assert_eq!(tracker.dropped().count(), 20);
+        }
+    }
+
+    mod invariants {
+        use super::*;
+
+        #[test]
+        fn test_invariants_after_mutations() {
+            let mut list = SinglyLinkedList::bounded(6, Overflow::EvictFront);
+            for i in 0..10 {
+                list.push(i);
+                list.push_front(i);
+            }
+            list.insert(3, 42).unwrap();
+            let _ = list.remove(5);
+            let _ = list.pop_back();
+            list.sort();
+            assert_eq!(list.check_invariants(), Ok(()));
+
+            list.clear();
+            assert_eq!(list.check_invariants(), Ok(()));
+        }
+
+        #[test]
+        fn test_invariants_detect_corruption() {
+            let mut list = setup_list(3);
+            list.state.size = 2;
+            assert_eq!(
+                list.check_invariants(),
+                Err(InvariantViolation::SizeMismatch { size: 2, actual: 3 })
+            );
+            list.state.size = 3;
+        }
+
+        #[test]
+        fn test_invariants_detect_stale_handle() {
+            let mut list = setup_list(3);
+            let node = list.state.head;
+            let handle = list.state.handles.register(node);
+            list.state.head = unsafe { (*node).next };
+            list.state.size = 2;
+            assert_eq!(list.check_invariants(), Err(InvariantViolation::StaleHandle));
+
+            list.state.handles.release(node);
+            drop(unsafe { Box::from_raw(node) });
+            assert_eq!(list.check_invariants(), Ok(()));
+            assert_eq!(list.get_by_handle(handle), Err(DSError::InvalidHandle));
+        }
+    }
 }
@@ -0,0 +1,304 @@
+//! This module contains sliding window implementation.
+
+use std::time::{Duration, Instant, SystemTime};
+
+use super::api::List;
+use super::sorted::SortedList;
+
+/// A time (or any other key) window over the elements kept in [`SortedList`].
+///
+/// The key of an element is given by the extractor `F`, it must grow with the sort order
+/// of the elements (e.g. the element is ordered by its timestamp). The window of `width`
+/// ending at `now` contains the elements with `now - width < key`, the older elements
+/// are expired: on every push relative to the key of the newest element, or explicitly
+/// with [`expire`](SlidingWindow::expire).
+///
+/// The expired elements are returned as a [`SortedList`] built by relinking of the nodes.
+///
+/// # Type Parameters
+/// * `T`: The type of elements stored in the window.
+/// * `W`: The type of the window width (the key type must implement [`CheckedSub<W>`]).
+/// * `F`: The key extractor.
+///
+/// # Examples
+/// ```
+/// use plain_ds::List;
+/// use plain_ds::list::SlidingWindow;
+///
+/// // Requests per 10 time units
+/// let mut window = SlidingWindow::new(10, |timestamp: &u64| *timestamp);
+/// window.push(100);
+/// window.push(104);
+/// let expired = window.push(112);
+///
+/// assert_eq!(expired.to_vec(), vec![100]);
+/// assert_eq!(window.len(), 2);
+/// assert_eq!(window.expire(114).len(), 1);
+/// ```
+pub struct SlidingWindow<T, W, F> {
+    list: SortedList<T>,
+    width: W,
+    key: F,
+}
+
+/// Subtraction of the window width from the key, which detects the underflow.
+///
+/// The start of the window is `now - width`, so the window ending close to the smallest key
+/// (e.g. `0` for the unsigned keys) has no start: nothing is expired from it.
+pub trait CheckedSub<W>: Sized {
+    /// Returns `self - width`, or `None` if it is not representable.
+    fn checked_sub(self, width: W) -> Option<Self>;
+}
+
+macro_rules! impl_checked_sub {
+    ($($key:ty),*) => {
+        $(impl CheckedSub<$key> for $key {
+            fn checked_sub(self, width: $key) -> Option<Self> {
+                <$key>::checked_sub(self, width)
+            }
+        })*
+    };
+}
+
+impl_checked_sub!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, Duration);
+
+impl CheckedSub<Duration> for Instant {
+    fn checked_sub(self, width: Duration) -> Option<Self> {
+        Instant::checked_sub(&self, width)
+    }
+}
+
+impl CheckedSub<Duration> for SystemTime {
+    fn checked_sub(self, width: Duration) -> Option<Self> {
+        SystemTime::checked_sub(&self, width)
+    }
+}
+
+impl CheckedSub<f32> for f32 {
+    fn checked_sub(self, width: f32) -> Option<Self> {
+        Some(self - width)
+    }
+}
+
+impl CheckedSub<f64> for f64 {
+    fn checked_sub(self, width: f64) -> Option<Self> {
+        Some(self - width)
+    }
+}
+
+impl<T, K, W, F> SlidingWindow<T, W, F>
+where
+    T: PartialOrd,
+    K: PartialOrd + CheckedSub<W>,
+    W: Copy,
+    F: Fn(&T) -> K,
+{
+    /// Creates empty window of `width` over the keys given by `key`.
+    pub fn new(width: W, key: F) -> Self {
+        Self {
+            list: SortedList::new(),
+            width,
+            key,
+        }
+    }
+
+    /// Returns the window width.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn width(&self) -> W {
+        self.width
+    }
+
+    /// Returns the number of elements in the window.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    /// Checks if the window is empty.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Returns the oldest element in the window.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn oldest(&self) -> Option<&T> {
+        self.list.head()
+    }
+
+    /// Returns the newest element in the window.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn newest(&self) -> Option<&T> {
+        self.list.last()
+    }
+
+    /// Returns an iterator over the elements of the window from the oldest to the newest.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.list.iter()
+    }
+
+    /// Returns the elements of the window as a sorted list.
+    pub fn as_list(&self) -> &SortedList<T> {
+        &self.list
+    }
+
+    /// Adds a new element and expires the elements which are out of the window
+    /// ending at the newest element. Returns the expired elements (the new one may be
+    /// among them, if it is older than the window).
+    ///
+    /// **Efficiency**: O(n) at worst, O(1 + k) for the element which is not older than
+    /// the others, where k is the number of expired elements
+    pub fn push(&mut self, payload: T) -> SortedList<T> {
+        self.list.push(payload);
+        match self.list.last() {
+            Some(newest) => {
+                let now = (self.key)(newest);
+                self.expire(now)
+            }
+            None => SortedList::new(),
+        }
+    }
+
+    /// Removes the elements which are out of the window ending at `now` and returns them.
+    /// Nothing is expired, if `now - width` underflows.
+    ///
+    /// **Efficiency**: O(k), where k is the number of expired elements
+    pub fn expire(&mut self, now: K) -> SortedList<T> {
+        match CheckedSub::checked_sub(now, self.width) {
+            Some(start) => {
+                let key = &self.key;
+                self.list.pop_while(|payload| key(payload) <= start)
+            }
+            None => SortedList::new(),
+        }
+    }
+
+    /// Removes all elements from the window.
+    ///
+    /// **Efficiency**: O(n)
+    pub fn clear(&mut self) {
+        self.list.clear();
+    }
+
+    /// Consumes the window and returns its elements as a sorted list.
+    pub fn into_list(self) -> SortedList<T> {
+        self.list
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_push_expires_old_elements() {
+        let mut window = SlidingWindow::new(10, |timestamp: &u32| *timestamp);
+        assert!(window.push(100).is_empty());
+        assert!(window.push(105).is_empty());
+        assert!(window.push(109).is_empty());
+        assert_eq!(window.len(), 3);
+
+        let expired = window.push(110);
+        assert_eq!(expired.to_vec(), [100], "element with key + width == now must be expired");
+        assert_eq!(window.oldest(), Some(&105));
+        assert_eq!(window.newest(), Some(&110));
+
+        let expired = window.push(200);
+        assert_eq!(expired.to_vec(), [105, 109, 110]);
+        assert_eq!(window.iter().copied().collect::<Vec<_>>(), [200]);
+    }
+
+    #[test]
+    fn test_late_element() {
+        let mut window = SlidingWindow::new(10, |timestamp: &u32| *timestamp);
+        window.push(100);
+        window.push(95);
+        assert_eq!(window.as_list().to_vec(), [95, 100], "late element inside the window is kept");
+
+        let expired = window.push(80);
+        assert_eq!(expired.to_vec(), [80], "element older than the window is expired at once");
+        assert_eq!(window.len(), 2);
+    }
+
+    #[test]
+    fn test_expire() {
+        let mut window = SlidingWindow::new(5, |timestamp: &u32| *timestamp);
+        for timestamp in [1, 2, 3, 4, 5] {
+            window.push(timestamp);
+        }
+        assert!(window.expire(5).is_empty());
+        assert_eq!(window.expire(7).to_vec(), [1, 2]);
+        assert_eq!(window.expire(100).to_vec(), [3, 4, 5]);
+        assert!(window.is_empty());
+        assert!(window.expire(200).is_empty(), "expire() of empty window");
+        assert!(window.push(1).is_empty(), "window must be usable after expiry");
+    }
+
+    #[test]
+    fn test_extreme_keys() {
+        let mut window = SlidingWindow::new(10, |timestamp: &u64| *timestamp);
+        assert!(window.push(u64::MAX - 5).is_empty(), "key + width must not overflow");
+        assert!(window.push(u64::MAX).is_empty());
+        assert_eq!(window.expire(u64::MAX).len(), 0);
+        assert_eq!(window.len(), 2);
+
+        let mut window = SlidingWindow::new(10, |timestamp: &u64| *timestamp);
+        window.push(0);
+        window.push(3);
+        assert!(window.expire(9).is_empty(), "the window without start expires nothing");
+        assert_eq!(window.expire(10).to_vec(), [0]);
+    }
+
+    #[test]
+    fn test_key_extractor() {
+        #[derive(Debug, PartialEq, PartialOrd)]
+        struct Request {
+            at: Instant,
+            client: u32,
+        }
+
+        let start = Instant::now();
+        let request = |secs, client| Request {
+            at: start + Duration::from_secs(secs),
+            client,
+        };
+
+        let mut window = SlidingWindow::new(Duration::from_secs(60), |request: &Request| request.at);
+        assert_eq!(window.width(), Duration::from_secs(60));
+        window.push(request(0, 1));
+        window.push(request(30, 2));
+        let expired = window.push(request(61, 3));
+        assert_eq!(expired.iter().map(|request| request.client).collect::<Vec<_>>(), [1]);
+        assert_eq!(window.iter().map(|request| request.client).collect::<Vec<_>>(), [2, 3]);
+
+        let rest = window.into_list();
+        assert_eq!(rest.len(), 2);
+    }
+
+    #[test]
+    fn test_memory_leaks() {
+        use drop_tracker::DropTracker;
+
+        let mut tracker = DropTracker::new();
+
+        let mut window = SlidingWindow::new(3, |item: &drop_tracker::DropItem<i32>| **item);
+        let mut expired = Vec::new();
+        for timestamp in 0..10 {
+            expired.push(window.push(tracker.track(timestamp)));
+        }
+        assert_eq!(window.len(), 3);
+        assert_eq!(tracker.alive().count(), 10, "expired elements are owned by the caller");
+
+        drop(expired);
+        assert_eq!(tracker.alive().count(), 3);
+        window.clear();
+        assert_eq!(tracker.alive().count(), 0);
+        assert_eq!(tracker.dropped().count(), 10);
+    }
+}
@@ -1,18 +1,25 @@
 use super::api::List;
+use super::bounded::{Bound, Overflow};
 use super::common::ListCommon;
+use super::handle::ListHandle;
 use super::node_one_link::Node;
+use super::span_index::SpanIndex;
+use crate::core::{DSError, InvariantViolation, Result, debug_check_invariants};
+use std::cmp::Ordering;
+use std::ptr;
 
 /// An ordered collection that maintains its elements in sorted order.
 ///
 /// The `SortedList` automatically keeps elements sorted upon insertion,
-/// ensuring efficient search operations.
+/// ensuring efficient search operations. Insertion is stable: equal elements keep
+/// their insertion order, unless [`EqualOrder::Lifo`] is selected.
 ///
 /// # Type Parameters
 /// * `T`: The type of elements stored in the list. Must implement `PartialOrd`.
 ///
 /// # Examples
 /// ```
-/// use plain_ds::SortedList;
+/// use plain_ds::{List, SortedList};
 ///
 /// let mut list = SortedList::new();
 /// list.push(3);
@@ -22,8 +29,50 @@ use super::node_one_link::Node;
 /// assert_eq!(list.len(), 3);
 /// assert_eq!(list.to_vec(), vec![1, 2, 3]);
 /// ```
+///
+/// Use [`update`](SortedList::update) and [`update_where`](SortedList::update_where)
+/// to change elements: they move the changed nodes to their new positions. `get_mut` and
+/// `iter_mut` from the [`List`] trait give raw mutable access and may break the sort order;
+/// call [`resort`](SortedList::resort) after using them.
+///
+/// Positional access is O(n). The list created with [`with_index`](SortedList::with_index)
+/// maintains a span index (an indexable skip list over the nodes), which makes
+/// [`select`](SortedList::select), [`rank`](SortedList::rank), `get`, `push` and `remove`
+/// O(log n) expected.
 pub struct SortedList<T> {
     state: ListCommon<T>,
+    bound: Option<Bound>,
+    equal_order: EqualOrder,
+    index: Option<SpanIndex<T>>,
+}
+
+/// Defines the order of equal elements in [`SortedList`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EqualOrder {
+    /// Equal elements keep the insertion order: a new element goes after the equal ones.
+    #[default]
+    Fifo,
+    /// Equal elements are in reverse insertion order: a new element goes before the equal ones.
+    Lifo,
+}
+
+impl EqualOrder {
+    // Checks if the new element must be placed before the existing one.
+    fn goes_before<T: PartialOrd>(self, new: &T, existing: &T) -> bool {
+        match self {
+            EqualOrder::Fifo => new < existing,
+            EqualOrder::Lifo => new <= existing,
+        }
+    }
+}
+
+/// Defines which elements are kept by the list created with [`SortedList::with_limit`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Keep {
+    /// The smallest elements are kept, the largest one is evicted on overflow.
+    Smallest,
+    /// The largest elements are kept, the smallest one is evicted on overflow.
+    Largest,
 }
 
 impl<T> SortedList<T> {
@@ -31,20 +80,447 @@ impl<T> SortedList<T> {
     pub fn new() -> Self {
         Self {
             state: ListCommon::new(),
+            bound: None,
+            equal_order: EqualOrder::Fifo,
+            index: None,
         }
     }
 
-    /// Creates list from slice.
+    /// Creates empty ordered list which never grows beyond `capacity` elements.
+    /// The `policy` defines what happens when a new element is added to the full list:
+    /// [`Overflow::EvictFront`] evicts the smallest element, [`Overflow::EvictBack`] - the largest.
+    pub fn bounded(capacity: usize, policy: Overflow) -> Self {
+        Self {
+            state: ListCommon::new(),
+            bound: Some(Bound { capacity, policy }),
+            equal_order: EqualOrder::Fifo,
+            index: None,
+        }
+    }
+
+    /// Creates empty ordered list which keeps only `k` best elements ("top-K"):
+    /// the smallest or the largest ones, depending on `keep`.
+    ///
+    /// When the list is full, a candidate which is not strictly better than the
+    /// [`threshold`](SortedList::threshold) (including the one equal to it) is rejected
+    /// without allocation, otherwise it reuses the node of the evicted element.
+    ///
+    /// # Examples
+    /// ```
+    /// use plain_ds::List;
+    /// use plain_ds::list::{Keep, SortedList};
+    ///
+    /// let mut top = SortedList::with_limit(3, Keep::Largest);
+    /// for score in [5, 1, 9, 3, 7, 2] {
+    ///     top.push(score);
+    /// }
+    /// assert_eq!(top.threshold(), Some(&5));
+    /// assert_eq!(top.into_sorted_vec(), vec![5, 7, 9]);
+    /// ```
+    pub fn with_limit(k: usize, keep: Keep) -> Self {
+        let policy = match keep {
+            Keep::Smallest => Overflow::EvictBack,
+            Keep::Largest => Overflow::EvictFront,
+        };
+        Self::bounded(k, policy)
+    }
+
+    /// Returns the worst element kept by the full list with eviction policy: a new element
+    /// must be better than it to get into the list. Returns `None`, if any new element
+    /// is accepted or the list rejects everything on overflow.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn threshold(&self) -> Option<&T> {
+        if !self.is_full() {
+            return None;
+        }
+        let node = match self.bound?.policy {
+            Overflow::EvictFront => self.state.head,
+            Overflow::EvictBack => self.state.last,
+            Overflow::Reject => return None,
+        };
+        unsafe { node.as_ref() }.map(|node| &node.payload)
+    }
+
+    /// Consumes the list and returns its elements in ascending order.
+    ///
+    /// **Efficiency**: O(n)
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        let mut vec = Vec::with_capacity(self.state.len());
+        vec.extend(self.state.into_iter());
+        vec
+    }
+
+    /// Sets the order of equal elements for the subsequent insertions
+    /// ([`EqualOrder::Fifo`] by default).
+    ///
+    /// # Examples
+    /// ```
+    /// use plain_ds::list::{EqualOrder, Overflow, SortedList};
+    ///
+    /// let list: SortedList<u8> = SortedList::bounded(8, Overflow::EvictFront).with_equal_order(EqualOrder::Lifo);
+    /// assert_eq!(list.equal_order(), EqualOrder::Lifo);
+    /// ```
+    pub fn with_equal_order(mut self, order: EqualOrder) -> Self {
+        self.equal_order = order;
+        self
+    }
+
+    /// Turns on the indexed mode: the list maintains span counts of its nodes, so
+    /// [`select`](SortedList::select), [`rank`](SortedList::rank) and positional access
+    /// are O(log n) expected. It costs extra memory: a quarter of the elements get a tower
+    /// of express links, which takes two heap allocations (a box of 32 bytes and a vector
+    /// of 16-byte links, 4/3 links per tower on average). That is about 13 bytes and half
+    /// an allocation per element on average on 64-bit targets, plus the allocator overhead.
+    /// It also makes `push` and `pop_front` O(log n) expected.
+    ///
+    /// **Efficiency**: O(n)
+    ///
+    /// # Examples
+    /// ```
+    /// use plain_ds::{List, SortedList};
+    ///
+    /// let mut latencies = SortedList::new().with_index();
+    /// latencies.extend([30, 10, 20, 50, 40]);
+    /// assert_eq!(latencies.select(3), Ok(&40));
+    /// assert_eq!(latencies.rank(&35), 3);
+    /// ```
+    pub fn with_index(mut self) -> Self {
+        self.index = Some(SpanIndex::build(&self.state));
+        self
+    }
+
+    /// Checks if the list maintains the span index.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn is_indexed(&self) -> bool {
+        self.index.is_some()
+    }
+
+    /// Returns the order of equal elements.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn equal_order(&self) -> EqualOrder {
+        self.equal_order
+    }
+
+    /// Returns the maximum number of elements, or `None` if the list is unbounded.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn capacity(&self) -> Option<usize> {
+        self.bound.map(|bound| bound.capacity)
+    }
+
+    /// Checks if the list is bounded and contains the maximum number of elements.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn is_full(&self) -> bool {
+        self.capacity()
+            .is_some_and(|capacity| self.state.len() >= capacity)
+    }
+
+    /// Adds a new node to the list according to the sort order and the overflow policy.
+    /// Returns the evicted element (it may be the new one), if any.
+    /// Error returns, if the list is full and the policy is [`Overflow::Reject`].
+    ///
+    /// When the full list evicts elements, the new element which would be evicted itself
+    /// is returned back without allocation, otherwise the node of the evicted element is reused.
+    ///
+    /// **Efficiency**: O(n) at worst
+    pub fn try_push(&mut self, payload: T) -> Result<Option<T>>
+    where
+        T: PartialOrd,
+    {
+        let (evicted, _) = self.push_node(payload)?;
+        debug_check_invariants!(self);
+        Ok(evicted)
+    }
+
+    /// Adds a new node to the list like [`try_push`](Self::try_push) and returns the handle
+    /// of the new element, which stays valid until the element is removed.
+    /// If the new element is evicted itself, the returned handle is already invalid.
+    /// Error returns, if the list is full and the policy is [`Overflow::Reject`].
+    ///
+    /// **Efficiency**: O(n) at worst
+    ///
+    /// # Examples
+    /// ```
+    /// use plain_ds::{List, SortedList};
+    ///
+    /// let mut list = SortedList::from_vec(vec![10, 30]);
+    /// let handle = list.push_with_handle(20).unwrap();
+    /// list.push(5);
+    ///
+    /// assert_eq!(list.get_by_handle(handle), Ok(&20));
+    /// assert_eq!(list.replace_by_handle(handle, 40), Ok(20));
+    /// assert_eq!(list.to_vec(), vec![5, 10, 30, 40]);
+    /// assert_eq!(list.remove_by_handle(handle), Ok(40));
+    /// assert!(list.get_by_handle(handle).is_err());
+    /// ```
+    pub fn push_with_handle(&mut self, payload: T) -> Result<ListHandle>
+    where
+        T: PartialOrd,
+    {
+        let (_, node) = self.push_node(payload)?;
+        let handle = if node.is_null() {
+            ListHandle::detached()
+        } else {
+            self.state.handles.register(node)
+        };
+        debug_check_invariants!(self);
+        Ok(handle)
+    }
+
+    /// Returns the element of the handle.
+    /// Error returns, if the element was removed from the list.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn get_by_handle(&self, handle: ListHandle) -> Result<&T> {
+        let node = self.state.handles.node(handle)?;
+        Ok(unsafe { &(*node).payload })
+    }
+
+    /// Removes the element of the handle and returns it.
+    /// Error returns, if the element was removed from the list.
+    ///
+    /// The next element takes the place of the removed one, so the last element,
+    /// which has no next one, needs the search of its predecessor from the head.
+    /// The indexed mode searches the position of the element with the index.
+    ///
+    /// **Efficiency**: O(1), or O(n) for the last element; O(log n + k) expected
+    /// in the indexed mode, where k is the number of elements equal to the removed one
+    pub fn remove_by_handle(&mut self, handle: ListHandle) -> Result<T>
+    where
+        T: PartialOrd,
+    {
+        let node = self.state.handles.node(handle)?;
+        let payload = match self.index {
+            // The span index refers to the nodes, so the payloads must stay in their nodes
+            Some(_) => self.take_at(self.position_of(node)),
+            None => self.state.remove_node(node),
+        };
+        debug_check_invariants!(self);
+        Ok(payload)
+    }
+
+    /// Replaces the element of the handle, moves it to the position corresponding
+    /// to the new value and returns the old one. The handle stays valid.
+    /// Error returns, if the element was removed from the list.
+    ///
+    /// **Efficiency**: O(n) at worst; O(log n + k) expected in the indexed mode,
+    /// where k is the number of elements equal to the replaced one
+    pub fn replace_by_handle(&mut self, handle: ListHandle, mut payload: T) -> Result<T>
+    where
+        T: PartialOrd,
+    {
+        let node = self.state.handles.node(handle)?;
+        self.update(self.position_of(node), |value| std::mem::swap(value, &mut payload))?;
+        Ok(payload)
+    }
+
+    /// Changes the element at `index` with `f` and moves its node to the position
+    /// corresponding to the new value. The handle of the element stays valid.
+    /// Error returns, if the index out of bounds.
+    ///
+    /// **Efficiency**: O(n) at worst
+    pub fn update(&mut self, index: usize, f: impl FnOnce(&mut T)) -> Result<()>
+    where
+        T: PartialOrd,
+    {
+        self.check_index(index)?;
+        let node = if let Some(span_index) = &mut self.index {
+            unsafe { f(&mut (*span_index.node_at(&self.state, index)).payload) };
+            span_index.remove(&mut self.state, index)
+        } else {
+            let prev = if index == 0 {
+                ptr::null_mut()
+            } else {
+                self.state.node_at(index - 1)
+            };
+            unsafe { f(&mut (*self.state.node_after(prev)).payload) };
+            self.unlink_after(prev)
+        };
+        // The handle is lost, if a comparison panics and the node is freed
+        let id = self.state.handles.release(node);
+        self.link_sorted(node);
+        self.state.handles.restore(node, id);
+        debug_check_invariants!(self);
+        Ok(())
+    }
+
+    /// Changes every element which satisfies the predicate with `f` and moves the changed
+    /// nodes to the positions corresponding to their new values.
+    /// Returns the number of changed elements.
+    ///
+    /// **Efficiency**: O(n * m), where m is the number of changed elements
+    pub fn update_where(&mut self, predicate: impl Fn(&T) -> bool, f: impl FnMut(&mut T)) -> usize
+    where
+        T: PartialOrd,
+    {
+        let count = DetachedIndex::new(self).list.relink_where(predicate, f);
+        debug_check_invariants!(self);
+        count
+    }
+
+    // Changes every element which satisfies the predicate and relinks its node.
+    // The list must not have the index.
+    fn relink_where(&mut self, predicate: impl Fn(&T) -> bool, mut f: impl FnMut(&mut T)) -> usize
+    where
+        T: PartialOrd,
+    {
+        // The changed nodes are kept in a separate chain, so they are freed on panic in `f`
+        let mut changed = ListCommon::new();
+        let mut prev = ptr::null_mut();
+        let mut current = self.state.head;
+        while !current.is_null() {
+            unsafe {
+                if predicate(&(*current).payload) {
+                    f(&mut (*current).payload);
+                    let node = self.unlink_after(prev);
+                    self.state.handles.transfer(node, &mut changed.handles);
+                    if changed.last.is_null() {
+                        changed.head = node;
+                    } else {
+                        (*changed.last).next = node;
+                    }
+                    changed.last = node;
+                    changed.size += 1;
+                } else {
+                    prev = current;
+                }
+            }
+            current = self.state.node_after(prev);
+        }
+
+        let count = changed.len();
+        while !changed.head.is_null() {
+            let node = changed.head;
+            changed.head = unsafe { (*node).next };
+            changed.size -= 1;
+            let id = changed.handles.release(node);
+            self.link_sorted(node);
+            self.state.handles.restore(node, id);
+        }
+        changed.last = ptr::null_mut();
+        count
+    }
+
+    /// Restores the sort order after the elements were changed with `get_mut` or `iter_mut`.
+    ///
+    /// **Efficiency**: O(n log n)
+    pub fn resort(&mut self)
+    where
+        T: PartialOrd,
+    {
+        DetachedIndex::new(self).list.state.sort();
+        debug_check_invariants!(self);
+    }
+
+    /// Checks the internal structure of the list: `size` is equal to the number of nodes,
+    /// `last` is the tail node, the node chain has no cycles and the elements are sorted.
+    /// With the `check-invariants` feature, it runs after every mutation in debug builds.
     ///
     /// **Efficiency**: O(n)
+    pub fn check_invariants(&self) -> std::result::Result<(), InvariantViolation>
+    where
+        T: PartialOrd,
+    {
+        self.state.check_invariants()?;
+        self.state.check_sorted()?;
+        match &self.index {
+            Some(span_index) => span_index.check_invariants(&self.state),
+            None => Ok(()),
+        }
+    }
+
+    /// Creates list from slice.
+    ///
+    /// **Efficiency**: O(n log n)
     pub fn from_slice(slice: &[T]) -> Self
     where
         T: Clone + PartialOrd,
     {
-        let mut list = SortedList::new();
-        for value in slice.iter() {
-            list.push((*value).clone());
+        slice.iter().cloned().collect()
+    }
+
+    /// Creates list from vector, moving its elements.
+    ///
+    /// **Efficiency**: O(n log n)
+    pub fn from_vec(vec: Vec<T>) -> Self
+    where
+        T: PartialOrd,
+    {
+        IntoIterator::into_iter(vec).collect()
+    }
+
+    /// Creates list from the iterator whose elements are already in ascending order,
+    /// without sorting them.
+    /// The caller must ensure the order: otherwise the list will not be sorted
+    /// (this is checked only in debug builds).
+    ///
+    /// **Efficiency**: O(n)
+    pub fn from_sorted_iter_unchecked(iter: impl IntoIterator<Item = T>) -> Self
+    where
+        T: PartialOrd,
+    {
+        let mut state = ListCommon::new();
+        for payload in iter {
+            debug_assert!(
+                state.last().is_none_or(|last| *last <= payload),
+                "from_sorted_iter_unchecked(): elements are not sorted"
+            );
+            state.push_back(payload);
+        }
+        Self {
+            state,
+            bound: None,
+            equal_order: EqualOrder::Fifo,
+            index: None,
+        }
+    }
+
+    /// Merges all lists into one by relinking of their nodes (no reallocation).
+    /// The merge is stable: equal elements go in the order of the lists,
+    /// and keep their order within every list.
+    ///
+    /// The result is unbounded, not indexed and has [`EqualOrder::Fifo`].
+    ///
+    /// **Efficiency**: O(n log k), where k is the number of lists
+    ///
+    /// # Examples
+    /// ```
+    /// use plain_ds::SortedList;
+    ///
+    /// let shards = vec![
+    ///     SortedList::from_vec(vec![1, 4, 7]),
+    ///     SortedList::from_vec(vec![2, 5]),
+    ///     SortedList::from_vec(vec![3, 6]),
+    /// ];
+    /// assert_eq!(SortedList::merge_all(shards).to_vec(), vec![1, 2, 3, 4, 5, 6, 7]);
+    /// ```
+    pub fn merge_all(lists: impl IntoIterator<Item = SortedList<T>>) -> Self
+    where
+        T: PartialOrd,
+    {
+        let mut chains: Vec<ListCommon<T>> = lists.into_iter().map(|list| list.state).collect();
+        // Adjacent lists are merged pairwise, so every element is relinked O(log k) times
+        while chains.len() > 1 {
+            let mut merged = Vec::with_capacity(chains.len().div_ceil(2));
+            let mut iter = IntoIterator::into_iter(chains);
+            while let Some(mut left) = iter.next() {
+                if let Some(right) = iter.next() {
+                    left.merge_sorted(right);
+                }
+                merged.push(left);
+            }
+            chains = merged;
+        }
+
+        let mut list = Self::new();
+        if let Some(state) = chains.pop() {
+            list.state = state;
         }
+        debug_check_invariants!(list);
         list
     }
 
@@ -69,118 +545,657 @@ impl<T> SortedList<T> {
         self.state.find_if(predicate)
     }
 
-    // Helper for insertion into the middle (used in push())
-    fn insert_in_middle(&mut self, ptr: *mut Node<T>)
+    /// Removes the leading elements which satisfy the predicate and returns them
+    /// as a new list (the nodes are relinked, not reallocated). The predicate must be true
+    /// for a prefix of the list, e.g. `|x| x < bound`.
+    ///
+    /// The returned list is unbounded, not indexed and has the same order of equal elements.
+    ///
+    /// **Efficiency**: O(k), O(k log n) expected in the indexed mode,
+    /// where k is the number of removed elements
+    pub fn pop_while(&mut self, predicate: impl Fn(&T) -> bool) -> SortedList<T>
     where
         T: PartialOrd,
     {
-        let mut prev = self.state.head;
-        unsafe {
-            let mut next = (*prev).next;
-
-            while !next.is_null() {
-                if (*ptr).payload < (*next).payload {
-                    (*prev).next = ptr;
-                    (*ptr).next = next;
-                    return;
+        let count = self.state.iter().take_while(|payload| predicate(payload)).count();
+        let front = match &mut self.index {
+            Some(span_index) => {
+                let mut front = ListCommon::new();
+                for _ in 0..count {
+                    let node = span_index.remove(&mut self.state, 0);
+                    self.state.handles.transfer(node, &mut front.handles);
+                    if front.last.is_null() {
+                        front.head = node;
+                    } else {
+                        unsafe { (*front.last).next = node };
+                    }
+                    front.last = node;
+                    front.size += 1;
                 }
-                prev = next;
-                next = (*next).next;
+                front
             }
+            None => self.state.split_front(count),
+        };
+        debug_check_invariants!(self);
+        Self {
+            state: front,
+            bound: None,
+            equal_order: self.equal_order,
+            index: None,
         }
     }
-}
 
-impl<'a, T: 'a> List<'a, T> for SortedList<T>
-where
-    T: PartialOrd,
-{
-    /// Returns list size.
+    /// Removes all elements which are less than `bound` and returns them
+    /// as a new list (the nodes are relinked, not reallocated).
     ///
-    /// **Efficiency**: O(1)
-    fn len(&self) -> usize {
-        self.state.len()
+    /// **Efficiency**: O(k), O(k log n) expected in the indexed mode,
+    /// where k is the number of removed elements
+    ///
+    /// # Examples
+    /// ```
+    /// use plain_ds::{List, SortedList};
+    ///
+    /// let mut timestamps = SortedList::from_vec(vec![105, 100, 120, 110]);
+    /// let expired = timestamps.expire_before(&110);
+    /// assert_eq!(expired.to_vec(), vec![100, 105]);
+    /// assert_eq!(timestamps.to_vec(), vec![110, 120]);
+    /// ```
+    pub fn expire_before(&mut self, bound: &T) -> SortedList<T>
+    where
+        T: PartialOrd,
+    {
+        self.pop_while(|payload| payload < bound)
     }
 
-    /// Returns the payload value of the first node in the list.
+    /// Returns the number of elements which are less than `value`
+    /// (the index where `value` would be inserted before the equal elements).
     ///
-    /// **Efficiency**: O(1)
-    fn head(&self) -> Option<&T> {
-        self.state.head()
+    /// **Efficiency**: O(n), O(log n) expected in the indexed mode
+    pub fn rank(&self, value: &T) -> usize
+    where
+        T: PartialOrd,
+    {
+        match &self.index {
+            Some(span_index) => span_index.count_while(&self.state, |payload| payload < value),
+            None => self.state.iter().take_while(|payload| *payload < value).count(),
+        }
     }
 
-    /// Returns the payload value of the last node in the list.
+    /// Returns the `k`-th smallest element (counting from zero), or error if `k` out of bounds.
     ///
-    /// **Efficiency**: O(1)
-    fn last(&self) -> Option<&T> {
-        self.state.last()
+    /// **Efficiency**: O(n), O(log n) expected in the indexed mode
+    pub fn select(&self, k: usize) -> Result<&T> {
+        self.check_index(k)?;
+        Ok(unsafe { &(*self.node_at(k)).payload })
     }
 
-    /// Returns an iterator over the immutable items of the list.
-    fn iter(&self) -> impl Iterator<Item = &'a T> {
-        self.state.iter()
+    /// Returns the median element (the lower one for even length),
+    /// or `None` if the list is empty.
+    ///
+    /// **Efficiency**: O(n), O(log n) expected in the indexed mode
+    pub fn median(&self) -> Option<&T> {
+        self.quantile(0.5)
     }
 
-    /// Returns an iterator over the mutable items of the list.
-    fn iter_mut(&mut self) -> impl Iterator<Item = &'a mut T> {
-        self.state.iter_mut()
+    /// Returns the `q`-quantile by the nearest-rank method: the smallest element
+    /// which is not less than `q * len` elements (including itself).
+    /// Returns `None` if the list is empty or `q` is not in the range `0.0..=1.0`.
+    ///
+    /// **Efficiency**: O(n), O(log n) expected in the indexed mode
+    ///
+    /// # Examples
+    /// ```
+    /// use plain_ds::SortedList;
+    ///
+    /// let latencies = SortedList::from_vec((1..=100).collect());
+    /// assert_eq!(latencies.quantile(0.95), Some(&95));
+    /// assert_eq!(latencies.quantile(0.0), Some(&1));
+    /// assert_eq!(latencies.quantile(1.5), None);
+    /// ```
+    pub fn quantile(&self, q: f64) -> Option<&T> {
+        self.quantile_index(q).map(|k| unsafe { &(*self.node_at(k)).payload })
     }
 
-    /// Returns an iterator that consumes the list.
-    fn into_iter(self) -> impl Iterator<Item = T> {
-        self.state.into_iter()
+    /// Returns the quantiles for every value of `qs` (see [`quantile`](SortedList::quantile)).
+    /// The values of `qs` may go in any order. Without index, the list is walked only once.
+    ///
+    /// **Efficiency**: O(n + m log m), O(m log n) expected in the indexed mode,
+    /// where m is the length of `qs`
+    ///
+    /// # Examples
+    /// ```
+    /// use plain_ds::SortedList;
+    ///
+    /// let latencies = SortedList::from_vec((1..=100).rev().collect());
+    /// assert_eq!(latencies.percentiles(&[0.99, 0.5, 2.0]), vec![Some(&99), Some(&50), None]);
+    /// ```
+    pub fn percentiles(&self, qs: &[f64]) -> Vec<Option<&T>> {
+        let mut result = vec![None; qs.len()];
+        if let Some(span_index) = &self.index {
+            for (slot, q) in result.iter_mut().zip(qs) {
+                *slot = self
+                    .quantile_index(*q)
+                    .map(|k| unsafe { &(*span_index.node_at(&self.state, k)).payload });
+            }
+            return result;
+        }
+
+        // Pairs of the element index and the result index, in the order of the list
+        let mut targets: Vec<(usize, usize)> = qs
+            .iter()
+            .enumerate()
+            .filter_map(|(slot, q)| self.quantile_index(*q).map(|k| (k, slot)))
+            .collect();
+        targets.sort_unstable();
+
+        let mut iter = self.state.iter();
+        let mut current = iter.next();
+        let mut position = 0;
+        for (k, slot) in targets {
+            while position < k {
+                current = iter.next();
+                position += 1;
+            }
+            result[slot] = current;
+        }
+        result
     }
 
-    /// Adds a new node to the list according to the sort order.
-    ///
-    /// **Efficiency**: O(n) at worst
-    fn push(&mut self, payload: T) {
-        let ptr = Box::into_raw(Box::new(Node::new(payload)));
+    // Adds a new node according to the sort order and the overflow policy.
+    // Returns the evicted element (if any) and the node of the new element,
+    // which is null if the new element is evicted itself.
+    fn push_node(&mut self, payload: T) -> Result<(Option<T>, *mut Node<T>)>
+    where
+        T: PartialOrd,
+    {
+        Bound::check(self.bound, self.state.len())?;
+        if let Some(bound) = self.bound
+            && self.state.len() >= bound.capacity
+        {
+            // The new element of the list with zero capacity is evicted itself
+            if self.state.len() == 0 {
+                return Ok((Some(payload), ptr::null_mut()));
+            }
+            let (evicted, node) = match bound.policy {
+                Overflow::EvictFront => self.replace_front(payload),
+                Overflow::EvictBack => self.replace_back(payload),
+                Overflow::Reject => unreachable!("checked by Bound::check()"),
+            };
+            return Ok((Some(evicted), node));
+        }
+        Ok((None, self.insert_sorted(payload)))
+    }
+
+    // Adds a new node according to the sort order ignoring the capacity limit
+    // and returns it.
+    fn insert_sorted(&mut self, payload: T) -> *mut Node<T>
+    where
+        T: PartialOrd,
+    {
+        let node = Box::into_raw(Box::new(Node::new(payload)));
+        self.link_sorted(node);
+        node
+    }
+
+    // Links the node according to the sort order.
+    fn link_sorted(&mut self, ptr: *mut Node<T>)
+    where
+        T: PartialOrd,
+    {
+        unsafe { (*ptr).next = ptr::null_mut() };
+        // The comparisons go before relinking, so the node is freed if one of them panics
+        let guard = DetachedNode(ptr);
+
+        let order = self.equal_order;
+        if let Some(span_index) = &mut self.index {
+            span_index.insert(&mut self.state, ptr, |new, existing| order.goes_before(new, existing));
+            std::mem::forget(guard);
+            return;
+        }
 
-        if self.is_empty() {
+        if self.state.len() == 0 {
             self.state.head = ptr;
             self.state.last = ptr;
         } else {
             unsafe {
                 // Quick Case: Insert at the Beginning
-                if (*ptr).payload <= (*self.state.head).payload {
+                if self.goes_before(&(*ptr).payload, &(*self.state.head).payload) {
                     (*ptr).next = self.state.head;
                     self.state.head = ptr;
+                    self.state.shift_finger();
                 }
                 // Quick Case: Insert at the End
-                else if (*self.state.last).payload <= (*ptr).payload {
+                else if !self.goes_before(&(*ptr).payload, &(*self.state.last).payload) {
                     (*self.state.last).next = ptr;
                     self.state.last = ptr;
                 }
                 // General case: searching for a position in the middle
                 else {
                     self.insert_in_middle(ptr);
+                    self.state.reset_finger();
                 }
             }
         }
+        std::mem::forget(guard);
         self.state.size += 1;
     }
 
-    /// Removes a node from the end of the list and returns its payload value.
-    ///
-    /// **Efficiency**: O(n)
-    fn pop_back(&mut self) -> Option<T> {
-        self.state.pop_back()
+    // Puts the new element into the non-empty list instead of the smallest one.
+    // Returns the evicted element and the node of the new one (null, if it is evicted itself).
+    // The element equal to the smallest one is evicted itself, whatever the equal order is.
+    fn replace_front(&mut self, payload: T) -> (T, *mut Node<T>)
+    where
+        T: PartialOrd,
+    {
+        if unsafe { &(*self.state.head).payload }.partial_cmp(&payload) != Some(Ordering::Less) {
+            return (payload, ptr::null_mut());
+        }
+        let node = self.unlink_at(0);
+        self.state.handles.release(node);
+        let evicted = unsafe { std::mem::replace(&mut (*node).payload, payload) };
+        self.link_sorted(node);
+        (evicted, node)
     }
 
-    /// Removes a node from the front of the list and returns its payload value.
-    ///
-    /// **Efficiency**: O(1)
-    fn pop_front(&mut self) -> Option<T> {
-        self.state.pop_front()
-    }
+    // Puts the new element into the non-empty list instead of the largest one.
+    // Returns the evicted element and the node of the new one (null, if it is evicted itself).
+    // The element equal to the largest one is evicted itself, whatever the equal order is.
+    // One scan finds both the position of the new element
+    // and the predecessor of the last node, so the last node is unlinked in O(1).
+    fn replace_back(&mut self, payload: T) -> (T, *mut Node<T>)
+    where
+        T: PartialOrd,
+    {
+        let last = self.state.last;
+        if payload.partial_cmp(unsafe { &(*last).payload }) != Some(Ordering::Less) {
+            return (payload, ptr::null_mut());
+        }
+        self.state.handles.release(last);
+        if self.index.is_some() {
+            let node = self.unlink_at(self.state.len() - 1);
+            let evicted = unsafe { std::mem::replace(&mut (*node).payload, payload) };
+            self.link_sorted(node);
+            return (evicted, node);
+        }
+
+        let mut position = ptr::null_mut();
+        let mut before_last = ptr::null_mut();
+        let mut current = self.state.head;
+        unsafe {
+            while current != last {
+                if !self.goes_before(&payload, &(*current).payload) {
+                    position = current;
+                }
+                before_last = current;
+                current = (*current).next;
+            }
+
+            // Unlink the last node
+            if before_last.is_null() {
+                self.state.head = ptr::null_mut();
+            } else {
+                (*before_last).next = ptr::null_mut();
+            }
+            self.state.last = before_last;
+
+            // Relink it after `position` with the new payload
+            let evicted = std::mem::replace(&mut (*last).payload, payload);
+            if position.is_null() {
+                (*last).next = self.state.head;
+                self.state.head = last;
+            } else {
+                (*last).next = (*position).next;
+                (*position).next = last;
+            }
+            if (*last).next.is_null() {
+                self.state.last = last;
+            }
+            self.state.reset_finger();
+            (evicted, last)
+        }
+    }
+
+    // Checks if the new element must be placed before the existing one.
+    fn goes_before(&self, new: &T, existing: &T) -> bool
+    where
+        T: PartialOrd,
+    {
+        self.equal_order.goes_before(new, existing)
+    }
+
+    // Unlinks the node with valid index and returns it.
+    fn unlink_at(&mut self, index: usize) -> *mut Node<T> {
+        match &mut self.index {
+            Some(span_index) => span_index.remove(&mut self.state, index),
+            None => {
+                let prev = if index == 0 {
+                    ptr::null_mut()
+                } else {
+                    self.state.node_at(index - 1)
+                };
+                self.unlink_after(prev)
+            }
+        }
+    }
+
+    // Returns the node with valid index.
+    fn node_at(&self, index: usize) -> *mut Node<T> {
+        match &self.index {
+            Some(span_index) => span_index.node_at(&self.state, index),
+            None => self.state.node_at(index),
+        }
+    }
+
+    // Returns the index of the `q`-quantile by the nearest-rank method.
+    fn quantile_index(&self, q: f64) -> Option<usize> {
+        if self.state.len() == 0 || !(0.0..=1.0).contains(&q) {
+            return None;
+        }
+        let rank = (q * self.state.len() as f64).ceil() as usize;
+        Some(rank.clamp(1, self.state.len()) - 1)
+    }
+
+    // Removes the node with valid index and returns its payload.
+    fn take_at(&mut self, index: usize) -> T {
+        let node = self.unlink_at(index);
+        self.state.handles.release(node);
+        unsafe { Box::from_raw(node).payload }
+    }
+
+    // Returns the index of the node of the list. The indexed mode counts the smaller
+    // elements with the index and then looks for the node among the equal ones.
+    fn position_of(&self, node: *mut Node<T>) -> usize
+    where
+        T: PartialOrd,
+    {
+        let Some(span_index) = &self.index else {
+            return self.state.index_of(node);
+        };
+        let payload = unsafe { &(*node).payload };
+        let mut position = span_index.count_while(&self.state, |existing| existing < payload);
+        if position < self.state.len() {
+            let mut current = span_index.node_at(&self.state, position);
+            while !current.is_null() {
+                if current == node {
+                    return position;
+                }
+                current = unsafe { (*current).next };
+                position += 1;
+            }
+        }
+        // Incomparable elements (like NaN) break the order, so the node is searched from the head
+        self.state.index_of(node)
+    }
+
+    // Error returns, if the index out of bounds.
+    fn check_index(&self, index: usize) -> Result<()> {
+        if index >= self.state.len() {
+            return Err(DSError::IndexOutOfBounds {
+                index,
+                len: self.state.len(),
+            });
+        }
+        Ok(())
+    }
+
+    // Unlinks the node which follows `prev` (null `prev` means the head) and returns it.
+    fn unlink_after(&mut self, prev: *mut Node<T>) -> *mut Node<T> {
+        let node = self.state.node_after(prev);
+        unsafe {
+            if prev.is_null() {
+                self.state.head = (*node).next;
+            } else {
+                (*prev).next = (*node).next;
+            }
+            if (*node).next.is_null() {
+                self.state.last = prev;
+            }
+            (*node).next = ptr::null_mut();
+        }
+        self.state.size -= 1;
+        self.state.reset_finger();
+        node
+    }
+
+    // Helper for insertion into the middle (used in insert_sorted())
+    fn insert_in_middle(&mut self, ptr: *mut Node<T>)
+    where
+        T: PartialOrd,
+    {
+        let mut prev = self.state.head;
+        unsafe {
+            let mut next = (*prev).next;
+
+            while !next.is_null() {
+                if self.goes_before(&(*ptr).payload, &(*next).payload) {
+                    (*prev).next = ptr;
+                    (*ptr).next = next;
+                    return;
+                }
+                prev = next;
+                next = (*next).next;
+            }
+        }
+    }
+}
+
+// Owns the node which is not linked to the list yet and frees it on drop.
+struct DetachedNode<T>(*mut Node<T>);
+
+impl<T> Drop for DetachedNode<T> {
+    fn drop(&mut self) {
+        drop(unsafe { Box::from_raw(self.0) });
+    }
+}
+
+// Takes the span index (if any) out of the list, so the nodes may be relinked bypassing it,
+// and rebuilds the index on drop, even if the relinking is interrupted by a panic.
+struct DetachedIndex<'a, T> {
+    list: &'a mut SortedList<T>,
+    indexed: bool,
+}
+
+impl<'a, T> DetachedIndex<'a, T> {
+    fn new(list: &'a mut SortedList<T>) -> Self {
+        let indexed = list.index.take().is_some();
+        Self { list, indexed }
+    }
+}
+
+impl<T> Drop for DetachedIndex<'_, T> {
+    fn drop(&mut self) {
+        if self.indexed {
+            self.list.index = Some(SpanIndex::build(&self.list.state));
+        }
+    }
+}
+
+impl<T> Default for SortedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PartialOrd> FromIterator<T> for SortedList<T> {
+    /// Builds the list in O(n log n): the node chain is sorted once with merge sort.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = SortedList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T: PartialOrd> Extend<T> for SortedList<T> {
+    /// Adds the elements in O(m log m + n): the new node chain is sorted once
+    /// with merge sort and then merged into the list in one pass.
+    /// Equal elements are ordered according to the [`EqualOrder`] of the list.
+    ///
+    /// If the list is bounded, the result is the same as of pushing the elements one by one.
+    /// With an eviction policy they are really pushed one by one in O(m * capacity),
+    /// so the list never holds more than `capacity` elements.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        DetachedIndex::new(self).list.extend_unindexed(iter);
+        debug_check_invariants!(self);
+    }
+}
+
+impl<T: PartialOrd> SortedList<T> {
+    // Implements `extend()` for the list without the index.
+    fn extend_unindexed(&mut self, iter: impl IntoIterator<Item = T>) {
+        let free = match self.bound {
+            Some(Bound {
+                policy: Overflow::EvictFront | Overflow::EvictBack,
+                ..
+            }) => {
+                // The chain of all new elements is not built: each one is evicted at once,
+                // if it is not better than the kept ones
+                for payload in iter {
+                    let _ = self.push_node(payload);
+                }
+                return;
+            }
+            Some(Bound {
+                capacity,
+                policy: Overflow::Reject,
+            }) => capacity.saturating_sub(self.state.len()),
+            None => usize::MAX,
+        };
+
+        // Merge sort is stable, so the order of equal elements is defined by the chain
+        let mut chain = ListCommon::new();
+        for payload in iter.into_iter().take(free) {
+            match self.equal_order {
+                EqualOrder::Fifo => chain.push_back(payload),
+                EqualOrder::Lifo => chain.push_front(payload),
+            }
+        }
+        chain.sort();
+        match self.equal_order {
+            EqualOrder::Fifo => self.state.merge_sorted(chain),
+            EqualOrder::Lifo => {
+                // The new elements go first, so they are placed before the equal ones
+                std::mem::swap(&mut self.state, &mut chain);
+                self.state.merge_sorted(chain);
+            }
+        }
+    }
+}
+
+impl<T> List<T> for SortedList<T>
+where
+    T: PartialOrd,
+{
+    /// Returns list size.
+    ///
+    /// **Efficiency**: O(1)
+    fn len(&self) -> usize {
+        self.state.len()
+    }
+
+    /// Returns the payload value of the first node in the list.
+    ///
+    /// **Efficiency**: O(1)
+    fn head(&self) -> Option<&T> {
+        self.state.head()
+    }
+
+    /// Returns the payload value of the last node in the list.
+    ///
+    /// **Efficiency**: O(1)
+    fn last(&self) -> Option<&T> {
+        self.state.last()
+    }
+
+    /// Returns a list item by index, or error if index out of bounds.
+    ///
+    /// **Efficiency**: O(n), O(1) amortized for sequential access; O(log n) expected
+    /// in the indexed mode
+    fn get(&self, index: usize) -> crate::Result<&T> {
+        self.check_index(index)?;
+        Ok(unsafe { &(*self.node_at(index)).payload })
+    }
+
+    /// Returns a mutable list item by index, or error if index out of bounds.
+    ///
+    /// **Warning**: changing the item may break the sort order, use
+    /// [`update`](SortedList::update) instead or call [`resort`](SortedList::resort) afterwards.
+    ///
+    /// **Efficiency**: O(n), O(1) amortized for sequential access; O(log n) expected
+    /// in the indexed mode
+    fn get_mut(&mut self, index: usize) -> crate::Result<&mut T> {
+        self.check_index(index)?;
+        Ok(unsafe { &mut (*self.node_at(index)).payload })
+    }
+
+    /// Returns an iterator over the immutable items of the list.
+    fn iter<'a>(&'a self) -> impl Iterator<Item = &'a T>
+    where
+        T: 'a,
+    {
+        self.state.iter()
+    }
+
+    /// Returns an iterator over the mutable items of the list.
+    ///
+    /// **Warning**: changing the items may break the sort order, use
+    /// [`update_where`](SortedList::update_where) instead or call
+    /// [`resort`](SortedList::resort) afterwards.
+    fn iter_mut<'a>(&'a mut self) -> impl Iterator<Item = &'a mut T>
+    where
+        T: 'a,
+    {
+        self.state.iter_mut()
+    }
+
+    /// Returns an iterator that consumes the list.
+    fn into_iter(self) -> impl Iterator<Item = T> {
+        self.state.into_iter()
+    }
+
+    /// Adds a new node to the list according to the sort order.
+    /// If the list is bounded, the overflow policy is applied
+    /// (the evicted or rejected element is dropped).
+    ///
+    /// **Efficiency**: O(n) at worst
+    fn push(&mut self, payload: T) {
+        let _ = self.try_push(payload);
+    }
+
+    /// Removes a node from the end of the list and returns its payload value.
+    ///
+    /// **Efficiency**: O(n), O(log n) expected in the indexed mode
+    fn pop_back(&mut self) -> Option<T> {
+        let payload = match self.index {
+            Some(_) if self.state.len() > 0 => Some(self.take_at(self.state.len() - 1)),
+            _ => self.state.pop_back(),
+        };
+        debug_check_invariants!(self);
+        payload
+    }
+
+    /// Removes a node from the front of the list and returns its payload value.
+    ///
+    /// **Efficiency**: O(1), O(log n) expected in the indexed mode
+    fn pop_front(&mut self) -> Option<T> {
+        let payload = match self.index {
+            Some(_) if self.state.len() > 0 => Some(self.take_at(0)),
+            _ => self.state.pop_front(),
+        };
+        debug_check_invariants!(self);
+        payload
+    }
 
     /// Removes a node from the specified location in the list.
     /// Error returns, if the index out of bounds.
     ///
-    /// **Efficiency**: O(n)
+    /// **Efficiency**: O(n), O(log n) expected in the indexed mode
     fn remove(&mut self, index: usize) -> crate::Result<T> {
-        self.state.remove(index)
+        let payload = match self.index {
+            Some(_) => self.check_index(index).map(|_| self.take_at(index)),
+            None => self.state.remove(index),
+        };
+        debug_check_invariants!(self);
+        payload
     }
 
     /// Finds the first node whose payload is equal to the given `value` and returns its index.
@@ -209,6 +1224,10 @@ where
 mod tests {
     use super::*;
 
+    crate::list_conformance_tests!(conformance, SortedList::new);
+    crate::list_conformance_tests!(lifo_conformance, || SortedList::new().with_equal_order(EqualOrder::Lifo));
+    crate::list_conformance_tests!(indexed_conformance, || SortedList::new().with_index());
+
     #[test]
     fn test_from_slice() {
         let list = SortedList::from_slice(&[2, 1, 5, 4, 3]);
@@ -378,6 +1397,19 @@ mod tests {
             );
         }
 
+        #[test]
+        fn test_get_after_push() {
+            let mut list = SortedList::from_slice(&[10, 20, 30, 40]);
+            assert_eq!(list.get(2), Ok(&30));
+
+            list.push(5); // head
+            assert_eq!(list.get(3), Ok(&30), "cached position must be shifted by push to head");
+
+            list.push(25); // middle
+            let values: Vec<_> = (0..list.len()).map(|i| *list.get(i).unwrap()).collect();
+            assert_eq!(values, [5, 10, 20, 25, 30, 40], "get() after push() returns wrong items");
+        }
+
         #[test]
         fn test_push_after_clear() {
             let mut list = SortedList::from_slice(&[1, 2, 3]);
@@ -665,4 +1697,1305 @@ mod tests {
             );
         }
     }
+
+    mod bounded {
+        use super::*;
+        use crate::core::DSError;
+
+        fn setup_bounded(capacity: usize, policy: Overflow, values: &[i32]) -> SortedList<i32> {
+            let mut list = SortedList::bounded(capacity, policy);
+            for value in values {
+                list.push(*value);
+            }
+            list
+        }
+
+        #[test]
+        fn test_capacity_and_is_full() {
+            let list = SortedList::from_slice(&[1, 2]);
+            assert_eq!(list.capacity(), None, "list must be unbounded by default");
+            assert!(!list.is_full(), "unbounded list is never full");
+
+            let list = setup_bounded(2, Overflow::Reject, &[1, 2]);
+            assert_eq!(list.capacity(), Some(2));
+            assert!(list.is_full(), "list must be full");
+        }
+
+        #[test]
+        fn test_reject_policy() {
+            let mut list = setup_bounded(3, Overflow::Reject, &[5, 1, 3]);
+            assert_eq!(
+                list.try_push(2),
+                Err(DSError::CapacityExceeded { capacity: 3 }),
+                "try_push() into full list must be rejected"
+            );
+            list.push(0);
+            assert_eq!(list.to_vec(), [1, 3, 5], "rejected elements must not be added");
+        }
+
+        #[test]
+        fn test_evict_smallest() {
+            let mut list = setup_bounded(3, Overflow::EvictFront, &[5, 1, 3]);
+
+            assert_eq!(list.try_push(4), Ok(Some(1)), "the smallest element must be evicted");
+            assert_eq!(list.to_vec(), [3, 4, 5]);
+
+            assert_eq!(list.try_push(0), Ok(Some(0)), "the new smallest element is evicted itself");
+            assert_eq!(list.to_vec(), [3, 4, 5]);
+
+            list.push(9);
+            assert_eq!(list.to_vec(), [4, 5, 9]);
+            assert_eq!(list.len(), 3, "size must not exceed the capacity");
+        }
+
+        #[test]
+        fn test_evict_largest() {
+            let mut list = setup_bounded(3, Overflow::EvictBack, &[5, 1, 3]);
+
+            assert_eq!(list.try_push(2), Ok(Some(5)), "the largest element must be evicted");
+            assert_eq!(list.to_vec(), [1, 2, 3]);
+            assert_eq!(list.last(), Some(&3), "last must be updated after eviction");
+
+            assert_eq!(list.try_push(7), Ok(Some(7)), "the new largest element is evicted itself");
+            assert_eq!(list.to_vec(), [1, 2, 3]);
+        }
+
+        #[test]
+        fn test_bounded_memory_leaks() {
+            use drop_tracker::DropTracker;
+
+            let mut tracker = DropTracker::new();
+
+            let mut list = SortedList::bounded(4, Overflow::EvictBack);
+            for i in [7, 3, 9, 1, 8, 2, 6, 0, 5, 4] {
+                list.push(tracker.track(i));
+            }
+            assert_eq!(tracker.alive().count(), 4, "evicted elements must be dropped");
+            assert_eq!(list.iter().map(|item| **item).collect::<Vec<_>>(), [0, 1, 2, 3]);
+
+            drop(list);
+            assert_eq!(tracker.alive().count(), 0);
+            assert_eq!(tracker.dropped().count(), 10);
+        }
+    }
+
+    mod top_k {
+        use super::*;
+
+        fn setup_top(k: usize, keep: Keep, values: &[i32]) -> SortedList<i32> {
+            let mut list = SortedList::with_limit(k, keep);
+            for value in values {
+                list.push(*value);
+            }
+            list
+        }
+
+        #[test]
+        fn test_keep_largest() {
+            let mut list = setup_top(3, Keep::Largest, &[5, 1, 9, 3, 7, 2]);
+            assert_eq!(list.to_vec(), [5, 7, 9]);
+            assert_eq!(list.threshold(), Some(&5));
+
+            assert_eq!(list.try_push(4), Ok(Some(4)), "worse candidate must be rejected");
+            assert_eq!(list.try_push(8), Ok(Some(5)), "the worst element must be evicted");
+            assert_eq!(list.to_vec(), [7, 8, 9]);
+            assert_eq!(list.try_push(10), Ok(Some(7)));
+            assert_eq!(list.last(), Some(&10), "last must be updated");
+            assert_eq!(list.into_sorted_vec(), [8, 9, 10]);
+        }
+
+        #[test]
+        fn test_keep_smallest() {
+            let mut list = setup_top(3, Keep::Smallest, &[5, 1, 9, 3, 7, 2]);
+            assert_eq!(list.to_vec(), [1, 2, 3]);
+            assert_eq!(list.threshold(), Some(&3));
+
+            assert_eq!(list.try_push(4), Ok(Some(4)), "worse candidate must be rejected");
+            assert_eq!(list.try_push(0), Ok(Some(3)), "the worst element must be evicted");
+            assert_eq!(list.to_vec(), [0, 1, 2]);
+            assert_eq!(list.last(), Some(&2), "last must be updated");
+
+            assert_eq!(list.try_push(1), Ok(Some(2)), "insertion before the last node");
+            assert_eq!(list.to_vec(), [0, 1, 1]);
+            assert_eq!(list.last(), Some(&1));
+            assert_eq!(list.get(2), Ok(&1), "finger must be valid after eviction");
+            assert_eq!(list.into_sorted_vec(), [0, 1, 1]);
+        }
+
+        #[test]
+        fn test_single_element_limit() {
+            let list = setup_top(1, Keep::Smallest, &[4, 2, 3]);
+            assert_eq!(list.to_vec(), [2]);
+            assert_eq!(list.head(), list.last());
+
+            let mut list = setup_top(1, Keep::Largest, &[4, 2, 5]);
+            assert_eq!(list.to_vec(), [5]);
+            assert_eq!(list.threshold(), Some(&5));
+            list.push(6);
+            assert_eq!(list.head(), Some(&6));
+        }
+
+        #[test]
+        fn test_threshold() {
+            let list = setup_top(3, Keep::Largest, &[1, 2]);
+            assert_eq!(list.threshold(), None, "not full list accepts everything");
+
+            let list = setup_top(0, Keep::Largest, &[1, 2]);
+            assert!(list.is_empty());
+            assert_eq!(list.threshold(), None);
+
+            let list = SortedList::from_slice(&[1, 2]);
+            assert_eq!(list.threshold(), None, "unbounded list accepts everything");
+
+            let mut list = SortedList::bounded(2, Overflow::Reject);
+            list.push(1);
+            list.push(2);
+            assert_eq!(list.threshold(), None, "full list with Reject policy accepts nothing");
+        }
+
+        #[test]
+        fn test_equal_elements() {
+            let mut list = setup_top(2, Keep::Largest, &[1, 3]);
+            assert_eq!(list.try_push(1), Ok(Some(1)));
+            assert_eq!(list.to_vec(), [1, 3]);
+
+            let mut list = setup_top(2, Keep::Smallest, &[1, 3]);
+            assert_eq!(list.try_push(3), Ok(Some(3)), "equal to the worst must be rejected");
+            assert_eq!(list.to_vec(), [1, 3]);
+        }
+
+        // Ordered by `key` only, so the rejected candidate can be told from the kept element
+        #[derive(Debug)]
+        struct Item {
+            key: i32,
+            name: &'static str,
+        }
+
+        impl PartialEq for Item {
+            fn eq(&self, other: &Self) -> bool {
+                self.key == other.key
+            }
+        }
+
+        impl PartialOrd for Item {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                self.key.partial_cmp(&other.key)
+            }
+        }
+
+        fn names(list: &SortedList<Item>) -> Vec<&'static str> {
+            list.iter().map(|item| item.name).collect()
+        }
+
+        #[test]
+        fn test_tie_with_threshold_is_rejected() {
+            for order in [EqualOrder::Fifo, EqualOrder::Lifo] {
+                let mut list = SortedList::with_limit(2, Keep::Largest).with_equal_order(order);
+                list.push(Item { key: 1, name: "old-1" });
+                list.push(Item { key: 3, name: "old-3" });
+                let rejected = list.try_push(Item { key: 1, name: "new-1" }).unwrap().unwrap();
+                assert_eq!(rejected.name, "new-1", "{order:?}: the candidate must be rejected");
+                assert_eq!(names(&list), ["old-1", "old-3"], "{order:?}: the list must not change");
+
+                let mut list = SortedList::with_limit(2, Keep::Smallest).with_equal_order(order);
+                list.push(Item { key: 1, name: "old-1" });
+                list.push(Item { key: 3, name: "old-3" });
+                let rejected = list.try_push(Item { key: 3, name: "new-3" }).unwrap().unwrap();
+                assert_eq!(rejected.name, "new-3", "{order:?}: the candidate must be rejected");
+                assert_eq!(names(&list), ["old-1", "old-3"], "{order:?}: the list must not change");
+            }
+        }
+
+        #[test]
+        fn test_same_as_sorting() {
+            let values: Vec<i32> = (0..200).map(|i| (i * 37 + 11) % 101).collect();
+            let mut sorted = values.clone();
+            sorted.sort();
+
+            let list = setup_top(10, Keep::Smallest, &values);
+            assert_eq!(list.into_sorted_vec(), sorted[..10]);
+
+            let list = setup_top(10, Keep::Largest, &values);
+            assert_eq!(list.into_sorted_vec(), sorted[sorted.len() - 10..]);
+        }
+
+        #[test]
+        fn test_top_k_memory_leaks() {
+            use drop_tracker::DropTracker;
+
+            let mut tracker = DropTracker::new();
+
+            for keep in [Keep::Smallest, Keep::Largest] {
+                let mut list = SortedList::with_limit(3, keep);
+                for i in [7, 3, 9, 1, 8, 2, 6, 0, 5, 4] {
+                    list.push(tracker.track(i + 10 * keep as usize));
+                }
+                assert_eq!(list.len(), 3);
+                drop(list);
+            }
+            assert_eq!(tracker.alive().count(), 0);
+            assert_eq!(tracker.dropped().count(), 20);
+        }
+    }
+
+    mod order_statistics {
+        use super::*;
+
+        fn setup_lists(values: &[i32]) -> [SortedList<i32>; 2] {
+            [SortedList::from_slice(values), SortedList::from_slice(values).with_index()]
+        }
+
+        #[test]
+        fn test_rank() {
+            for list in setup_lists(&[10, 20, 20, 30, 40]) {
+                assert_eq!(list.rank(&5), 0);
+                assert_eq!(list.rank(&10), 0);
+                assert_eq!(list.rank(&20), 1, "rank() must count only smaller elements");
+                assert_eq!(list.rank(&25), 3);
+                assert_eq!(list.rank(&40), 4);
+                assert_eq!(list.rank(&99), 5);
+            }
+            assert_eq!(SortedList::<i32>::new().rank(&1), 0);
+            assert_eq!(SortedList::<i32>::new().with_index().rank(&1), 0);
+        }
+
+        #[test]
+        fn test_select() {
+            for list in setup_lists(&[40, 10, 30, 20]) {
+                for k in 0..4 {
+                    assert_eq!(list.select(k), Ok(&(10 * (k as i32 + 1))));
+                }
+                assert_eq!(list.select(4), Err(DSError::IndexOutOfBounds { index: 4, len: 4 }));
+            }
+        }
+
+        #[test]
+        fn test_median() {
+            for list in setup_lists(&[3, 1, 2]) {
+                assert_eq!(list.median(), Some(&2));
+            }
+            for list in setup_lists(&[4, 3, 1, 2]) {
+                assert_eq!(list.median(), Some(&2), "lower median for even length");
+            }
+            for list in setup_lists(&[]) {
+                assert_eq!(list.median(), None);
+            }
+        }
+
+        #[test]
+        fn test_quantile() {
+            let values: Vec<i32> = (1..=100).rev().collect();
+            for list in setup_lists(&values) {
+                assert_eq!(list.quantile(0.0), Some(&1));
+                assert_eq!(list.quantile(0.01), Some(&1));
+                assert_eq!(list.quantile(0.5), Some(&50));
+                assert_eq!(list.quantile(0.501), Some(&51));
+                assert_eq!(list.quantile(0.95), Some(&95));
+                assert_eq!(list.quantile(1.0), Some(&100));
+                assert_eq!(list.quantile(-0.1), None);
+                assert_eq!(list.quantile(1.1), None);
+                assert_eq!(list.quantile(f64::NAN), None);
+            }
+            for list in setup_lists(&[7]) {
+                assert_eq!(list.quantile(0.0), Some(&7));
+                assert_eq!(list.quantile(1.0), Some(&7));
+            }
+        }
+
+        #[test]
+        fn test_percentiles() {
+            let values: Vec<i32> = (1..=1000).collect();
+            for list in setup_lists(&values) {
+                let qs = [0.99, 0.5, 0.999, 2.0, 0.0, 0.5];
+                let expected: Vec<_> = qs.iter().map(|q| list.quantile(*q)).collect();
+                assert_eq!(list.percentiles(&qs), expected, "must be equal to quantile() results");
+                assert_eq!(
+                    list.percentiles(&qs),
+                    [Some(&990), Some(&500), Some(&999), None, Some(&1), Some(&500)]
+                );
+                assert!(list.percentiles(&[]).is_empty());
+            }
+            for list in setup_lists(&[]) {
+                assert_eq!(list.percentiles(&[0.5, 0.9]), [None, None]);
+            }
+        }
+    }
+
+    mod merge_all {
+        use super::*;
+
+        #[derive(Debug)]
+        struct Record {
+            key: u8,
+            tag: &'static str,
+        }
+
+        impl PartialEq for Record {
+            fn eq(&self, other: &Self) -> bool {
+                self.key == other.key
+            }
+        }
+
+        impl PartialOrd for Record {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                self.key.partial_cmp(&other.key)
+            }
+        }
+
+        fn records(items: &[(u8, &'static str)]) -> SortedList<Record> {
+            items.iter().map(|(key, tag)| Record { key: *key, tag }).collect()
+        }
+
+        #[test]
+        fn test_merge_all() {
+            let lists = vec![
+                SortedList::from_slice(&[5, 1, 9]),
+                SortedList::from_slice(&[2, 8]),
+                SortedList::new(),
+                SortedList::from_slice(&[3, 7, 4]),
+                SortedList::from_slice(&[6]),
+            ];
+            let list = SortedList::merge_all(lists);
+            assert_eq!(list.to_vec(), [1, 2, 3, 4, 5, 6, 7, 8, 9]);
+            assert_eq!(list.last(), Some(&9), "last must be set after merge");
+            assert_eq!(list.check_invariants(), Ok(()));
+
+            assert!(SortedList::<u8>::merge_all(Vec::new()).is_empty(), "merge of no lists");
+            let list = SortedList::merge_all([SortedList::from_slice(&[2, 1])]);
+            assert_eq!(list.to_vec(), [1, 2], "merge of one list");
+        }
+
+        #[test]
+        fn test_merge_all_is_stable() {
+            let lists = vec![
+                records(&[(1, "a1"), (2, "a2"), (2, "a3")]),
+                records(&[(2, "b1"), (3, "b2")]),
+                records(&[(1, "c1"), (2, "c2"), (3, "c3")]),
+            ];
+            let list = SortedList::merge_all(lists);
+            assert_eq!(
+                list.iter().map(|record| record.tag).collect::<Vec<_>>(),
+                ["a1", "c1", "a2", "a3", "b1", "c2", "b2", "c3"],
+                "equal elements must go in the order of the lists"
+            );
+        }
+
+        #[test]
+        fn test_merge_all_many_lists() {
+            let lists = (0..37).map(|shard| (0..10).map(|i| i * 37 + shard).collect::<SortedList<_>>());
+            let list = SortedList::merge_all(lists);
+            assert_eq!(list.to_vec(), (0..370).collect::<Vec<_>>());
+            assert_eq!(list.get(369), Ok(&369));
+        }
+
+        #[test]
+        fn test_merge_all_settings() {
+            let lists = vec![
+                SortedList::bounded(2, Overflow::Reject).with_index(),
+                SortedList::new().with_equal_order(EqualOrder::Lifo),
+            ];
+            let mut list = SortedList::merge_all(lists);
+            assert_eq!(list.capacity(), None, "result must be unbounded");
+            assert!(!list.is_indexed());
+            assert_eq!(list.equal_order(), EqualOrder::Fifo);
+            list.extend([3, 1, 2]);
+            assert_eq!(list.to_vec(), [1, 2, 3]);
+        }
+
+        #[test]
+        fn test_merge_all_memory_leaks() {
+            use drop_tracker::DropTracker;
+
+            let mut tracker = DropTracker::new();
+
+            let lists: Vec<SortedList<_>> = (0..4)
+                .map(|shard| (0..5).map(|i| tracker.track(i * 4 + shard)).collect())
+                .collect();
+            let list = SortedList::merge_all(lists);
+            assert_eq!(tracker.alive().count(), 20, "merge must not drop elements");
+            assert_eq!(list.iter().map(|item| **item).collect::<Vec<_>>(), (0..20).collect::<Vec<_>>());
+
+            drop(list);
+            assert_eq!(tracker.alive().count(), 0);
+            assert_eq!(tracker.dropped().count(), 20);
+        }
+    }
+
+    mod pop_while {
+        use super::*;
+
+        #[test]
+        fn test_pop_while() {
+            let mut list = SortedList::from_slice(&[5, 1, 4, 2, 3]);
+            let front = list.pop_while(|value| *value < 3);
+            assert_eq!(front.to_vec(), [1, 2]);
+            assert_eq!(front.last(), Some(&2), "last of the removed prefix must be set");
+            assert_eq!(list.to_vec(), [3, 4, 5]);
+            assert_eq!(front.check_invariants(), Ok(()));
+            assert_eq!(list.check_invariants(), Ok(()));
+
+            assert!(list.pop_while(|_| false).is_empty(), "nothing must be removed");
+            assert_eq!(list.len(), 3);
+
+            let all = list.pop_while(|_| true);
+            assert_eq!(all.to_vec(), [3, 4, 5]);
+            assert!(list.is_empty());
+            assert_eq!(list.last(), None);
+            list.push(1);
+            assert_eq!(list.to_vec(), [1], "list must be usable after pop_while()");
+        }
+
+        #[test]
+        fn test_expire_before() {
+            let mut list = SortedList::from_slice(&[10, 20, 20, 30]);
+            assert!(list.expire_before(&10).is_empty(), "bound itself must not be removed");
+            assert_eq!(list.expire_before(&25).to_vec(), [10, 20, 20]);
+            assert_eq!(list.to_vec(), [30]);
+            assert_eq!(list.expire_before(&99).to_vec(), [30]);
+            assert!(list.is_empty());
+        }
+
+        #[test]
+        fn test_removed_prefix_keeps_settings() {
+            let mut list = SortedList::bounded(5, Overflow::Reject)
+                .with_equal_order(EqualOrder::Lifo)
+                .with_index();
+            list.extend([1, 2, 3, 4, 5]);
+            let mut front = list.expire_before(&4);
+            assert_eq!(front.equal_order(), EqualOrder::Lifo);
+            assert_eq!(front.capacity(), None, "removed prefix must be unbounded");
+            assert!(!front.is_indexed());
+            front.extend([10, 11, 12]);
+            assert_eq!(front.len(), 6);
+
+            assert!(list.is_indexed(), "list must stay indexed");
+            assert_eq!(list.check_invariants(), Ok(()));
+            assert_eq!(list.select(1), Ok(&5));
+            list.push(0);
+            assert_eq!(list.to_vec(), [0, 4, 5]);
+        }
+
+        #[test]
+        fn test_pop_while_memory_leaks() {
+            use drop_tracker::DropTracker;
+
+            let mut tracker = DropTracker::new();
+
+            for indexed in [false, true] {
+                let mut list = SortedList::new();
+                if indexed {
+                    list = list.with_index();
+                }
+                for i in 0..10 {
+                    list.push(tracker.track(i + 10 * indexed as i32));
+                }
+                let front = list.pop_while(|item| **item % 10 < 4);
+                assert_eq!(front.len(), 4);
+                drop(list);
+                assert_eq!(front.len(), 4, "removed prefix must own its nodes");
+            }
+            assert_eq!(tracker.alive().count(), 0);
+            assert_eq!(tracker.dropped().count(), 20);
+        }
+    }
+
+    mod indexed {
+        use super::*;
+
+        fn values(list: &SortedList<i32>) -> Vec<i32> {
+            list.to_vec()
+        }
+
+        #[test]
+        fn test_with_index() {
+            let list = SortedList::from_slice(&[3, 1, 2]);
+            assert!(!list.is_indexed());
+            let list = list.with_index();
+            assert!(list.is_indexed(), "the index must be built");
+            assert_eq!(list.check_invariants(), Ok(()));
+            assert_eq!(list.get(2), Ok(&3));
+        }
+
+        #[test]
+        fn test_mutations_keep_index() {
+            let mut list = SortedList::new().with_index();
+            let mut expected = Vec::new();
+            for i in 0..300 {
+                let value = (i * 7919) % 307;
+                list.push(value);
+                let position = expected.partition_point(|existing| *existing <= value);
+                expected.insert(position, value);
+            }
+            assert_eq!(list.check_invariants(), Ok(()));
+
+            for i in 0..100 {
+                let index = (i * 13) % expected.len();
+                assert_eq!(list.remove(index), Ok(expected.remove(index)));
+            }
+            assert_eq!(list.pop_front(), Some(expected.remove(0)));
+            assert_eq!(list.pop_back(), expected.pop());
+            assert_eq!(list.check_invariants(), Ok(()));
+
+            list.update(5, |value| *value += 1000).unwrap();
+            let value = expected.remove(5) + 1000;
+            expected.push(value);
+            assert_eq!(list.last(), Some(&value), "updated element must be moved");
+
+            list.update_where(|value| value % 2 == 0, |value| *value = -*value);
+            for value in expected.iter_mut().filter(|value| **value % 2 == 0) {
+                *value = -*value;
+            }
+            expected.sort();
+            assert!(list.is_indexed(), "update_where() must keep the index");
+            assert_eq!(list.check_invariants(), Ok(()));
+
+            list.extend([500, -500, 0]);
+            expected.extend([500, -500, 0]);
+            expected.sort();
+            assert_eq!(list.check_invariants(), Ok(()));
+
+            for value in list.iter_mut() {
+                *value = -*value;
+            }
+            list.resort();
+            for value in expected.iter_mut() {
+                *value = -*value;
+            }
+            expected.sort();
+            assert_eq!(list.check_invariants(), Ok(()));
+            assert_eq!(values(&list), expected);
+            for (k, value) in expected.iter().enumerate() {
+                assert_eq!(list.select(k), Ok(value));
+                assert_eq!(list.rank(value), expected.partition_point(|existing| existing < value));
+            }
+
+            list.clear();
+            assert!(list.is_empty());
+            assert_eq!(list.check_invariants(), Ok(()));
+            list.push(1);
+            assert_eq!(values(&list), [1], "list must be usable after clear()");
+        }
+
+        #[test]
+        fn test_indexed_top_k() {
+            for keep in [Keep::Smallest, Keep::Largest] {
+                let mut list = SortedList::with_limit(5, keep).with_index();
+                let mut plain = SortedList::with_limit(5, keep);
+                for i in 0..100 {
+                    let value = (i * 37) % 101;
+                    assert_eq!(list.try_push(value), plain.try_push(value));
+                }
+                assert_eq!(list.check_invariants(), Ok(()));
+                assert_eq!(values(&list), values(&plain));
+            }
+
+            let mut list = SortedList::with_limit(0, Keep::Largest).with_index();
+            assert_eq!(list.try_push(1), Ok(Some(1)), "zero capacity list evicts everything");
+            assert!(list.is_empty());
+        }
+
+        #[test]
+        fn test_lifo_with_index() {
+            #[derive(Debug, Clone, Copy)]
+            struct Record(u8, u8);
+
+            impl PartialEq for Record {
+                fn eq(&self, other: &Self) -> bool {
+                    self.0 == other.0
+                }
+            }
+
+            impl PartialOrd for Record {
+                fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                    self.0.partial_cmp(&other.0)
+                }
+            }
+
+            for order in [EqualOrder::Fifo, EqualOrder::Lifo] {
+                let mut list = SortedList::new().with_equal_order(order).with_index();
+                let mut plain = SortedList::new().with_equal_order(order);
+                for i in 0..50 {
+                    list.push(Record(i % 5, i));
+                    plain.push(Record(i % 5, i));
+                }
+                let tags = |list: &SortedList<Record>| list.iter().map(|record| record.1).collect::<Vec<_>>();
+                assert_eq!(tags(&list), tags(&plain), "{order:?}: order of equal elements must be kept");
+            }
+        }
+
+        #[test]
+        fn test_indexed_memory_leaks() {
+            use drop_tracker::DropTracker;
+
+            let mut tracker = DropTracker::new();
+
+            let mut list = SortedList::new().with_index();
+            for i in [7, 3, 9, 1, 8, 2, 6, 0, 5, 4] {
+                list.push(tracker.track(i));
+            }
+            let _ = list.remove(3);
+            let _ = list.pop_back();
+            assert_eq!(tracker.alive().count(), 8);
+
+            drop(list);
+            assert_eq!(tracker.alive().count(), 0);
+            assert_eq!(tracker.dropped().count(), 10);
+        }
+    }
+
+    mod update {
+        use super::*;
+
+        #[test]
+        fn test_update_moves_node() {
+            let mut list = SortedList::from_slice(&[1, 3, 5, 7]);
+
+            assert_eq!(list.update(0, |x| *x = 6), Ok(()));
+            assert_eq!(list.to_vec(), [3, 5, 6, 7], "head must move to the middle");
+            assert_eq!(list.update(3, |x| *x = 0), Ok(()));
+            assert_eq!(list.to_vec(), [0, 3, 5, 6], "last must move to the head");
+            assert_eq!(list.last(), Some(&6), "last must be updated");
+            assert_eq!(list.update(1, |x| *x = 9), Ok(()));
+            assert_eq!(list.to_vec(), [0, 5, 6, 9], "middle must move to the end");
+            assert_eq!(list.last(), Some(&9), "last must be updated");
+            assert_eq!(list.len(), 4, "update() must not change the size");
+            assert_eq!(list.get(1), Ok(&5), "cached position must be valid after update()");
+        }
+
+        #[test]
+        fn test_update_single_and_out_of_bounds() {
+            let mut list = SortedList::from_slice(&[1]);
+            assert_eq!(list.update(0, |x| *x += 1), Ok(()));
+            assert_eq!(list.to_vec(), [2]);
+            assert_eq!(
+                list.update(1, |x| *x += 1),
+                Err(DSError::IndexOutOfBounds { index: 1, len: 1 })
+            );
+        }
+
+        #[test]
+        fn test_update_where() {
+            let mut list = SortedList::from_slice(&[1, 2, 3, 4, 5, 6]);
+
+            let count = list.update_where(|x| x % 2 == 0, |x| *x *= 10);
+            assert_eq!(count, 3, "bad number of changed elements");
+            assert_eq!(list.to_vec(), [1, 3, 5, 20, 40, 60]);
+            assert_eq!(list.last(), Some(&60));
+
+            assert_eq!(list.update_where(|x| *x > 100, |x| *x = 0), 0);
+            assert_eq!(list.update_where(|_| true, |x| *x = 100 - *x), 6);
+            assert_eq!(list.to_vec(), [40, 60, 80, 95, 97, 99]);
+            assert_eq!(list.find(&80), Some(2), "find() must work after update_where()");
+        }
+
+        #[test]
+        fn test_resort_after_raw_mutation() {
+            let mut list = SortedList::from_slice(&[1, 2, 3, 4]);
+            for x in list.iter_mut() {
+                *x = -*x;
+            }
+            *list.get_mut(0).unwrap() = 10;
+            list.resort();
+            assert_eq!(list.to_vec(), [-4, -3, -2, 10]);
+            assert_eq!(list.last(), Some(&10));
+        }
+
+        #[test]
+        fn test_update_memory_leaks() {
+            use drop_tracker::DropTracker;
+
+            let mut tracker = DropTracker::new();
+
+            let mut list = SortedList::new();
+            for i in 0..10 {
+                list.push(tracker.track(i));
+            }
+            list.update(0, |x| *x = tracker.track(20)).unwrap();
+            list.update_where(|x| **x < 5, |x| *x = tracker.track(**x + 30));
+            assert_eq!(tracker.alive().count(), 10, "replaced elements must be dropped");
+
+            drop(list);
+            assert_eq!(tracker.alive().count(), 0);
+            assert_eq!(tracker.dropped().count(), 15);
+        }
+    }
+
+    mod bulk {
+        use super::*;
+
+        #[test]
+        fn test_from_vec_and_collect() {
+            let list = SortedList::from_vec(vec![5, 3, 8, 1, 9, 2, 7]);
+            assert_eq!(list.to_vec(), [1, 2, 3, 5, 7, 8, 9]);
+            assert_eq!(list.len(), 7, "bad length after from_vec()");
+            assert_eq!(list.last(), Some(&9), "last must be the largest element");
+
+            let list: SortedList<_> = (0..100).rev().collect();
+            assert_eq!(list.to_vec(), (0..100).collect::<Vec<_>>());
+
+            let empty: SortedList<i32> = std::iter::empty().collect();
+            assert!(empty.is_empty());
+            assert_eq!(empty.last(), None);
+        }
+
+        #[test]
+        fn test_extend_merges_into_existing() {
+            let mut list = SortedList::from_slice(&[2, 4, 6]);
+            list.extend([5, 1, 7, 3]);
+            assert_eq!(list.to_vec(), [1, 2, 3, 4, 5, 6, 7]);
+            assert_eq!(list.len(), 7, "bad length after extend()");
+            assert_eq!(list.last(), Some(&7), "last must be updated after extend()");
+
+            list.extend([9, 8]);
+            assert_eq!(list.to_vec(), [1, 2, 3, 4, 5, 6, 7, 8, 9], "extend() after all elements");
+            list.extend(Vec::new());
+            assert_eq!(list.len(), 9, "extend() with empty iterator must not change the list");
+
+            list.push(0);
+            assert_eq!(list.head(), Some(&0), "list must be consistent after extend()");
+            assert_eq!(list.get(4), Ok(&4));
+        }
+
+        #[test]
+        fn test_extend_keeps_insertion_order_of_equal_elements() {
+            #[derive(Debug, PartialEq)]
+            struct Record(u8, &'static str);
+
+            impl PartialOrd for Record {
+                fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                    self.0.partial_cmp(&other.0)
+                }
+            }
+
+            let mut list = SortedList::from_vec(vec![Record(2, "a"), Record(1, "b"), Record(2, "c")]);
+            list.extend([Record(2, "d"), Record(1, "e")]);
+            let names: Vec<_> = list.iter().map(|record| record.1).collect();
+            assert_eq!(names, ["b", "e", "a", "c", "d"]);
+        }
+
+        #[test]
+        fn test_from_sorted_iter_unchecked() {
+            let list = SortedList::from_sorted_iter_unchecked(1..=5);
+            assert_eq!(list.to_vec(), [1, 2, 3, 4, 5]);
+            assert_eq!(list.last(), Some(&5));
+            assert_eq!(list.find(&3), Some(2));
+        }
+
+        #[test]
+        fn test_extend_bounded() {
+            let mut list = SortedList::bounded(4, Overflow::Reject);
+            list.extend([5, 1, 3]);
+            list.extend([4, 0, 2]);
+            assert_eq!(list.to_vec(), [1, 3, 4, 5], "elements after the capacity must be rejected");
+
+            let mut list = SortedList::bounded(3, Overflow::EvictFront);
+            list.extend([5, 1, 3, 9, 2]);
+            assert_eq!(list.to_vec(), [3, 5, 9], "the smallest elements must be evicted");
+
+            let mut list = SortedList::bounded(3, Overflow::EvictBack);
+            list.push(4);
+            list.extend([5, 1, 3, 9, 2]);
+            assert_eq!(list.to_vec(), [1, 2, 3], "the largest elements must be evicted");
+            assert_eq!(list.last(), Some(&3), "last must be updated after eviction");
+        }
+
+        #[test]
+        fn test_extend_bounded_memory() {
+            use std::cell::Cell;
+            use std::rc::Rc;
+
+            // Counts the alive elements
+            struct Counted(i32, Rc<Cell<usize>>);
+
+            impl Counted {
+                fn new(value: i32, alive: &Rc<Cell<usize>>) -> Self {
+                    alive.set(alive.get() + 1);
+                    Self(value, Rc::clone(alive))
+                }
+            }
+
+            impl Drop for Counted {
+                fn drop(&mut self) {
+                    self.1.set(self.1.get() - 1);
+                }
+            }
+
+            impl PartialEq for Counted {
+                fn eq(&self, other: &Self) -> bool {
+                    self.0 == other.0
+                }
+            }
+
+            impl PartialOrd for Counted {
+                fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                    self.0.partial_cmp(&other.0)
+                }
+            }
+
+            for policy in [Overflow::EvictFront, Overflow::EvictBack] {
+                let alive = Rc::new(Cell::new(0));
+                let mut max_alive = 0;
+                let mut list = SortedList::bounded(3, policy);
+                list.extend((0..100).map(|i| {
+                    max_alive = max_alive.max(alive.get());
+                    Counted::new((i * 37) % 101, &alive)
+                }));
+                assert!(max_alive <= 4, "{policy:?}: {max_alive} elements were alive at once");
+                assert_eq!(alive.get(), 3);
+            }
+        }
+
+        #[test]
+        fn test_bulk_memory_leaks() {
+            use drop_tracker::DropTracker;
+
+            let mut tracker = DropTracker::new();
+
+            let mut list: SortedList<_> = [7, 3, 9, 1].into_iter().map(|i| tracker.track(i)).collect();
+            list.extend([8, 2, 6].into_iter().map(|i| tracker.track(i)));
+            assert_eq!(tracker.alive().count(), 7);
+
+            let mut bounded = SortedList::bounded(2, Overflow::EvictBack);
+            bounded.extend([10, 11, 12, 13].into_iter().map(|i| tracker.track(i)));
+            assert_eq!(tracker.alive().count(), 9, "evicted elements must be dropped");
+
+            drop(list);
+            drop(bounded);
+            assert_eq!(tracker.alive().count(), 0);
+            assert_eq!(tracker.dropped().count(), 11);
+        }
+    }
+
+    mod equal_order {
+        use super::*;
+
+        // Record which is ordered by key only
+        #[derive(Debug, Clone)]
+        struct Record {
+            key: u8,
+            name: &'static str,
+        }
+
+        impl PartialEq for Record {
+            fn eq(&self, other: &Self) -> bool {
+                self.key == other.key
+            }
+        }
+
+        impl PartialOrd for Record {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                self.key.partial_cmp(&other.key)
+            }
+        }
+
+        fn record(key: u8, name: &'static str) -> Record {
+            Record { key, name }
+        }
+
+        fn names(list: &SortedList<Record>) -> Vec<&'static str> {
+            list.iter().map(|record| record.name).collect()
+        }
+
+        fn setup_list(order: EqualOrder) -> SortedList<Record> {
+            let mut list = SortedList::new().with_equal_order(order);
+            list.push(record(1, "head"));
+            list.push(record(2, "middle"));
+            list.push(record(3, "tail"));
+            list
+        }
+
+        #[test]
+        fn test_fifo_is_default() {
+            assert_eq!(SortedList::<u8>::new().equal_order(), EqualOrder::Fifo);
+            assert_eq!(SortedList::<u8>::bounded(1, Overflow::Reject).equal_order(), EqualOrder::Fifo);
+        }
+
+        #[test]
+        fn test_fifo_at_head() {
+            let mut list = setup_list(EqualOrder::Fifo);
+            list.push(record(1, "head2"));
+            list.push(record(1, "head3"));
+            assert_eq!(names(&list), ["head", "head2", "head3", "middle", "tail"]);
+        }
+
+        #[test]
+        fn test_fifo_in_middle() {
+            let mut list = setup_list(EqualOrder::Fifo);
+            list.push(record(2, "middle2"));
+            list.push(record(2, "middle3"));
+            assert_eq!(names(&list), ["head", "middle", "middle2", "middle3", "tail"]);
+        }
+
+        #[test]
+        fn test_fifo_at_tail() {
+            let mut list = setup_list(EqualOrder::Fifo);
+            list.push(record(3, "tail2"));
+            list.push(record(3, "tail3"));
+            assert_eq!(names(&list), ["head", "middle", "tail", "tail2", "tail3"]);
+            assert_eq!(list.last().map(|record| record.name), Some("tail3"));
+        }
+
+        #[test]
+        fn test_lifo_at_head() {
+            let mut list = setup_list(EqualOrder::Lifo);
+            list.push(record(1, "head2"));
+            list.push(record(1, "head3"));
+            assert_eq!(names(&list), ["head3", "head2", "head", "middle", "tail"]);
+        }
+
+        #[test]
+        fn test_lifo_in_middle() {
+            let mut list = setup_list(EqualOrder::Lifo);
+            list.push(record(2, "middle2"));
+            list.push(record(2, "middle3"));
+            assert_eq!(names(&list), ["head", "middle3", "middle2", "middle", "tail"]);
+        }
+
+        #[test]
+        fn test_lifo_at_tail() {
+            let mut list = setup_list(EqualOrder::Lifo);
+            list.push(record(3, "tail2"));
+            list.push(record(3, "tail3"));
+            assert_eq!(names(&list), ["head", "middle", "tail3", "tail2", "tail"]);
+            assert_eq!(list.last().map(|record| record.name), Some("tail"));
+        }
+
+        #[test]
+        fn test_extend_follows_equal_order() {
+            let mut list = setup_list(EqualOrder::Fifo);
+            list.extend([record(2, "a"), record(3, "b"), record(2, "c"), record(1, "d")]);
+            assert_eq!(names(&list), ["head", "d", "middle", "a", "c", "tail", "b"]);
+
+            let mut list = setup_list(EqualOrder::Lifo);
+            list.extend([record(2, "a"), record(3, "b"), record(2, "c"), record(1, "d")]);
+            assert_eq!(names(&list), ["d", "head", "c", "a", "middle", "b", "tail"]);
+        }
+
+        #[test]
+        fn test_update_follows_equal_order() {
+            let mut list = setup_list(EqualOrder::Fifo);
+            list.update(0, |record| record.key = 2).unwrap();
+            assert_eq!(names(&list), ["middle", "head", "tail"]);
+
+            let mut list = setup_list(EqualOrder::Lifo);
+            list.update(2, |record| record.key = 2).unwrap();
+            assert_eq!(names(&list), ["head", "tail", "middle"]);
+        }
+    }
+
+    mod handles {
+        use super::*;
+        use crate::core::DSError;
+        use crate::list::ListHandle;
+
+        fn setup(indexed: bool) -> (SortedList<i32>, Vec<ListHandle>) {
+            let list = SortedList::new();
+            let mut list = if indexed { list.with_index() } else { list };
+            let handles = [50, 10, 40, 20, 30]
+                .into_iter()
+                .map(|value| list.push_with_handle(value).unwrap())
+                .collect();
+            (list, handles)
+        }
+
+        #[test]
+        fn test_get_and_replace() {
+            for indexed in [false, true] {
+                let (mut list, handles) = setup(indexed);
+                list.extend([15, 35]);
+                assert_eq!(list.to_vec(), [10, 15, 20, 30, 35, 40, 50]);
+                assert_eq!(list.get_by_handle(handles[0]), Ok(&50));
+                assert_eq!(list.get_by_handle(handles[3]), Ok(&20));
+
+                assert_eq!(list.replace_by_handle(handles[0], 5), Ok(50));
+                assert_eq!(list.get_by_handle(handles[0]), Ok(&5), "handle must follow the moved element");
+                assert_eq!(list.to_vec(), [5, 10, 15, 20, 30, 35, 40]);
+                assert_eq!(list.check_invariants(), Ok(()));
+
+                list.update(1, |x| *x = 45).unwrap();
+                assert_eq!(list.get_by_handle(handles[1]), Ok(&45), "handle must survive update()");
+                list.update_where(|x| x % 20 == 0, |x| *x += 1);
+                assert_eq!(list.get_by_handle(handles[2]), Ok(&41), "handle must survive update_where()");
+                assert_eq!(list.get_by_handle(handles[3]), Ok(&21));
+                assert_eq!(list.check_invariants(), Ok(()));
+            }
+        }
+
+        #[test]
+        fn test_remove() {
+            for indexed in [false, true] {
+                let (mut list, handles) = setup(indexed);
+                assert_eq!(list.remove_by_handle(handles[2]), Ok(40));
+                assert_eq!(list.remove_by_handle(handles[1]), Ok(10));
+                assert_eq!(list.remove_by_handle(handles[0]), Ok(50));
+                assert_eq!(list.to_vec(), [20, 30]);
+                assert_eq!(list.get_by_handle(handles[3]), Ok(&20));
+                assert_eq!(list.get_by_handle(handles[4]), Ok(&30));
+                assert_eq!(list.check_invariants(), Ok(()));
+
+                for handle in &handles[..3] {
+                    assert_eq!(list.get_by_handle(*handle), Err(DSError::InvalidHandle));
+                    assert_eq!(list.remove_by_handle(*handle), Err(DSError::InvalidHandle));
+                    assert_eq!(list.replace_by_handle(*handle, 0), Err(DSError::InvalidHandle));
+                }
+
+                list.pop_front();
+                assert_eq!(list.get_by_handle(handles[3]), Err(DSError::InvalidHandle));
+                list.clear();
+                assert_eq!(list.get_by_handle(handles[4]), Err(DSError::InvalidHandle));
+            }
+        }
+
+        #[test]
+        fn test_equal_elements() {
+            #[derive(Debug, PartialEq)]
+            struct Record(u8, char);
+
+            impl PartialOrd for Record {
+                fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                    self.0.partial_cmp(&other.0)
+                }
+            }
+
+            for indexed in [false, true] {
+                let list = SortedList::new();
+                let mut list = if indexed { list.with_index() } else { list };
+                let handles: Vec<_> = [(1, 'a'), (2, 'b'), (2, 'c'), (2, 'd'), (3, 'e')]
+                    .into_iter()
+                    .map(|(key, name)| list.push_with_handle(Record(key, name)).unwrap())
+                    .collect();
+
+                assert_eq!(list.remove_by_handle(handles[2]), Ok(Record(2, 'c')));
+                assert_eq!(list.replace_by_handle(handles[3], Record(0, 'x')), Ok(Record(2, 'd')));
+                assert_eq!(list.remove_by_handle(handles[1]), Ok(Record(2, 'b')));
+                let names: Vec<_> = list.iter().map(|record| record.1).collect();
+                assert_eq!(names, ['x', 'a', 'e'], "indexed: {indexed}");
+                assert_eq!(list.check_invariants(), Ok(()));
+            }
+        }
+
+        #[test]
+        fn test_handles_follow_elements() {
+            for indexed in [false, true] {
+                let (mut list, handles) = setup(indexed);
+                let expired = list.expire_before(&30);
+                assert_eq!(expired.get_by_handle(handles[1]), Ok(&10));
+                assert_eq!(expired.get_by_handle(handles[3]), Ok(&20));
+                assert_eq!(list.get_by_handle(handles[1]), Err(DSError::InvalidHandle));
+                assert_eq!(list.get_by_handle(handles[4]), Ok(&30));
+                assert_eq!(expired.check_invariants(), Ok(()));
+                assert_eq!(list.check_invariants(), Ok(()));
+
+                let merged = SortedList::merge_all([list, expired, SortedList::from_vec(vec![25])]);
+                for (handle, value) in handles.iter().zip([50, 10, 40, 20, 30]) {
+                    assert_eq!(merged.get_by_handle(*handle), Ok(&value));
+                }
+                assert_eq!(merged.check_invariants(), Ok(()));
+            }
+        }
+
+        #[test]
+        fn test_bounded() {
+            let mut list = SortedList::bounded(2, Overflow::EvictFront);
+            let first = list.push_with_handle(1).unwrap();
+            let second = list.push_with_handle(2).unwrap();
+            let third = list.push_with_handle(3).unwrap();
+            assert_eq!(list.get_by_handle(first), Err(DSError::InvalidHandle), "evicted element");
+            assert_eq!(list.get_by_handle(second), Ok(&2));
+            assert_eq!(list.get_by_handle(third), Ok(&3), "new element reuses the evicted node");
+            let fourth = list.push_with_handle(0).unwrap();
+            assert_eq!(list.get_by_handle(fourth), Err(DSError::InvalidHandle), "new element is evicted itself");
+
+            let mut list = SortedList::bounded(2, Overflow::EvictBack).with_index();
+            let first = list.push_with_handle(1).unwrap();
+            let second = list.push_with_handle(3).unwrap();
+            let third = list.push_with_handle(2).unwrap();
+            assert_eq!(list.get_by_handle(first), Ok(&1));
+            assert_eq!(list.get_by_handle(second), Err(DSError::InvalidHandle), "evicted element");
+            assert_eq!(list.get_by_handle(third), Ok(&2));
+            assert_eq!(list.check_invariants(), Ok(()));
+
+            let mut list = SortedList::bounded(0, Overflow::EvictFront);
+            let handle = list.push_with_handle(1).unwrap();
+            assert_eq!(list.get_by_handle(handle), Err(DSError::InvalidHandle));
+
+            let mut list = SortedList::bounded(1, Overflow::Reject);
+            list.push(1);
+            assert_eq!(list.push_with_handle(2), Err(DSError::CapacityExceeded { capacity: 1 }));
+        }
+    }
+
+    mod panic_safety {
+        use super::*;
+        use drop_tracker::{DropItem, DropTracker};
+        use std::cell::Cell;
+        use std::cmp::Ordering;
+        use std::panic::{self, AssertUnwindSafe};
+
+        thread_local! {
+            // The number of comparisons of `Fragile` elements allowed before panic
+            static COMPARISONS_LEFT: Cell<usize> = const { Cell::new(usize::MAX) };
+        }
+
+        #[derive(Debug, PartialEq)]
+        struct Fragile(DropItem<u32>);
+
+        impl PartialOrd for Fragile {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                COMPARISONS_LEFT.with(|left| {
+                    assert!(left.get() > 0, "comparison budget is over");
+                    left.set(left.get() - 1);
+                });
+                self.0.partial_cmp(&other.0)
+            }
+        }
+
+        // Runs `f` with `limit` comparisons allowed and checks that it panics
+        fn assert_panics(limit: usize, f: impl FnOnce()) {
+            COMPARISONS_LEFT.with(|left| left.set(limit));
+            let result = panic::catch_unwind(AssertUnwindSafe(f));
+            COMPARISONS_LEFT.with(|left| left.set(usize::MAX));
+            assert!(result.is_err(), "comparison must panic with the limit {limit}");
+        }
+
+        fn setup(tracker: &mut DropTracker<u32>, indexed: bool) -> SortedList<Fragile> {
+            let list = SortedList::new();
+            let mut list = if indexed { list.with_index() } else { list };
+            for i in 0..10 {
+                list.push(Fragile(tracker.track(2 * i + 1)));
+            }
+            list
+        }
+
+        #[test]
+        fn test_push_with_panicking_comparison() {
+            for indexed in [false, true] {
+                for limit in [0, 1] {
+                    let mut tracker = DropTracker::new();
+                    let mut list = setup(&mut tracker, indexed);
+
+                    assert_panics(limit, || list.push(Fragile(tracker.track(10))));
+                    assert_eq!(list.check_invariants(), Ok(()), "list must be valid after panic in push()");
+                    assert_eq!(list.len(), 10);
+                    assert_eq!(tracker.alive().count(), 10, "new element must be dropped on panic");
+
+                    assert_panics(limit, || {
+                        let _ = list.update(4, |_| {});
+                    });
+                    assert_eq!(list.check_invariants(), Ok(()), "list must be valid after panic in update()");
+                    assert_eq!(list.len(), 9);
+                    assert_eq!(tracker.alive().count(), 9, "updated element must be dropped on panic");
+
+                    list.push(Fragile(tracker.track(0)));
+                    assert_eq!(list.select(9).map(|item| *item.0), Ok(19), "list must be usable after panic");
+                    drop(list);
+                    assert_eq!(tracker.alive().count(), 0);
+                }
+            }
+        }
+
+        #[test]
+        fn test_handles_with_panicking_comparison() {
+            for indexed in [false, true] {
+                let mut tracker = DropTracker::new();
+                let mut list = setup(&mut tracker, indexed);
+                let handles: Vec<_> = (10..13)
+                    .map(|i| list.push_with_handle(Fragile(tracker.track(2 * i + 1))).unwrap())
+                    .collect();
+
+                assert_panics(0, || {
+                    let _ = list.replace_by_handle(handles[0], Fragile(tracker.track(0)));
+                });
+                if indexed {
+                    let item = list.get_by_handle(handles[0]).map(|item| *item.0);
+                    assert_eq!(item, Ok(21), "the position search panics before the element is changed");
+                } else {
+                    assert_eq!(list.get_by_handle(handles[0]), Err(DSError::InvalidHandle), "element is dropped on panic");
+                }
+                assert_panics(3, || {
+                    list.update_where(|_| true, |_| {});
+                });
+                assert_eq!(list.check_invariants(), Ok(()), "registry must not keep the freed nodes");
+                for handle in &handles[1..] {
+                    if let Ok(item) = list.get_by_handle(*handle) {
+                        assert!(*item.0 > 20, "valid handle must refer to its element");
+                    }
+                }
+                drop(list);
+                assert_eq!(tracker.alive().count(), 0);
+            }
+        }
+
+        #[test]
+        fn test_extend_with_panicking_comparison() {
+            for equal_order in [EqualOrder::Fifo, EqualOrder::Lifo] {
+                for limit in [3, 15] {
+                    let mut tracker = DropTracker::new();
+                    let mut list = setup(&mut tracker, true).with_equal_order(equal_order);
+                    let items: Vec<_> = (0..10).map(|i| Fragile(tracker.track(20 - 2 * i))).collect();
+
+                    assert_panics(limit, || list.extend(items));
+                    assert_eq!(list.check_invariants(), Ok(()), "list must be valid after panic in extend()");
+                    assert_eq!(
+                        tracker.alive().count(),
+                        list.len(),
+                        "elements which are not in the list must be dropped"
+                    );
+                    let last = list.len() - 1;
+                    assert_eq!(list.select(last).ok(), list.last(), "index must be rebuilt after panic");
+                    drop(list);
+                    assert_eq!(tracker.alive().count(), 0);
+                }
+            }
+        }
+
+        #[test]
+        fn test_resort_with_panicking_comparison() {
+            for indexed in [false, true] {
+                let mut tracker = DropTracker::new();
+                let mut list = setup(&mut tracker, indexed);
+
+                assert_panics(4, || list.resort());
+                assert_eq!(list.len(), 10, "resort() must keep all elements on panic");
+                assert_eq!(list.iter().count(), 10);
+                for (i, item) in list.iter().enumerate() {
+                    assert_eq!(list.get(i), Ok(item), "positional access must be valid after panic");
+                }
+
+                list.resort();
+                assert_eq!(list.check_invariants(), Ok(()), "list must be usable after panic in resort()");
+                drop(list);
+                assert_eq!(tracker.alive().count(), 0);
+                assert_eq!(tracker.dropped().count(), 10);
+            }
+        }
+    }
+
+    mod invariants {
+        use super::*;
+
+        #[test]
+        fn test_invariants_after_mutations() {
+            let mut list = SortedList::bounded(8, Overflow::EvictBack);
+            list.extend([9, 2, 7, 4, 1]);
+            for i in [5, 3, 8, 0, 6] {
+                list.push(i);
+            }
+            list.update(2, |x| *x = 10).unwrap();
+            list.update_where(|x| x % 2 == 0, |x| *x += 1);
+            let _ = list.remove(1);
+            let _ = list.pop_back();
+            assert_eq!(list.check_invariants(), Ok(()));
+        }
+
+        #[test]
+        fn test_invariants_detect_broken_order() {
+            let mut list = SortedList::from_slice(&[1, 2, 3, 4]);
+            *list.get_mut(1).unwrap() = 5;
+            assert_eq!(list.check_invariants(), Err(InvariantViolation::NotSorted { index: 2 }));
+
+            list.resort();
+            assert_eq!(list.check_invariants(), Ok(()));
+        }
+    }
 }
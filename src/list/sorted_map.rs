@@ -0,0 +1,683 @@
+//! This module contains sorted map implementation.
+
+use std::borrow::Borrow;
+use std::ops::{Bound, RangeBounds};
+use std::ptr;
+
+use super::common::ListCommon;
+use super::node_one_link::{Iter, IterMut, Node};
+
+type MapNode<K, V> = Node<(K, V)>;
+
+/// An ordered map built on the singly-linked node chain of [`SortedList`](crate::SortedList).
+///
+/// The entries are kept sorted by key only, so values do not need to implement any
+/// comparison traits. Every key is contained in the map at most once.
+///
+/// **Efficiency**: lookup, insertion and removal are O(n) at worst; insertion of a key
+/// greater than all others, `first_key_value`, `last_key_value` and `pop_first` are O(1).
+///
+/// # Type Parameters
+/// * `K`: The type of keys. Must implement `Ord`.
+/// * `V`: The type of values.
+///
+/// # Examples
+/// ```
+/// use plain_ds::list::SortedMap;
+///
+/// let mut map = SortedMap::new();
+/// map.insert(3, "c");
+/// map.insert(1, "a");
+/// assert_eq!(map.insert(3, "C"), Some("c"));
+///
+/// *map.entry(2).or_insert("b") = "B";
+/// assert_eq!(map.keys().collect::<Vec<_>>(), vec![&1, &2, &3]);
+/// assert_eq!(map.first_key_value(), Some((&1, &"a")));
+/// ```
+pub struct SortedMap<K, V> {
+    list: ListCommon<(K, V)>,
+}
+
+impl<K: Ord, V> SortedMap<K, V> {
+    /// Creates empty sorted map.
+    pub fn new() -> Self {
+        Self {
+            list: ListCommon::new(),
+        }
+    }
+
+    /// Returns the number of entries in the map.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    /// Checks if the map is empty.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Checks if the map contains `key`.
+    ///
+    /// **Efficiency**: O(n) at worst
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.search(key).1
+    }
+
+    /// Returns the value by `key`.
+    ///
+    /// **Efficiency**: O(n) at worst
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match self.search(key) {
+            (prev, true) => Some(unsafe { &(*self.list.node_after(prev)).payload.1 }),
+            _ => None,
+        }
+    }
+
+    /// Returns the mutable value by `key`.
+    ///
+    /// **Efficiency**: O(n) at worst
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match self.search(key) {
+            (prev, true) => Some(unsafe { &mut (*self.list.node_after(prev)).payload.1 }),
+            _ => None,
+        }
+    }
+
+    /// Inserts the entry into the map.
+    /// If the map already contains `key`, replaces its value and returns the old one.
+    ///
+    /// **Efficiency**: O(n) at worst, O(1) if `key` is greater than all keys in the map
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.entry(key) {
+            MapEntry::Occupied(mut entry) => Some(entry.insert(value)),
+            MapEntry::Vacant(entry) => {
+                entry.insert(value);
+                None
+            }
+        }
+    }
+
+    /// Removes the entry by `key` and returns its value.
+    ///
+    /// **Efficiency**: O(n) at worst
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    /// Removes the entry by `key` and returns it.
+    ///
+    /// **Efficiency**: O(n) at worst
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match self.search(key) {
+            (prev, true) => Some(self.unlink(prev)),
+            _ => None,
+        }
+    }
+
+    /// Returns the entry by `key` for in-place manipulation.
+    ///
+    /// **Efficiency**: O(n) at worst
+    pub fn entry(&mut self, key: K) -> MapEntry<'_, K, V> {
+        match self.search(&key) {
+            (prev, true) => MapEntry::Occupied(OccupiedEntry { map: self, prev }),
+            (prev, false) => MapEntry::Vacant(VacantEntry {
+                map: self,
+                key,
+                prev,
+            }),
+        }
+    }
+
+    /// Returns the entry with the smallest key.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        self.list.head().map(|(key, value)| (key, value))
+    }
+
+    /// Returns the entry with the greatest key.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn last_key_value(&self) -> Option<(&K, &V)> {
+        self.list.last().map(|(key, value)| (key, value))
+    }
+
+    /// Removes the entry with the smallest key and returns it.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn pop_first(&mut self) -> Option<(K, V)> {
+        self.list.pop_front()
+    }
+
+    /// Returns an iterator over the entries, sorted by key.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        Iter::new(self.list.head).map(|(key, value)| (key, value))
+    }
+
+    /// Returns an iterator over the entries with mutable values, sorted by key.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&K, &mut V)> {
+        IterMut::new(self.list.head).map(|(key, value)| (&*key, value))
+    }
+
+    /// Returns an iterator over the keys, in sorted order.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    /// Returns an iterator over the values, in order of their keys.
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    /// Returns an iterator over the mutable values, in order of their keys.
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut V> {
+        self.iter_mut().map(|(_, value)| value)
+    }
+
+    /// Returns an iterator over the entries whose keys are in `range`, sorted by key.
+    ///
+    /// **Efficiency**: O(n) at worst
+    pub fn range<Q, R>(&self, range: R) -> impl Iterator<Item = (&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(start) => self.search(start).0,
+            Bound::Excluded(start) => match self.search(start) {
+                (prev, true) => self.list.node_after(prev),
+                (prev, false) => prev,
+            },
+            Bound::Unbounded => ptr::null_mut(),
+        };
+
+        Iter::new(self.list.node_after(start))
+            .take_while(move |(key, _)| match range.end_bound() {
+                Bound::Included(end) => key.borrow() <= end,
+                Bound::Excluded(end) => key.borrow() < end,
+                Bound::Unbounded => true,
+            })
+            .map(|(key, value)| (key, value))
+    }
+
+    /// Removes all entries from the map.
+    ///
+    /// **Efficiency**: O(n)
+    pub fn clear(&mut self) {
+        self.list = ListCommon::new();
+    }
+
+    // Finds the last node whose key is less than `key` (null, if there is no such node),
+    // and checks if the next node contains `key`.
+    fn search<Q>(&self, key: &Q) -> (*mut MapNode<K, V>, bool)
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        if self.list.last.is_null() {
+            return (ptr::null_mut(), false);
+        }
+        // Quick Case: the key is greater than all keys
+        if unsafe { (*self.list.last).payload.0.borrow() } < key {
+            return (self.list.last, false);
+        }
+
+        let mut prev = ptr::null_mut();
+        let mut current = self.list.head;
+        unsafe {
+            while (*current).payload.0.borrow() < key {
+                prev = current;
+                current = (*current).next;
+            }
+            (prev, (*current).payload.0.borrow() == key)
+        }
+    }
+
+    // Links a new node after `prev` (null `prev` means the head).
+    fn link_after(&mut self, prev: *mut MapNode<K, V>, entry: (K, V)) -> *mut MapNode<K, V> {
+        let node = Box::into_raw(Box::new(Node::new(entry)));
+        unsafe { (*node).next = self.list.node_after(prev) };
+        if prev.is_null() {
+            self.list.head = node;
+        } else {
+            unsafe { (*prev).next = node };
+        }
+        if prev == self.list.last {
+            self.list.last = node;
+        }
+        self.list.size += 1;
+        self.list.reset_finger();
+        node
+    }
+
+    // Removes the node which follows `prev` (null `prev` means the head).
+    fn unlink(&mut self, prev: *mut MapNode<K, V>) -> (K, V) {
+        let node = unsafe { Box::from_raw(self.list.node_after(prev)) };
+        if prev.is_null() {
+            self.list.head = node.next;
+        } else {
+            unsafe { (*prev).next = node.next };
+        }
+        if node.next.is_null() {
+            self.list.last = prev;
+        }
+        self.list.size -= 1;
+        self.list.reset_finger();
+        node.payload
+    }
+}
+
+impl<K: Ord, V> Default for SortedMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A view into a single entry of the [`SortedMap`], which may either be vacant or occupied.
+pub enum MapEntry<'m, K: Ord, V> {
+    /// The map contains the key.
+    Occupied(OccupiedEntry<'m, K, V>),
+    /// The map does not contain the key.
+    Vacant(VacantEntry<'m, K, V>),
+}
+
+impl<'m, K: Ord, V> MapEntry<'m, K, V> {
+    /// Returns the key of the entry.
+    pub fn key(&self) -> &K {
+        match self {
+            MapEntry::Occupied(entry) => entry.key(),
+            MapEntry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    /// Inserts `default` value if the entry is vacant, and returns the mutable value.
+    pub fn or_insert(self, default: V) -> &'m mut V {
+        self.or_insert_with(|| default)
+    }
+
+    /// Inserts the result of `default` if the entry is vacant, and returns the mutable value.
+    pub fn or_insert_with(self, default: impl FnOnce() -> V) -> &'m mut V {
+        match self {
+            MapEntry::Occupied(entry) => entry.into_mut(),
+            MapEntry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Inserts the default value if the entry is vacant, and returns the mutable value.
+    pub fn or_default(self) -> &'m mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+
+    /// Modifies the value if the entry is occupied.
+    pub fn and_modify(mut self, f: impl FnOnce(&mut V)) -> Self {
+        if let MapEntry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+}
+
+/// An occupied entry of the [`SortedMap`].
+pub struct OccupiedEntry<'m, K: Ord, V> {
+    map: &'m mut SortedMap<K, V>,
+    prev: *mut MapNode<K, V>,
+}
+
+impl<'m, K: Ord, V> OccupiedEntry<'m, K, V> {
+    /// Returns the key of the entry.
+    pub fn key(&self) -> &K {
+        unsafe { &(*self.node()).payload.0 }
+    }
+
+    /// Returns the value of the entry.
+    pub fn get(&self) -> &V {
+        unsafe { &(*self.node()).payload.1 }
+    }
+
+    /// Returns the mutable value of the entry.
+    pub fn get_mut(&mut self) -> &mut V {
+        unsafe { &mut (*self.node()).payload.1 }
+    }
+
+    /// Converts the entry into the mutable value with the lifetime of the map.
+    pub fn into_mut(self) -> &'m mut V {
+        unsafe { &mut (*self.node()).payload.1 }
+    }
+
+    /// Replaces the value of the entry and returns the old one.
+    pub fn insert(&mut self, value: V) -> V {
+        std::mem::replace(self.get_mut(), value)
+    }
+
+    /// Removes the entry from the map and returns its value.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn remove(self) -> V {
+        self.map.unlink(self.prev).1
+    }
+
+    fn node(&self) -> *mut MapNode<K, V> {
+        self.map.list.node_after(self.prev)
+    }
+}
+
+/// A vacant entry of the [`SortedMap`].
+pub struct VacantEntry<'m, K: Ord, V> {
+    map: &'m mut SortedMap<K, V>,
+    key: K,
+    prev: *mut MapNode<K, V>,
+}
+
+impl<'m, K: Ord, V> VacantEntry<'m, K, V> {
+    /// Returns the key of the entry.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Inserts the value into the map at the position of the entry, and returns
+    /// the mutable value.
+    ///
+    /// **Efficiency**: O(1)
+    pub fn insert(self, value: V) -> &'m mut V {
+        let node = self.map.link_after(self.prev, (self.key, value));
+        unsafe { &mut (*node).payload.1 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup_map(keys: &[i32]) -> SortedMap<i32, String> {
+        let mut map = SortedMap::new();
+        for key in keys {
+            map.insert(*key, key.to_string());
+        }
+        map
+    }
+
+    fn keys(map: &SortedMap<i32, String>) -> Vec<i32> {
+        map.keys().copied().collect()
+    }
+
+    #[test]
+    fn test_creation() {
+        let map: SortedMap<i32, i32> = SortedMap::new();
+        assert_eq!(map.len(), 0, "not zero length after creation");
+        assert!(map.is_empty(), "not empty after creation");
+        assert_eq!(map.first_key_value(), None);
+        assert_eq!(map.last_key_value(), None);
+    }
+
+    mod insert {
+        use super::*;
+
+        #[test]
+        fn test_insert_keeps_key_order() {
+            let map = setup_map(&[5, 1, 4, 2, 3]);
+            assert_eq!(map.len(), 5, "bad length after insert()");
+            assert_eq!(keys(&map), [1, 2, 3, 4, 5], "entries must be sorted by key");
+            assert_eq!(map.first_key_value(), Some((&1, &"1".to_string())));
+            assert_eq!(map.last_key_value(), Some((&5, &"5".to_string())));
+        }
+
+        #[test]
+        fn test_insert_existing_key_returns_old_value() {
+            let mut map = setup_map(&[1, 2, 3]);
+            assert_eq!(map.insert(2, "two".to_string()), Some("2".to_string()));
+            assert_eq!(map.len(), 3, "replacing must not change the size");
+            assert_eq!(map.get(&2), Some(&"two".to_string()));
+        }
+
+        #[test]
+        fn test_insert_positions() {
+            let mut map = setup_map(&[20, 40]);
+            assert_eq!(map.insert(10, "head".to_string()), None);
+            assert_eq!(map.insert(50, "last".to_string()), None);
+            assert_eq!(map.insert(30, "middle".to_string()), None);
+            assert_eq!(keys(&map), [10, 20, 30, 40, 50]);
+            assert_eq!(map.last_key_value().map(|(key, _)| *key), Some(50));
+        }
+
+        #[test]
+        fn test_values_without_ordering() {
+            struct Opaque(u8);
+
+            let mut map = SortedMap::new();
+            map.insert("b", Opaque(2));
+            map.insert("a", Opaque(1));
+            assert_eq!(
+                map.values().map(|value| value.0).collect::<Vec<_>>(),
+                [1, 2]
+            );
+        }
+    }
+
+    mod get_remove {
+        use super::*;
+
+        #[test]
+        fn test_get() {
+            let map = setup_map(&[1, 3, 5]);
+            assert_eq!(map.get(&3), Some(&"3".to_string()));
+            assert_eq!(map.get(&0), None, "key smaller than all keys");
+            assert_eq!(map.get(&4), None, "key between keys");
+            assert_eq!(map.get(&6), None, "key greater than all keys");
+            assert!(map.contains_key(&5));
+            assert!(!map.contains_key(&2));
+        }
+
+        #[test]
+        fn test_get_by_borrowed_key() {
+            let mut map = SortedMap::new();
+            map.insert("beta".to_string(), 2);
+            map.insert("alpha".to_string(), 1);
+            assert_eq!(map.get("alpha"), Some(&1));
+            assert_eq!(map.remove("beta"), Some(2));
+        }
+
+        #[test]
+        fn test_get_mut() {
+            let mut map = setup_map(&[1, 2]);
+            map.get_mut(&2).unwrap().push('!');
+            assert_eq!(map.get(&2), Some(&"2!".to_string()));
+            assert_eq!(map.get_mut(&7), None);
+        }
+
+        #[test]
+        fn test_remove_positions() {
+            let mut map = setup_map(&[1, 2, 3, 4, 5]);
+            assert_eq!(map.remove(&1), Some("1".to_string())); // head
+            assert_eq!(map.remove(&3), Some("3".to_string())); // middle
+            assert_eq!(map.remove(&5), Some("5".to_string())); // last
+            assert_eq!(map.remove(&5), None);
+            assert_eq!(keys(&map), [2, 4]);
+            assert_eq!(map.len(), 2, "size must decrease after remove()");
+            assert_eq!(
+                map.last_key_value().map(|(key, _)| *key),
+                Some(4),
+                "last must be updated"
+            );
+
+            map.insert(6, "6".to_string());
+            assert_eq!(
+                keys(&map),
+                [2, 4, 6],
+                "map must be consistent after removals"
+            );
+        }
+
+        #[test]
+        fn test_pop_first_and_clear() {
+            let mut map = setup_map(&[2, 1]);
+            assert_eq!(map.pop_first(), Some((1, "1".to_string())));
+            map.clear();
+            assert!(map.is_empty(), "map should be empty after clear()");
+            assert_eq!(map.pop_first(), None);
+        }
+    }
+
+    mod entry {
+        use super::*;
+
+        #[test]
+        fn test_entry_or_insert() {
+            let mut map: SortedMap<&str, i32> = SortedMap::new();
+            for word in ["b", "a", "b", "c", "b"] {
+                *map.entry(word).or_insert(0) += 1;
+            }
+            assert_eq!(
+                map.iter().collect::<Vec<_>>(),
+                [(&"a", &1), (&"b", &3), (&"c", &1)]
+            );
+        }
+
+        #[test]
+        fn test_entry_or_default_and_modify() {
+            let mut map: SortedMap<i32, Vec<i32>> = SortedMap::new();
+            map.entry(1).or_default().push(10);
+            map.entry(1).and_modify(|value| value.push(11)).or_default();
+            map.entry(0).and_modify(|value| value.push(0)).or_default();
+            assert_eq!(map.get(&1), Some(&vec![10, 11]));
+            assert_eq!(
+                map.get(&0),
+                Some(&vec![]),
+                "and_modify() must not touch vacant entry"
+            );
+        }
+
+        #[test]
+        fn test_occupied_entry() {
+            let mut map = setup_map(&[1, 2, 3]);
+            match map.entry(2) {
+                MapEntry::Occupied(mut entry) => {
+                    assert_eq!(entry.key(), &2);
+                    assert_eq!(entry.get(), "2");
+                    assert_eq!(entry.insert("two".to_string()), "2");
+                    assert_eq!(entry.remove(), "two");
+                }
+                MapEntry::Vacant(_) => panic!("entry must be occupied"),
+            }
+            assert_eq!(keys(&map), [1, 3]);
+        }
+
+        #[test]
+        fn test_vacant_entry() {
+            let mut map = setup_map(&[1, 3]);
+            match map.entry(2) {
+                MapEntry::Vacant(entry) => {
+                    assert_eq!(entry.key(), &2);
+                    entry.insert("2".to_string()).push('!');
+                }
+                MapEntry::Occupied(_) => panic!("entry must be vacant"),
+            }
+            assert_eq!(keys(&map), [1, 2, 3]);
+            assert_eq!(map.get(&2), Some(&"2!".to_string()));
+        }
+    }
+
+    mod iterators {
+        use super::*;
+
+        #[test]
+        fn test_keys_values() {
+            let mut map = setup_map(&[3, 1, 2]);
+            assert_eq!(map.keys().collect::<Vec<_>>(), [&1, &2, &3]);
+            for value in map.values_mut() {
+                value.push('x');
+            }
+            assert_eq!(
+                map.values().cloned().collect::<Vec<_>>(),
+                ["1x", "2x", "3x"]
+            );
+        }
+
+        #[test]
+        fn test_range() {
+            let map = setup_map(&[1, 3, 5, 7, 9]);
+            let range_keys = |items: Vec<(&i32, &String)>| {
+                items.into_iter().map(|(key, _)| *key).collect::<Vec<_>>()
+            };
+
+            assert_eq!(range_keys(map.range(3..7).collect()), [3, 5]);
+            assert_eq!(range_keys(map.range(3..=7).collect()), [3, 5, 7]);
+            assert_eq!(range_keys(map.range(4..).collect()), [5, 7, 9]);
+            assert_eq!(range_keys(map.range(..4).collect()), [1, 3]);
+            assert_eq!(range_keys(map.range(..).collect()), [1, 3, 5, 7, 9]);
+            assert_eq!(
+                range_keys(
+                    map.range((Bound::Excluded(3), Bound::Included(9)))
+                        .collect()
+                ),
+                [5, 7, 9]
+            );
+            assert_eq!(
+                range_keys(map.range((Bound::Excluded(4), Bound::Unbounded)).collect()),
+                [5, 7, 9]
+            );
+            assert!(
+                map.range(10..).next().is_none(),
+                "range after all keys must be empty"
+            );
+            assert!(
+                map.range(4..5).next().is_none(),
+                "range between keys must be empty"
+            );
+        }
+    }
+
+    mod memory_leaks {
+        use super::*;
+        use drop_tracker::DropTracker;
+
+        #[test]
+        fn test_memory_leaks() {
+            let mut tracker = DropTracker::new();
+
+            let mut map = SortedMap::new();
+            for i in [5, 3, 8, 1, 9, 2] {
+                map.insert(i, tracker.track(i));
+            }
+            map.insert(5, tracker.track(50));
+            let _ = map.remove(&3);
+            let _ = map.pop_first();
+            assert_eq!(
+                tracker.alive().count(),
+                4,
+                "replaced and removed values must be dropped"
+            );
+
+            drop(map);
+            assert_eq!(tracker.alive().count(), 0);
+            assert_eq!(tracker.dropped().count(), 7);
+        }
+    }
+}
@@ -0,0 +1,417 @@
+//! This module contains the span index of `SortedList`: levels of express links over
+//! the list nodes (as in an indexable skip list), which make positional access O(log n).
+
+use super::common::ListCommon;
+use super::node_one_link::Node;
+use crate::core::InvariantViolation;
+use std::collections::HashMap;
+use std::ptr;
+
+// The maximum number of index levels: it is enough for 4^16 elements
+const MAX_HEIGHT: usize = 16;
+
+// Seed of the pseudo-random generator of tower heights
+const SEED: u64 = 0x2545_F491_4F6C_DD1D;
+
+// Express link to the next tower on some level.
+// `span` is the number of list positions it moves forward: the head is at position 0,
+// the element with index `i` is at position `i + 1` and the end of the list is at `len + 1`.
+struct Link<T> {
+    next: *mut Tower<T>,
+    span: usize,
+}
+
+// Express links above a list node. The head tower has null node.
+struct Tower<T> {
+    node: *mut Node<T>,
+    links: Vec<Link<T>>,
+}
+
+// The last tower (and its position) on every level before the searched place
+type Path<T> = [(*mut Tower<T>, usize); MAX_HEIGHT];
+
+/// Span index over the nodes of a sorted list: towers of express links with the number
+/// of skipped nodes (span counts), so a node is found by its position or value
+/// in O(log n) expected time.
+///
+/// The index does not own the list nodes. While the index exists, the list nodes must be
+/// relinked only by `insert` and `remove`, otherwise the index must be rebuilt.
+pub struct SpanIndex<T> {
+    head: *mut Tower<T>,
+    rng: u64,
+}
+
+impl<T> SpanIndex<T> {
+    /// Builds the index over the nodes of the list.
+    ///
+    /// **Efficiency**: O(n)
+    pub fn build(state: &ListCommon<T>) -> Self {
+        let mut index = Self {
+            head: Box::into_raw(Box::new(Tower {
+                node: ptr::null_mut(),
+                links: Vec::new(),
+            })),
+            rng: SEED,
+        };
+
+        // The last tower on every level
+        let mut tails: Vec<(*mut Tower<T>, usize)> = Vec::new();
+        let mut node = state.head;
+        let mut position = 1;
+        unsafe {
+            while !node.is_null() {
+                let height = index.random_height();
+                if height > 0 {
+                    let tower = Box::into_raw(Box::new(Tower {
+                        node,
+                        links: (0..height).map(|_| Link::end(0)).collect(),
+                    }));
+                    for level in 0..height {
+                        if level == tails.len() {
+                            (*index.head).links.push(Link::end(0));
+                            tails.push((index.head, 0));
+                        }
+                        let (tail, tail_position) = tails[level];
+                        (&mut (*tail).links)[level] = Link {
+                            next: tower,
+                            span: position - tail_position,
+                        };
+                        tails[level] = (tower, position);
+                    }
+                }
+                node = (*node).next;
+                position += 1;
+            }
+
+            for (level, (tail, tail_position)) in tails.into_iter().enumerate() {
+                (&mut (*tail).links)[level] = Link::end(position - tail_position);
+            }
+        }
+        index
+    }
+
+    /// Returns the node by valid index.
+    ///
+    /// **Efficiency**: O(log n) expected
+    pub fn node_at(&self, state: &ListCommon<T>, index: usize) -> *mut Node<T> {
+        let target = index + 1;
+        let (tower, position) = self.search(|next_position, _| next_position <= target)[0];
+        let mut node = unsafe { (*tower).node };
+        for _ in position..target {
+            node = state.node_after(node);
+        }
+        node
+    }
+
+    /// Returns the number of the leading elements which satisfy the predicate.
+    /// The predicate must be true for a prefix of the list and false for the rest of it.
+    ///
+    /// **Efficiency**: O(log n) expected
+    pub fn count_while(&self, state: &ListCommon<T>, predicate: impl Fn(&T) -> bool) -> usize {
+        let (tower, mut position) = self.search(|_, payload| predicate(payload))[0];
+        unsafe {
+            let mut node = state.node_after((*tower).node);
+            while !node.is_null() && predicate(&(*node).payload) {
+                position += 1;
+                node = (*node).next;
+            }
+        }
+        position
+    }
+
+    /// Links the node to the list before the first element for which
+    /// `goes_before(new, existing)` is true, or to the end of the list.
+    ///
+    /// **Efficiency**: O(log n) expected
+    pub fn insert(
+        &mut self,
+        state: &mut ListCommon<T>,
+        node: *mut Node<T>,
+        goes_before: impl Fn(&T, &T) -> bool,
+    ) {
+        unsafe {
+            let payload = &(*node).payload;
+            let path = self.search(|_, existing| !goes_before(payload, existing));
+            let (tower, mut position) = path[0];
+            let mut prev = (*tower).node;
+            loop {
+                let next = state.node_after(prev);
+                if next.is_null() || goes_before(payload, &(*next).payload) {
+                    break;
+                }
+                prev = next;
+                position += 1;
+            }
+
+            (*node).next = state.node_after(prev);
+            if prev.is_null() {
+                state.head = node;
+            } else {
+                (*prev).next = node;
+            }
+            if (*node).next.is_null() {
+                state.last = node;
+            }
+            state.size += 1;
+            state.reset_finger();
+
+            // Position of the new node
+            let position = position + 1;
+            let height = self.random_height();
+            while self.levels() < height {
+                // The spans are adjusted below as if the new node was not linked yet
+                (*self.head).links.push(Link::end(state.len()));
+            }
+            let tower = if height > 0 {
+                Box::into_raw(Box::new(Tower {
+                    node,
+                    links: Vec::with_capacity(height),
+                }))
+            } else {
+                ptr::null_mut()
+            };
+
+            for (level, &(prev_tower, prev_position)) in path.iter().enumerate().take(self.levels()) {
+                let link = &mut (&mut (*prev_tower).links)[level];
+                if level < height {
+                    (*tower).links.push(Link {
+                        next: link.next,
+                        span: prev_position + link.span + 1 - position,
+                    });
+                    *link = Link {
+                        next: tower,
+                        span: position - prev_position,
+                    };
+                } else {
+                    link.span += 1;
+                }
+            }
+        }
+    }
+
+    /// Unlinks the node with valid index from the list and returns it.
+    ///
+    /// **Efficiency**: O(log n) expected
+    pub fn remove(&mut self, state: &mut ListCommon<T>, index: usize) -> *mut Node<T> {
+        let target = index + 1;
+        let path = self.search(|next_position, _| next_position < target);
+        unsafe {
+            let (tower, position) = path[0];
+            let mut prev = (*tower).node;
+            for _ in position + 1..target {
+                prev = state.node_after(prev);
+            }
+
+            let node = state.node_after(prev);
+            if prev.is_null() {
+                state.head = (*node).next;
+            } else {
+                (*prev).next = (*node).next;
+            }
+            if (*node).next.is_null() {
+                state.last = prev;
+            }
+            (*node).next = ptr::null_mut();
+            state.size -= 1;
+            state.reset_finger();
+
+            let mut removed = ptr::null_mut();
+            for (level, &(prev_tower, _)) in path.iter().enumerate().take(self.levels()) {
+                let link = &mut (&mut (*prev_tower).links)[level];
+                if !link.next.is_null() && (*link.next).node == node {
+                    removed = link.next;
+                    let removed_link = &(&(*removed).links)[level];
+                    *link = Link {
+                        next: removed_link.next,
+                        span: link.span + removed_link.span - 1,
+                    };
+                } else {
+                    link.span -= 1;
+                }
+            }
+            if !removed.is_null() {
+                drop(Box::from_raw(removed));
+            }
+            node
+        }
+    }
+
+    /// Checks that every express link points to the tower of the node
+    /// at the position defined by its span. The list itself must be valid.
+    ///
+    /// **Efficiency**: O(n)
+    pub fn check_invariants(&self, state: &ListCommon<T>) -> Result<(), InvariantViolation> {
+        let mut positions = HashMap::with_capacity(state.len());
+        let mut node = state.head;
+        while !node.is_null() {
+            positions.insert(node, positions.len() + 1);
+            node = unsafe { (*node).next };
+        }
+
+        for level in 0..self.levels() {
+            let (mut tower, mut position) = (self.head, 0);
+            loop {
+                let links = unsafe { &(*tower).links };
+                let link = match links.get(level) {
+                    Some(link) if link.span > 0 => link,
+                    _ => return Err(InvariantViolation::WrongSpan { level }),
+                };
+                let next_position = position + link.span;
+                if link.next.is_null() {
+                    if next_position != state.len() + 1 {
+                        return Err(InvariantViolation::WrongSpan { level });
+                    }
+                    break;
+                }
+                if positions.get(&unsafe { (*link.next).node }) != Some(&next_position) {
+                    return Err(InvariantViolation::WrongSpan { level });
+                }
+                (tower, position) = (link.next, next_position);
+            }
+        }
+        Ok(())
+    }
+
+    // Returns the number of index levels.
+    fn levels(&self) -> usize {
+        unsafe { (*self.head).links.len() }
+    }
+
+    // Walks the towers from the highest level down, moving forward while the next tower
+    // satisfies `advance(position, payload)`. Returns the last visited tower on every level
+    // (and the head for the levels above the highest one).
+    fn search(&self, mut advance: impl FnMut(usize, &T) -> bool) -> Path<T> {
+        let mut path = [(self.head, 0); MAX_HEIGHT];
+        let (mut tower, mut position) = (self.head, 0);
+        unsafe {
+            for level in (0..self.levels()).rev() {
+                loop {
+                    let link = &(&(*tower).links)[level];
+                    if link.next.is_null() || !advance(position + link.span, &(*(*link.next).node).payload) {
+                        break;
+                    }
+                    position += link.span;
+                    tower = link.next;
+                }
+                path[level] = (tower, position);
+            }
+        }
+        path
+    }
+
+    // Returns the height of a new tower: every next level is taken with probability 1/4.
+    fn random_height(&mut self) -> usize {
+        // xorshift64
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        ((self.rng.trailing_zeros() / 2) as usize).min(MAX_HEIGHT)
+    }
+}
+
+impl<T> Link<T> {
+    // Link to the end of the list
+    fn end(span: usize) -> Self {
+        Self {
+            next: ptr::null_mut(),
+            span,
+        }
+    }
+}
+
+impl<T> Drop for SpanIndex<T> {
+    fn drop(&mut self) {
+        // Every tower is linked on the lowest level
+        let mut tower = self.head;
+        while !tower.is_null() {
+            let tower_box = unsafe { Box::from_raw(tower) };
+            tower = tower_box.links.first().map_or(ptr::null_mut(), |link| link.next);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup(values: &[i32]) -> (ListCommon<i32>, SpanIndex<i32>) {
+        let mut state = ListCommon::new();
+        for value in values {
+            state.push_back(*value);
+        }
+        let index = SpanIndex::build(&state);
+        (state, index)
+    }
+
+    fn insert(state: &mut ListCommon<i32>, index: &mut SpanIndex<i32>, value: i32) {
+        let node = Box::into_raw(Box::new(Node::new(value)));
+        index.insert(state, node, |new, existing| new < existing);
+    }
+
+    fn remove(state: &mut ListCommon<i32>, index: &mut SpanIndex<i32>, position: usize) -> i32 {
+        unsafe { Box::from_raw(index.remove(state, position)).payload }
+    }
+
+    #[test]
+    fn test_build() {
+        for len in [0, 1, 2, 100, 1000] {
+            let values: Vec<i32> = (0..len).collect();
+            let (state, index) = setup(&values);
+            assert_eq!(index.check_invariants(&state), Ok(()), "len = {len}");
+            for i in 0..len as usize {
+                assert_eq!(unsafe { (*index.node_at(&state, i)).payload }, i as i32);
+            }
+        }
+    }
+
+    #[test]
+    fn test_count_while() {
+        let (state, index) = setup(&(0..500).map(|i| i * 2).collect::<Vec<_>>());
+        assert_eq!(index.count_while(&state, |value| *value < 0), 0);
+        assert_eq!(index.count_while(&state, |value| *value < 301), 151);
+        assert_eq!(index.count_while(&state, |value| *value < 302), 151);
+        assert_eq!(index.count_while(&state, |_| true), 500);
+    }
+
+    #[test]
+    fn test_insert_and_remove() {
+        let (mut state, mut index) = setup(&[]);
+        let mut expected = Vec::new();
+        for i in 0..1000 {
+            let value = (i * 7919) % 1009;
+            insert(&mut state, &mut index, value);
+            let position = expected.partition_point(|existing| *existing <= value);
+            expected.insert(position, value);
+        }
+        assert_eq!(index.check_invariants(&state), Ok(()));
+        assert_eq!(state.to_vec(), expected);
+
+        for i in 0..900 {
+            let position = (i * 31) % expected.len();
+            assert_eq!(remove(&mut state, &mut index, position), expected.remove(position));
+        }
+        assert_eq!(index.check_invariants(&state), Ok(()));
+        assert_eq!(state.check_invariants(), Ok(()));
+        assert_eq!(state.to_vec(), expected);
+        for (i, value) in expected.iter().enumerate() {
+            assert_eq!(unsafe { (*index.node_at(&state, i)).payload }, *value);
+        }
+
+        while !expected.is_empty() {
+            assert_eq!(remove(&mut state, &mut index, expected.len() - 1), expected.pop().unwrap());
+        }
+        assert_eq!(index.check_invariants(&state), Ok(()));
+        assert_eq!(state.len(), 0);
+        assert!(state.last.is_null());
+    }
+
+    #[test]
+    fn test_detects_stale_index() {
+        let (mut state, index) = setup(&(0..100).collect::<Vec<_>>());
+        state.push_back(100);
+        assert!(
+            matches!(index.check_invariants(&state), Err(InvariantViolation::WrongSpan { .. })),
+            "index must be stale after relinking bypassing it"
+        );
+    }
+}
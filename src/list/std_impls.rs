@@ -0,0 +1,384 @@
+//! This module contains implementations of the [`List`] trait for std collections
+//! (`std-lists` feature), so generic code may run against them.
+//!
+//! Note that with `List` in scope, the trait methods shadow some slice methods of `Vec`
+//! (`get`, `last`, `iter`) and make `into_iter()` ambiguous, so the feature is not enabled
+//! by default. Use the explicit syntax (`List::get(&vec, 0)`) where it matters.
+
+use std::collections::{LinkedList, VecDeque};
+
+use super::api::List;
+use crate::core::{DSError, Result};
+
+impl<T> List<T> for Vec<T> {
+    /// Returns the vector length.
+    ///
+    /// **Efficiency**: O(1)
+    fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    /// Returns the first element.
+    ///
+    /// **Efficiency**: O(1)
+    fn head(&self) -> Option<&T> {
+        self.as_slice().first()
+    }
+
+    /// Returns the last element.
+    ///
+    /// **Efficiency**: O(1)
+    fn last(&self) -> Option<&T> {
+        self.as_slice().last()
+    }
+
+    /// Returns an element by index, or error if index out of bounds.
+    ///
+    /// **Efficiency**: O(1)
+    fn get(&self, index: usize) -> Result<&T> {
+        let len = self.as_slice().len();
+        match self.as_slice().get(index) {
+            Some(item) => Ok(item),
+            None => Err(DSError::IndexOutOfBounds { index, len }),
+        }
+    }
+
+    /// Returns a mutable element by index, or error if index out of bounds.
+    ///
+    /// **Efficiency**: O(1)
+    fn get_mut(&mut self, index: usize) -> Result<&mut T> {
+        let len = self.as_slice().len();
+        match self.as_mut_slice().get_mut(index) {
+            Some(item) => Ok(item),
+            None => Err(DSError::IndexOutOfBounds { index, len }),
+        }
+    }
+
+    /// Returns an iterator over the immutable elements.
+    fn iter<'a>(&'a self) -> impl Iterator<Item = &'a T>
+    where
+        T: 'a,
+    {
+        self.as_slice().iter()
+    }
+
+    /// Returns an iterator over the mutable elements.
+    fn iter_mut<'a>(&'a mut self) -> impl Iterator<Item = &'a mut T>
+    where
+        T: 'a,
+    {
+        self.as_mut_slice().iter_mut()
+    }
+
+    /// Returns an iterator that consumes the vector.
+    fn into_iter(self) -> impl Iterator<Item = T> {
+        IntoIterator::into_iter(self)
+    }
+
+    /// Adds a new element to the end of the vector.
+    ///
+    /// **Efficiency**: O(1) amortized
+    fn push(&mut self, payload: T) {
+        Vec::push(self, payload);
+    }
+
+    /// Removes the last element and returns it.
+    ///
+    /// **Efficiency**: O(1)
+    fn pop_back(&mut self) -> Option<T> {
+        Vec::pop(self)
+    }
+
+    /// Removes the first element and returns it.
+    ///
+    /// **Efficiency**: O(n)
+    fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(Vec::remove(self, 0))
+        }
+    }
+
+    /// Removes an element by index, or returns error if index out of bounds.
+    ///
+    /// **Efficiency**: O(n)
+    fn remove(&mut self, index: usize) -> Result<T> {
+        let len = self.as_slice().len();
+        if index >= len {
+            return Err(DSError::IndexOutOfBounds { index, len });
+        }
+        Ok(Vec::remove(self, index))
+    }
+
+    /// Removes all elements.
+    ///
+    /// **Efficiency**: O(n)
+    fn clear(&mut self) {
+        Vec::clear(self);
+    }
+}
+
+impl<T> List<T> for VecDeque<T> {
+    /// Returns the deque length.
+    ///
+    /// **Efficiency**: O(1)
+    fn len(&self) -> usize {
+        VecDeque::len(self)
+    }
+
+    /// Returns the first element.
+    ///
+    /// **Efficiency**: O(1)
+    fn head(&self) -> Option<&T> {
+        self.front()
+    }
+
+    /// Returns the last element.
+    ///
+    /// **Efficiency**: O(1)
+    fn last(&self) -> Option<&T> {
+        self.back()
+    }
+
+    /// Returns an element by index, or error if index out of bounds.
+    ///
+    /// **Efficiency**: O(1)
+    fn get(&self, index: usize) -> Result<&T> {
+        match VecDeque::get(self, index) {
+            Some(item) => Ok(item),
+            None => Err(DSError::IndexOutOfBounds {
+                index,
+                len: VecDeque::len(self),
+            }),
+        }
+    }
+
+    /// Returns a mutable element by index, or error if index out of bounds.
+    ///
+    /// **Efficiency**: O(1)
+    fn get_mut(&mut self, index: usize) -> Result<&mut T> {
+        let len = VecDeque::len(self);
+        match VecDeque::get_mut(self, index) {
+            Some(item) => Ok(item),
+            None => Err(DSError::IndexOutOfBounds { index, len }),
+        }
+    }
+
+    /// Returns an iterator over the immutable elements.
+    fn iter<'a>(&'a self) -> impl Iterator<Item = &'a T>
+    where
+        T: 'a,
+    {
+        VecDeque::iter(self)
+    }
+
+    /// Returns an iterator over the mutable elements.
+    fn iter_mut<'a>(&'a mut self) -> impl Iterator<Item = &'a mut T>
+    where
+        T: 'a,
+    {
+        VecDeque::iter_mut(self)
+    }
+
+    /// Returns an iterator that consumes the deque.
+    fn into_iter(self) -> impl Iterator<Item = T> {
+        IntoIterator::into_iter(self)
+    }
+
+    /// Adds a new element to the back of the deque.
+    ///
+    /// **Efficiency**: O(1) amortized
+    fn push(&mut self, payload: T) {
+        self.push_back(payload);
+    }
+
+    /// Removes the last element and returns it.
+    ///
+    /// **Efficiency**: O(1)
+    fn pop_back(&mut self) -> Option<T> {
+        VecDeque::pop_back(self)
+    }
+
+    /// Removes the first element and returns it.
+    ///
+    /// **Efficiency**: O(1)
+    fn pop_front(&mut self) -> Option<T> {
+        VecDeque::pop_front(self)
+    }
+
+    /// Removes an element by index, or returns error if index out of bounds.
+    ///
+    /// **Efficiency**: O(min(i, n - i))
+    fn remove(&mut self, index: usize) -> Result<T> {
+        let len = VecDeque::len(self);
+        VecDeque::remove(self, index).ok_or(DSError::IndexOutOfBounds { index, len })
+    }
+
+    /// Removes all elements.
+    ///
+    /// **Efficiency**: O(n)
+    fn clear(&mut self) {
+        VecDeque::clear(self);
+    }
+}
+
+impl<T> List<T> for LinkedList<T> {
+    /// Returns the list length.
+    ///
+    /// **Efficiency**: O(1)
+    fn len(&self) -> usize {
+        LinkedList::len(self)
+    }
+
+    /// Returns the first element.
+    ///
+    /// **Efficiency**: O(1)
+    fn head(&self) -> Option<&T> {
+        self.front()
+    }
+
+    /// Returns the last element.
+    ///
+    /// **Efficiency**: O(1)
+    fn last(&self) -> Option<&T> {
+        self.back()
+    }
+
+    /// Returns an iterator over the immutable elements.
+    fn iter<'a>(&'a self) -> impl Iterator<Item = &'a T>
+    where
+        T: 'a,
+    {
+        LinkedList::iter(self)
+    }
+
+    /// Returns an iterator over the mutable elements.
+    fn iter_mut<'a>(&'a mut self) -> impl Iterator<Item = &'a mut T>
+    where
+        T: 'a,
+    {
+        LinkedList::iter_mut(self)
+    }
+
+    /// Returns an iterator that consumes the list.
+    fn into_iter(self) -> impl Iterator<Item = T> {
+        IntoIterator::into_iter(self)
+    }
+
+    /// Adds a new element to the back of the list.
+    ///
+    /// **Efficiency**: O(1)
+    fn push(&mut self, payload: T) {
+        self.push_back(payload);
+    }
+
+    /// Removes the last element and returns it.
+    ///
+    /// **Efficiency**: O(1)
+    fn pop_back(&mut self) -> Option<T> {
+        LinkedList::pop_back(self)
+    }
+
+    /// Removes the first element and returns it.
+    ///
+    /// **Efficiency**: O(1)
+    fn pop_front(&mut self) -> Option<T> {
+        LinkedList::pop_front(self)
+    }
+
+    /// Removes an element by index, or returns error if index out of bounds.
+    ///
+    /// **Efficiency**: O(min(i, n - i))
+    fn remove(&mut self, index: usize) -> Result<T> {
+        let len = LinkedList::len(self);
+        if index >= len {
+            return Err(DSError::IndexOutOfBounds { index, len });
+        }
+        let mut tail = self.split_off(index);
+        let payload = tail.pop_front();
+        self.append(&mut tail);
+        payload.ok_or(DSError::IndexOutOfBounds { index, len })
+    }
+
+    /// Removes all elements.
+    ///
+    /// **Efficiency**: O(n)
+    fn clear(&mut self) {
+        LinkedList::clear(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::list_conformance_tests!(vec_conformance, Vec::new);
+    crate::list_conformance_tests!(vec_deque_conformance, VecDeque::new);
+    crate::list_conformance_tests!(linked_list_conformance, LinkedList::new);
+
+    // Generic code must behave the same for all implementations
+    fn drain_odd<L: List<u32>>(list: &mut L) -> Vec<u32> {
+        let mut removed = Vec::new();
+        let mut index = 0;
+        while index < list.len() {
+            if list.get(index).is_ok_and(|value| value % 2 == 1) {
+                removed.push(list.remove(index).unwrap());
+            } else {
+                index += 1;
+            }
+        }
+        removed
+    }
+
+    #[test]
+    fn test_generic_code() {
+        let mut vec: Vec<u32> = (0..10).collect();
+        let mut deque: VecDeque<u32> = (0..10).collect();
+        let mut list: LinkedList<u32> = (0..10).collect();
+        let mut singly: crate::list::SinglyLinkedList<u32> = (0..10).collect();
+
+        let expected = drain_odd(&mut singly);
+        assert_eq!(expected, [1, 3, 5, 7, 9]);
+        assert_eq!(drain_odd(&mut vec), expected);
+        assert_eq!(drain_odd(&mut deque), expected);
+        assert_eq!(drain_odd(&mut list), expected);
+        assert_eq!(vec, [0, 2, 4, 6, 8]);
+        assert_eq!(deque, [0, 2, 4, 6, 8]);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), [0, 2, 4, 6, 8]);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut list: LinkedList<u32> = (0..5).collect();
+        assert_eq!(List::remove(&mut list, 0), Ok(0));
+        assert_eq!(List::remove(&mut list, 3), Ok(4), "remove the last element");
+        assert_eq!(List::remove(&mut list, 1), Ok(2), "remove from the middle");
+        assert_eq!(
+            List::remove(&mut list, 2),
+            Err(DSError::IndexOutOfBounds { index: 2, len: 2 })
+        );
+        assert_eq!(LinkedList::iter(&list).copied().collect::<Vec<_>>(), [1, 3]);
+
+        let mut vec = vec![1, 2];
+        assert_eq!(List::remove(&mut vec, 5), Err(DSError::IndexOutOfBounds { index: 5, len: 2 }));
+        assert_eq!(List::pop_front(&mut vec), Some(1));
+        assert_eq!(List::pop_front(&mut vec), Some(2));
+        assert_eq!(List::pop_front(&mut vec), None);
+    }
+
+    #[test]
+    fn test_get() {
+        let mut deque: VecDeque<u32> = VecDeque::new();
+        deque.push_back(2);
+        deque.push_front(1);
+        assert_eq!(List::get(&deque, 1), Ok(&2));
+        assert_eq!(List::get(&deque, 2), Err(DSError::IndexOutOfBounds { index: 2, len: 2 }));
+        *List::get_mut(&mut deque, 0).unwrap() = 7;
+        assert_eq!(List::head(&deque), Some(&7));
+
+        let vec = vec![3, 4];
+        assert_eq!(List::get(&vec, 1), Ok(&4));
+        assert_eq!(List::last(&vec), Some(&4));
+    }
+}
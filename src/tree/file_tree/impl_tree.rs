@@ -2,9 +2,9 @@
 
 use std::path::{Component, Path, PathBuf};
 
-use crate::core::utils;
+use crate::core::{utils, debug_check_invariants};
 use super::node::DirNode;
-use crate::{DSError, Result};
+use crate::{DSError, InvariantViolation, Result};
 
 /// `FileTree` is a specialized data structure for compactly storing in memory hierarchical
 /// structure of files and directories. It also provides fast search and access to data.
@@ -125,6 +125,7 @@ impl FileTree {
         // Create all necessary directories
         let _ = self.ensure_dirs(path);
 
+        debug_check_invariants!(self);
         Ok(())
     }
 
@@ -162,6 +163,7 @@ impl FileTree {
             }
         }
 
+        debug_check_invariants!(self);
         Ok(())
     }
 
@@ -190,14 +192,15 @@ impl FileTree {
         let parent = self.find_dir(dir)?;
 
         // Second pass: remove the file from the parent directory
-        if !parent.files_contains(&file_name) {
+        if !parent.files_contains(file_name) {
             // File doesn't exist — return error
             return Err(DSError::PathNotFound {
                 path: path.to_owned(),
             });
         }
-        parent.remove_file(&file_name);
+        parent.remove_file(file_name);
 
+        debug_check_invariants!(self);
         Ok(())
     }
 
@@ -234,6 +237,7 @@ impl FileTree {
         }
         parent.remove_dir(child);
 
+        debug_check_invariants!(self);
         Ok(())
     }
 
@@ -242,6 +246,17 @@ impl FileTree {
     /// **Efficiency**: O(1)
     pub fn clear(&mut self) {
         self.root.clear();
+        debug_check_invariants!(self);
+    }
+
+    /// Checks the internal structure of the tree: the containers of directory entries
+    /// are allocated only while they are not empty, and every entry name is a single
+    /// normal path component.
+    /// With the `check-invariants` feature, it runs after every mutation in debug builds.
+    ///
+    /// **Efficiency**: O(n), where `n` is a number of entries in the tree.
+    pub fn check_invariants(&self) -> std::result::Result<(), InvariantViolation> {
+        self.root.check_invariants(Path::new("/"))
     }
 
     /// Visits all leaf elements in the tree and performs a `visitor` for each of them.
@@ -291,10 +306,11 @@ impl FileTree {
         }
 
         // Second pass: checks the file in the last directory
-        if let Some(file_name) = file_name && is_found {
-            if !current.files_contains(file_name) {
-                is_found = false;
-            }
+        if let Some(file_name) = file_name
+            && is_found
+            && !current.files_contains(file_name)
+        {
+            is_found = false;
         }
 
         is_found
@@ -335,6 +351,7 @@ impl FileTree {
     }
 
     /// Helper method to build a string path from components for error reporting.
+    #[allow(dead_code)]
     fn build_path(&self, components: &[Component<'_>]) -> PathBuf {
         let mut pb = PathBuf::from("/");
         for component in components {
@@ -344,6 +361,12 @@ impl FileTree {
     }
 }
 
+impl Default for FileTree {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1251,4 +1274,29 @@ mod tests {
             );
         }
     }
+
+    mod invariants {
+        use super::*;
+
+        #[test]
+        fn test_invariants_after_mutations() {
+            let mut tree = FileTree::new();
+            tree.add_file("/a/b/file1.txt").unwrap();
+            tree.add_file("/a/file2.txt").unwrap();
+            tree.add_dir("/c/d").unwrap();
+            assert_eq!(tree.check_invariants(), Ok(()));
+
+            tree.remove_file("/a/b/file1.txt").unwrap();
+            tree.remove_file("/a/file2.txt").unwrap();
+            tree.remove_dir("/c/d").unwrap();
+            assert_eq!(tree.check_invariants(), Ok(()));
+            assert_eq!(tree.contains_dir("/a/b"), Ok(true), "empty directories must stay");
+
+            tree.clear();
+            assert_eq!(tree.check_invariants(), Ok(()));
+            assert!(tree.is_empty());
+            tree.add_file("/e/file3.txt").unwrap();
+            assert_eq!(tree.contains_file("/e/file3.txt"), Ok(true), "tree must be usable after clear()");
+        }
+    }
 }
@@ -1,7 +1,9 @@
 use std::collections::{btree_map, btree_set, BTreeMap, BTreeSet};
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use std::{alloc, ptr};
 
+use crate::core::InvariantViolation;
+
 #[derive(Debug, Default)]
 pub struct DirNode {
     dirs: *mut BTreeMap<String, DirNode>,
@@ -58,7 +60,7 @@ impl DirNode {
         }
     }
 
-    pub fn get_dir_mut(&self, name: &str) -> Option<&mut DirNode> {
+    pub fn get_dir_mut(&mut self, name: &str) -> Option<&mut DirNode> {
         if self.dirs.is_null() {
             None
         } else {
@@ -79,9 +81,7 @@ impl DirNode {
         }
         let name = name.into();
         unsafe {
-            if !(*self.dirs).contains_key(&name) {
-                (*self.dirs).insert(name, DirNode::new());
-            }
+            (*self.dirs).entry(name).or_default();
         }
     }
 
@@ -89,6 +89,9 @@ impl DirNode {
         if !self.files.is_null() {
             unsafe {
                 (*self.files).remove(name);
+                if (*self.files).is_empty() {
+                    Self::free(&mut self.files);
+                }
             }
         }
     }
@@ -97,6 +100,9 @@ impl DirNode {
         if !self.dirs.is_null() {
             unsafe {
                 (*self.dirs).remove(name);
+                if (*self.dirs).is_empty() {
+                    Self::free(&mut self.dirs);
+                }
             }
         }
     }
@@ -125,11 +131,41 @@ impl DirNode {
     }
 
     pub fn clear(&mut self) {
-        if !self.dirs.is_null() {
-            unsafe { (*self.dirs).clear() };
+        Self::free(&mut self.dirs);
+        Self::free(&mut self.files);
+    }
+
+    /// Checks the internal structure of the directory `path` and all its subdirectories:
+    /// the containers of files and subdirectories are allocated only while they are not empty,
+    /// and every name is a single normal path component.
+    ///
+    /// **Efficiency**: O(n), where `n` is a number of entries in the subtree.
+    pub fn check_invariants(&self, path: &Path) -> Result<(), InvariantViolation> {
+        let is_allocated_empty = (!self.dirs.is_null() && unsafe { (*self.dirs).is_empty() })
+            || (!self.files.is_null() && unsafe { (*self.files).is_empty() });
+        if is_allocated_empty {
+            return Err(InvariantViolation::EmptyAllocated {
+                path: path.to_owned(),
+            });
         }
-        if !self.files.is_null() {
-            unsafe { (*self.files).clear() };
+
+        for name in self.files_iter() {
+            Self::check_name(path, name)?;
+        }
+        for (name, dir) in self.dirs_iter() {
+            Self::check_name(path, name)?;
+            dir.check_invariants(&path.join(name))?;
+        }
+        Ok(())
+    }
+
+    fn check_name(parent: &Path, name: &str) -> Result<(), InvariantViolation> {
+        let mut components = Path::new(name).components();
+        match (components.next(), components.next()) {
+            (Some(Component::Normal(component)), None) if component == name => Ok(()),
+            _ => Err(InvariantViolation::InvalidName {
+                path: parent.join(name),
+            }),
         }
     }
 
@@ -139,6 +175,13 @@ impl DirNode {
         ptr
         //Box::into_raw(Box::new(value))
     }
+
+    fn free<T>(ptr: &mut *mut T) {
+        if !ptr.is_null() {
+            let _ = unsafe { Box::from_raw(*ptr) };
+            *ptr = ptr::null_mut();
+        }
+    }
 }
 
 pub struct Iter<'a> {
@@ -159,12 +202,7 @@ impl<'a> Iter<'a> {
 
 impl Drop for DirNode {
     fn drop(&mut self) {
-        if !self.dirs.is_null() {
-            let _ = unsafe { Box::from_raw(self.dirs) };
-        }
-        if !self.files.is_null() {
-            let _ = unsafe { Box::from_raw(self.files) };
-        }
+        self.clear();
     }
 }
 
@@ -173,13 +211,11 @@ impl<'a> Iterator for Iter<'a> {
 
     fn next(&mut self) -> Option<Self::Item> {
         if let Some((path, _)) = self.iter_dirs.next() {
-            return Some(self.parent.join(Path::new(path)));
+            Some(self.parent.join(Path::new(path)))
         } else {
-            if let Some(path) = self.iter_files.next() {
-                return Some(self.parent.join(Path::new(path)));
-            } else {
-                None
-            }
+            self.iter_files
+                .next()
+                .map(|path| self.parent.join(Path::new(path)))
         }
     }
 }
@@ -192,4 +228,55 @@ mod tests {
     fn test_node() {
         println!("DirNode = {}", size_of::<DirNode>());
     }
+
+    #[test]
+    fn test_invariants() {
+        let root = Path::new("/");
+        let mut node = DirNode::new();
+        assert_eq!(node.check_invariants(root), Ok(()));
+
+        node.insert_dir("dir");
+        node.get_dir_mut("dir").unwrap().insert_file("file");
+        node.insert_file("file");
+        assert_eq!(node.check_invariants(root), Ok(()));
+
+        node.remove_file("file");
+        assert!(node.files.is_null(), "empty container of files must be freed");
+        node.get_dir_mut("dir").unwrap().remove_file("file");
+        assert_eq!(node.check_invariants(root), Ok(()));
+        node.remove_dir("dir");
+        assert!(node.dirs.is_null(), "empty container of directories must be freed");
+    }
+
+    #[test]
+    fn test_invariants_detect_corruption() {
+        let root = Path::new("/");
+        let mut node = DirNode::new();
+        node.insert_dir("dir");
+        node.get_dir_mut("dir").unwrap().files = DirNode::alloc(BTreeSet::new());
+        assert_eq!(
+            node.check_invariants(root),
+            Err(InvariantViolation::EmptyAllocated {
+                path: PathBuf::from("/dir")
+            })
+        );
+
+        let mut node = DirNode::new();
+        node.insert_file("a/b");
+        assert_eq!(
+            node.check_invariants(root),
+            Err(InvariantViolation::InvalidName {
+                path: PathBuf::from("/a/b")
+            })
+        );
+
+        let mut node = DirNode::new();
+        node.insert_dir("..");
+        assert_eq!(
+            node.check_invariants(root),
+            Err(InvariantViolation::InvalidName {
+                path: PathBuf::from("/..")
+            })
+        );
+    }
 }
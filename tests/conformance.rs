@@ -0,0 +1,6 @@
+#![cfg(feature = "testing")]
+
+use plain_ds::{SinglyLinkedList, SortedList, list_conformance_tests};
+
+list_conformance_tests!(singly_linked, SinglyLinkedList::new);
+list_conformance_tests!(sorted, SortedList::new);